@@ -54,7 +54,7 @@ use crate::observed_attesters::{
     ObservedAggregators, ObservedAttesters, ObservedSyncAggregators, ObservedSyncContributors,
 };
 use crate::observed_block_producers::ObservedBlockProducers;
-use crate::observed_data_sidecars::ObservedDataSidecars;
+use crate::observed_data_sidecars::{ObservedDataSidecars, PersistedObservedSidecars};
 use crate::observed_operations::{ObservationOutcome, ObservedOperations};
 use crate::observed_slashable::ObservedSlashable;
 use crate::persisted_beacon_chain::{PersistedBeaconChain, DUMMY_CANONICAL_HEAD_BLOCK_ROOT};
@@ -140,6 +140,7 @@ pub const BEACON_CHAIN_DB_KEY: Hash256 = Hash256::ZERO;
 pub const OP_POOL_DB_KEY: Hash256 = Hash256::ZERO;
 pub const ETH1_CACHE_DB_KEY: Hash256 = Hash256::ZERO;
 pub const FORK_CHOICE_DB_KEY: Hash256 = Hash256::ZERO;
+pub const OBSERVED_SIDECARS_DB_KEY: Hash256 = Hash256::ZERO;
 
 /// Defines how old a block can be before it's no longer a candidate for the early attester cache.
 const EARLY_ATTESTER_CACHE_HISTORIC_SLOTS: u64 = 4;
@@ -720,6 +721,19 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(())
     }
 
+    /// Persists the observed blob and data column sidecar caches to disk.
+    pub fn persist_observed_sidecars(&self) -> Result<(), Error> {
+        let _timer = metrics::start_timer(&metrics::PERSIST_OBSERVED_SIDECARS);
+
+        let persisted = PersistedObservedSidecars {
+            blob_sidecars: self.observed_blob_sidecars.read().to_persisted(),
+            column_sidecars: self.observed_column_sidecars.read().to_persisted(),
+        };
+        self.store.put_item(&OBSERVED_SIDECARS_DB_KEY, &persisted)?;
+
+        Ok(())
+    }
+
     /// Returns the slot _right now_ according to `self.slot_clock`. Returns `Err` if the slot is
     /// unavailable.
     ///
@@ -4072,6 +4086,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self.import_block_update_metrics_and_events(
             block,
             block_root,
+            &consensus_context,
             block_time_imported,
             payload_verification_status,
             current_slot,
@@ -4368,10 +4383,35 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         &self,
         block: BeaconBlockRef<T::EthSpec>,
         block_root: Hash256,
+        ctxt: &ConsensusContext<T::EthSpec>,
         block_time_imported: Duration,
         payload_verification_status: PayloadVerificationStatus,
         current_slot: Slot,
     ) {
+        for outcome in &ctxt.deposit_outcomes {
+            metrics::inc_counter_vec(&metrics::DEPOSITS_PROCESSED_TOTAL, &[outcome.kind()]);
+        }
+        for outcome in &ctxt.consolidation_request_outcomes {
+            metrics::inc_counter_vec(&metrics::CONSOLIDATION_REQUESTS_TOTAL, &[outcome.kind()]);
+        }
+        for &(operation, count) in &ctxt.operation_signature_counts {
+            metrics::inc_counter_vec_by(
+                &metrics::OPERATION_SIGNATURE_VERIFICATIONS,
+                &[operation],
+                count,
+            );
+        }
+        let verified_deposits = ctxt
+            .deposit_outcomes
+            .iter()
+            .filter(|outcome| outcome.signature_verified())
+            .count();
+        metrics::inc_counter_vec_by(
+            &metrics::OPERATION_SIGNATURE_VERIFICATIONS,
+            &["deposit"],
+            verified_deposits as u64,
+        );
+
         // Only present some metrics for blocks from the previous epoch or later.
         //
         // This helps avoid noise in the metrics during sync.
@@ -7287,7 +7327,11 @@ impl<T: BeaconChainTypes> Drop for BeaconChain<T> {
         let drop = || -> Result<(), Error> {
             self.persist_head_and_fork_choice()?;
             self.persist_op_pool()?;
-            self.persist_eth1_cache()
+            self.persist_eth1_cache()?;
+            if self.config.persist_observed_sidecars {
+                self.persist_observed_sidecars()?;
+            }
+            Ok(())
         };
 
         if let Err(e) = drop() {
@@ -1,11 +1,17 @@
 use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
-use eth2::lighthouse::{AttestationRewards, BlockReward, BlockRewardMeta};
+use eth2::lighthouse::{
+    AttestationRewardBreakdown, AttestationRewards, BlockReward, BlockRewardMeta, UniqueAttestation,
+};
 use operation_pool::{AttMaxCover, MaxCover, RewardCache, SplitAttestation};
 use state_processing::{
     common::get_attesting_indices_from_state,
     per_block_processing::altair::sync_committee::compute_sync_aggregate_rewards,
 };
-use types::{AbstractExecPayload, BeaconBlockRef, BeaconState, EthSpec, Hash256};
+use std::collections::HashMap;
+use types::{
+    AbstractExecPayload, AttestationRef, BeaconBlockRef, BeaconState, BeaconStateError, EthSpec,
+    Hash256,
+};
 
 impl<T: BeaconChainTypes> BeaconChain<T> {
     pub fn compute_block_reward<Payload: AbstractExecPayload<T::EthSpec>>(
@@ -15,6 +21,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         state: &BeaconState<T::EthSpec>,
         reward_cache: &mut RewardCache,
         include_attestations: bool,
+        include_attestation_breakdown: bool,
     ) -> Result<BlockReward, BeaconChainError> {
         if block.slot() != state.slot() {
             return Err(BeaconChainError::BlockRewardSlotError);
@@ -81,7 +88,38 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         let per_attestation_rewards = per_attestation_rewards
             .into_iter()
             .map(|cover| cover.fresh_validators_rewards)
-            .collect();
+            .collect::<Vec<_>>();
+
+        // Attribute the rewards to individual attesters if desired.
+        let per_attestation_breakdown = if include_attestation_breakdown {
+            block
+                .body()
+                .attestations()
+                .zip(&per_attestation_rewards)
+                .map(|(att, rewards)| {
+                    let mut breakdown = unique_attestations(state, att)?
+                        .into_iter()
+                        .filter_map(|(validator_index, attestation)| {
+                            let reward = *rewards.get(&validator_index)?;
+                            Some(AttestationRewardBreakdown {
+                                attestation,
+                                validator_index,
+                                reward,
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    breakdown.sort_unstable_by_key(|b| {
+                        (
+                            b.attestation.committee_index,
+                            b.attestation.committee_position,
+                        )
+                    });
+                    Ok(breakdown)
+                })
+                .collect::<Result<Vec<_>, BeaconChainError>>()?
+        } else {
+            vec![]
+        };
 
         // Add the attestation data if desired.
         let attestations = if include_attestations {
@@ -100,6 +138,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             curr_epoch_total,
             per_attestation_rewards,
             attestations,
+            per_attestation_breakdown,
         };
 
         // Sync committee rewards.
@@ -130,3 +169,43 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         })
     }
 }
+
+/// Map each validator that participated in `att` to its position in its committee.
+fn unique_attestations<E: EthSpec>(
+    state: &BeaconState<E>,
+    att: AttestationRef<'_, E>,
+) -> Result<HashMap<u64, UniqueAttestation>, BeaconStateError> {
+    let slot = att.data().slot;
+    let committee_indices = match att {
+        AttestationRef::Base(att) => vec![att.data.index],
+        AttestationRef::Electra(att) => att.get_committee_indices(),
+    };
+
+    let mut unique_attestations = HashMap::new();
+    // Electra attestations concatenate the aggregation bits of each committee.
+    let mut committee_offset = 0;
+    for committee_index in committee_indices {
+        let committee = state.get_beacon_committee(slot, committee_index)?;
+        for (committee_position, &validator_index) in committee.committee.iter().enumerate() {
+            let voted = match att {
+                AttestationRef::Base(att) => att.aggregation_bits.get(committee_position),
+                AttestationRef::Electra(att) => att
+                    .aggregation_bits
+                    .get(committee_offset + committee_position),
+            };
+            if voted.unwrap_or(false) {
+                unique_attestations.insert(
+                    validator_index as u64,
+                    UniqueAttestation {
+                        slot,
+                        committee_index,
+                        committee_position,
+                    },
+                );
+            }
+        }
+        committee_offset += committee.committee.len();
+    }
+
+    Ok(unique_attestations)
+}
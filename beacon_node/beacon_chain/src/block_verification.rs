@@ -78,10 +78,13 @@ use slog::{debug, error, Logger};
 use slot_clock::SlotClock;
 use ssz::Encode;
 use ssz_derive::{Decode, Encode};
-use state_processing::per_block_processing::{errors::IntoWithIndex, is_merge_transition_block};
+use state_processing::per_block_processing::{
+    errors::{IntoWithIndex, ProposerSlashingInvalid},
+    is_merge_transition_block,
+};
 use state_processing::{
     block_signature_verifier::{BlockSignatureVerifier, Error as BlockSignatureVerifierError},
-    per_block_processing, per_slot_processing,
+    per_block_processing_with_deposit_chunk_size, per_slot_processing,
     state_advance::partial_state_advance,
     AllCaches, BlockProcessingError, BlockSignatureStrategy, ConsensusContext, SlotProcessingError,
     VerifyBlockRoot,
@@ -1599,6 +1602,7 @@ impl<T: BeaconChainTypes> ExecutionPendingBlock<T> {
                     &state,
                     &mut reward_cache,
                     true,
+                    false,
                 )?;
                 event_handler.register(EventKind::BlockReward(block_reward));
             }
@@ -1618,12 +1622,13 @@ impl<T: BeaconChainTypes> ExecutionPendingBlock<T> {
 
         let core_timer = metrics::start_timer(&metrics::BLOCK_PROCESSING_CORE);
 
-        if let Err(err) = per_block_processing(
+        if let Err(err) = per_block_processing_with_deposit_chunk_size(
             &mut state,
             block.as_block(),
             // Signatures were verified earlier in this function.
             BlockSignatureStrategy::NoVerification,
             VerifyBlockRoot::True,
+            chain.config.deposit_verification_chunk_size,
             &mut consensus_context,
             &chain.spec,
         ) {
@@ -1631,6 +1636,19 @@ impl<T: BeaconChainTypes> ExecutionPendingBlock<T> {
                 // Capture `BeaconStateError` so that we can easily distinguish between a block
                 // that's invalid and one that caused an internal error.
                 BlockProcessingError::BeaconStateError(e) => return Err(e.into()),
+                BlockProcessingError::ProposerSlashingInvalid {
+                    index,
+                    reason: ProposerSlashingInvalid::DuplicateInBlock(proposer_index),
+                } => {
+                    debug!(
+                        chain.log,
+                        "Block contains multiple proposer slashings for the same validator";
+                        "block_root" => ?block_root,
+                        "proposer_index" => proposer_index,
+                        "slashing_index" => index,
+                    );
+                    return Err(BlockError::PerBlockProcessingError(err));
+                }
                 other => return Err(BlockError::PerBlockProcessingError(other)),
             }
         };
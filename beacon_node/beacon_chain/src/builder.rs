@@ -1,5 +1,6 @@
 use crate::beacon_chain::{
-    CanonicalHead, LightClientProducerEvent, BEACON_CHAIN_DB_KEY, ETH1_CACHE_DB_KEY, OP_POOL_DB_KEY,
+    CanonicalHead, LightClientProducerEvent, BEACON_CHAIN_DB_KEY, ETH1_CACHE_DB_KEY,
+    OBSERVED_SIDECARS_DB_KEY, OP_POOL_DB_KEY,
 };
 use crate::beacon_proposer_cache::BeaconProposerCache;
 use crate::data_availability_checker::DataAvailabilityChecker;
@@ -12,7 +13,7 @@ use crate::head_tracker::HeadTracker;
 use crate::kzg_utils::blobs_to_data_column_sidecars;
 use crate::light_client_server_cache::LightClientServerCache;
 use crate::migrate::{BackgroundMigrator, MigratorConfig};
-use crate::observed_data_sidecars::ObservedDataSidecars;
+use crate::observed_data_sidecars::{ObservedDataSidecars, PersistedObservedSidecars};
 use crate::persisted_beacon_chain::PersistedBeaconChain;
 use crate::shuffling_cache::{BlockShufflingIds, ShufflingCache};
 use crate::validator_monitor::{ValidatorMonitor, ValidatorMonitorConfig};
@@ -900,6 +901,24 @@ where
         let canonical_head = CanonicalHead::new(fork_choice, Arc::new(head_snapshot));
         let shuffling_cache_size = self.chain_config.shuffling_cache_size;
 
+        let persisted_observed_sidecars = if self.chain_config.persist_observed_sidecars {
+            store
+                .get_item::<PersistedObservedSidecars>(&OBSERVED_SIDECARS_DB_KEY)
+                .map_err(|e| format!("DB error whilst reading observed sidecars: {:?}", e))?
+        } else {
+            None
+        };
+        let (observed_blob_sidecars, observed_column_sidecars) = match persisted_observed_sidecars {
+            Some(persisted) => (
+                ObservedDataSidecars::from_persisted(persisted.blob_sidecars, self.spec.clone()),
+                ObservedDataSidecars::from_persisted(persisted.column_sidecars, self.spec.clone()),
+            ),
+            None => (
+                ObservedDataSidecars::new(self.spec.clone()),
+                ObservedDataSidecars::new(self.spec.clone()),
+            ),
+        };
+
         // Calculate the weak subjectivity point in which to backfill blocks to.
         let genesis_backfill_slot = if self.chain_config.genesis_backfill {
             Slot::new(0)
@@ -957,8 +976,8 @@ where
             observed_sync_aggregators: <_>::default(),
             // TODO: allow for persisting and loading the pool from disk.
             observed_block_producers: <_>::default(),
-            observed_column_sidecars: RwLock::new(ObservedDataSidecars::new(self.spec.clone())),
-            observed_blob_sidecars: RwLock::new(ObservedDataSidecars::new(self.spec.clone())),
+            observed_column_sidecars: RwLock::new(observed_column_sidecars),
+            observed_blob_sidecars: RwLock::new(observed_blob_sidecars),
             observed_slashable: <_>::default(),
             observed_voluntary_exits: <_>::default(),
             observed_proposer_slashings: <_>::default(),
@@ -1,5 +1,7 @@
 pub use proto_array::{DisallowedReOrgOffsets, ReOrgThreshold};
 use serde::{Deserialize, Serialize};
+use state_processing::per_block_processing::process_operations::DEFAULT_DEPOSIT_VERIFICATION_CHUNK_SIZE;
+use std::num::NonZeroUsize;
 use std::time::Duration;
 use types::{Checkpoint, Epoch};
 
@@ -94,6 +96,12 @@ pub struct ChainConfig {
     /// The delay in milliseconds applied by the node between sending each blob or data column batch.
     /// This doesn't apply if the node is the block proposer.
     pub blob_publication_batch_interval: Duration,
+    /// Number of deposits whose merkle proofs are verified by each parallel task during block
+    /// processing.
+    pub deposit_verification_chunk_size: NonZeroUsize,
+    /// Persist the observed blob and data column sidecar caches on shutdown and reload them on
+    /// startup, so that gossip duplicates are still rejected after a restart.
+    pub persist_observed_sidecars: bool,
 }
 
 impl Default for ChainConfig {
@@ -129,6 +137,8 @@ impl Default for ChainConfig {
             enable_sampling: false,
             blob_publication_batches: 4,
             blob_publication_batch_interval: Duration::from_millis(300),
+            deposit_verification_chunk_size: DEFAULT_DEPOSIT_VERIFICATION_CHUNK_SIZE,
+            persist_observed_sidecars: false,
         }
     }
 }
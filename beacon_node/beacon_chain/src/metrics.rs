@@ -244,6 +244,28 @@ pub static BLOCK_SIZE: LazyLock<Result<Histogram>> = LazyLock::new(|| {
         linear_buckets(5120_f64, 5120_f64, 10),
     )
 });
+pub static DEPOSITS_PROCESSED_TOTAL: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "beacon_deposits_processed_total",
+        "Count of deposits in imported blocks, by kind",
+        &["kind"],
+    )
+});
+pub static CONSOLIDATION_REQUESTS_TOTAL: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "beacon_consolidation_requests_total",
+        "Count of consolidation requests in imported blocks, by kind",
+        &["kind"],
+    )
+});
+pub static OPERATION_SIGNATURE_VERIFICATIONS: LazyLock<Result<IntCounterVec>> =
+    LazyLock::new(|| {
+        try_create_int_counter_vec(
+            "beacon_operation_signature_verifications_total",
+            "Count of signatures of operations verified in imported blocks, by operation type",
+            &["operation"],
+        )
+    });
 
 /*
  * Unaggregated Attestation Verification
@@ -631,6 +653,12 @@ pub static PERSIST_ETH1_CACHE: LazyLock<Result<Histogram>> = LazyLock::new(|| {
         "Time taken to persist the eth1 caches",
     )
 });
+pub static PERSIST_OBSERVED_SIDECARS: LazyLock<Result<Histogram>> = LazyLock::new(|| {
+    try_create_histogram(
+        "beacon_persist_observed_sidecars",
+        "Time taken to persist the observed sidecar caches",
+    )
+});
 pub static PERSIST_FORK_CHOICE: LazyLock<Result<Histogram>> = LazyLock::new(|| {
     try_create_histogram(
         "beacon_persist_fork_choice",
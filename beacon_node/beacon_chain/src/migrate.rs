@@ -11,7 +11,7 @@ use std::thread;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use store::hot_cold_store::{migrate_database, HotColdDBError};
 use store::iter::RootsIterator;
-use store::{Error, ItemStore, StoreItem, StoreOp};
+use store::{AnchorInfo, Error, ItemStore, StoreItem, StoreOp};
 pub use store::{HotColdDB, MemoryStore};
 use types::{
     BeaconState, BeaconStateError, BeaconStateHash, Checkpoint, Epoch, EthSpec, FixedBytesExtended,
@@ -119,10 +119,18 @@ pub enum PruningError {
     UnexpectedUnequalStateRoots,
 }
 
+/// Returns `true` if all the historic states up to `end_slot` (inclusive) are stored, or all the
+/// historic states if `end_slot` is `None`.
+pub fn is_reconstructed(anchor: &AnchorInfo, end_slot: Option<Slot>) -> bool {
+    anchor.all_historic_states_stored()
+        || end_slot.is_some_and(|end_slot| anchor.state_lower_limit >= end_slot)
+}
+
 /// Message sent to the migration thread containing the information it needs to run.
 pub enum Notification {
     Finalization(FinalizationNotification),
-    Reconstruction,
+    /// Reconstruct historic states up to the given slot, or all of them if `None`.
+    Reconstruction(Option<Slot>),
     PruneBlobs(Epoch),
 }
 
@@ -191,12 +199,20 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
     }
 
     pub fn process_reconstruction(&self) {
-        if let Some(Notification::Reconstruction) =
-            self.send_background_notification(Notification::Reconstruction)
+        self.process_reconstruction_until(None)
+    }
+
+    /// Reconstruct historic states up to `end_slot` (inclusive), or all of them if `None`.
+    ///
+    /// States are reconstructed in order from the lower limit of the stored historic states, so
+    /// all the states prior to `end_slot` are reconstructed too.
+    pub fn process_reconstruction_until(&self, end_slot: Option<Slot>) {
+        if let Some(Notification::Reconstruction(end_slot)) =
+            self.send_background_notification(Notification::Reconstruction(end_slot))
         {
             // If we are running in foreground mode (as in tests), then this will just run a single
             // batch. We may need to tweak this in future.
-            Self::run_reconstruction(self.db.clone(), None, &self.log);
+            Self::run_reconstruction(self.db.clone(), end_slot, None, &self.log);
         }
     }
 
@@ -210,16 +226,27 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
 
     pub fn run_reconstruction(
         db: Arc<HotColdDB<E, Hot, Cold>>,
+        end_slot: Option<Slot>,
         opt_tx: Option<mpsc::Sender<Notification>>,
         log: &Logger,
     ) {
-        match db.reconstruct_historic_states(Some(BLOCKS_PER_RECONSTRUCTION)) {
+        let anchor = db.get_anchor_info();
+        if is_reconstructed(&anchor, end_slot) {
+            return;
+        }
+        // Don't reconstruct far beyond the requested end slot.
+        let num_blocks = end_slot.map_or(BLOCKS_PER_RECONSTRUCTION, |end_slot| {
+            let remaining = end_slot.saturating_sub(anchor.state_lower_limit).as_usize();
+            std::cmp::min(BLOCKS_PER_RECONSTRUCTION, remaining)
+        });
+
+        match db.reconstruct_historic_states(Some(num_blocks)) {
             Ok(()) => {
                 // Schedule another reconstruction batch if required and we have access to the
                 // channel for requeueing.
                 if let Some(tx) = opt_tx {
-                    if !db.get_anchor_info().all_historic_states_stored() {
-                        if let Err(e) = tx.send(Notification::Reconstruction) {
+                    if !is_reconstructed(&db.get_anchor_info(), end_slot) {
+                        if let Err(e) = tx.send(Notification::Reconstruction(end_slot)) {
                             error!(
                                 log,
                                 "Unable to requeue reconstruction notification";
@@ -424,14 +451,23 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
                 let mut finalization_notif = None;
                 let mut prune_blobs_notif = None;
                 match notif {
-                    Notification::Reconstruction => reconstruction_notif = Some(notif),
+                    Notification::Reconstruction(end_slot) => reconstruction_notif = Some(end_slot),
                     Notification::Finalization(fin) => finalization_notif = Some(fin),
                     Notification::PruneBlobs(dab) => prune_blobs_notif = Some(dab),
                 }
                 // Read the rest of the messages in the channel, taking the best of each type.
                 for notif in rx.try_iter() {
                     match notif {
-                        Notification::Reconstruction => reconstruction_notif = Some(notif),
+                        Notification::Reconstruction(end_slot) => {
+                            // Reconstructing all states (`None`) supersedes any end slot.
+                            reconstruction_notif = Some(match reconstruction_notif {
+                                Some(Some(current)) => {
+                                    end_slot.map(|slot| std::cmp::max(slot, current))
+                                }
+                                Some(None) => None,
+                                None => end_slot,
+                            });
+                        }
                         Notification::Finalization(fin) => {
                             if let Some(current) = finalization_notif.as_mut() {
                                 if fin.finalized_checkpoint.epoch
@@ -457,8 +493,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
                 if let Some(dab) = prune_blobs_notif {
                     Self::run_prune_blobs(db.clone(), dab, &log);
                 }
-                if reconstruction_notif.is_some() {
-                    Self::run_reconstruction(db.clone(), Some(inner_tx.clone()), &log);
+                if let Some(end_slot) = reconstruction_notif {
+                    Self::run_reconstruction(db.clone(), end_slot, Some(inner_tx.clone()), &log);
                 }
             }
         });
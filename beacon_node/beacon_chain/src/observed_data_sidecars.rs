@@ -4,11 +4,21 @@
 //! to this cache to reduce DoS risks.
 
 use crate::observed_block_producers::ProposalKey;
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
 use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
 use std::sync::Arc;
+use store::{DBColumn, Error as StoreError, StoreItem};
 use types::{BlobSidecar, ChainSpec, DataColumnSidecar, EthSpec, Slot};
 
+/// The number of slots beyond the current slot for which sidecars are still accepted when a
+/// current slot ceiling is configured. Allows for small clock disparities between peers.
+pub const FUTURE_SLOT_TOLERANCE: u64 = 1;
+
+/// Returns the current slot, or `None` if it cannot be determined.
+pub type CurrentSlotFn = Arc<dyn Fn() -> Option<Slot> + Send + Sync>;
+
 #[derive(Debug, PartialEq)]
 pub enum Error {
     /// The slot of the provided `ObservableDataSidecar` is prior to finalization and should not have been provided
@@ -18,6 +28,9 @@ pub enum Error {
     /// Note: The invalid data should have been caught and flagged as an error much before reaching
     /// here.
     InvalidDataIndex(u64),
+    /// The slot of the provided `ObservableDataSidecar` is too far beyond the current slot. Such a
+    /// sidecar should have been rejected prior to reaching this function.
+    FutureDataSidecar { slot: Slot, current_slot: Slot },
 }
 
 pub trait ObservableDataSidecar {
@@ -75,6 +88,12 @@ pub struct ObservedDataSidecars<T: ObservableDataSidecar> {
     finalized_slot: Slot,
     /// Stores all received data indices for a given `(ValidatorIndex, Slot)` tuple.
     items: HashMap<ProposalKey, HashSet<u64>>,
+    /// Accept sidecars at or prior to `finalized_slot`.
+    allow_finalized: bool,
+    /// If set, reject sidecars more than `FUTURE_SLOT_TOLERANCE` slots beyond the current slot.
+    current_slot_fn: Option<CurrentSlotFn>,
+    /// Overrides the maximum number of items per proposal derived from `spec`.
+    max_items_override: Option<usize>,
     spec: Arc<ChainSpec>,
     _phantom: PhantomData<T>,
 }
@@ -82,14 +101,47 @@ pub struct ObservedDataSidecars<T: ObservableDataSidecar> {
 impl<T: ObservableDataSidecar> ObservedDataSidecars<T> {
     /// Instantiates `Self` with `finalized_slot == 0`.
     pub fn new(spec: Arc<ChainSpec>) -> Self {
+        Self::new_with_allow_finalized(spec, false)
+    }
+
+    /// Instantiates `Self` with `finalized_slot == 0`.
+    ///
+    /// If `allow_finalized` is `true`, sidecars at or prior to the finalized slot are not rejected.
+    /// This is only intended for tools replaying historical sidecars, gossip verification must not
+    /// use it as it removes a DoS protection.
+    pub fn new_with_allow_finalized(spec: Arc<ChainSpec>, allow_finalized: bool) -> Self {
         Self {
             finalized_slot: Slot::new(0),
             items: HashMap::new(),
+            allow_finalized,
+            current_slot_fn: None,
+            max_items_override: None,
             spec,
             _phantom: PhantomData,
         }
     }
 
+    /// Reject sidecars more than `FUTURE_SLOT_TOLERANCE` slots beyond the slot returned by
+    /// `current_slot_fn`. No ceiling is applied whilst `current_slot_fn` returns `None`.
+    pub fn with_current_slot_fn(mut self, current_slot_fn: CurrentSlotFn) -> Self {
+        self.current_slot_fn = Some(current_slot_fn);
+        self
+    }
+
+    /// Override the maximum number of items per proposal, which is otherwise derived from the
+    /// spec.
+    #[cfg(test)]
+    pub fn with_max_items(mut self, max_items: usize) -> Self {
+        self.max_items_override = Some(max_items);
+        self
+    }
+
+    /// Returns the maximum number of items of a proposal at `slot`.
+    fn max_num_of_items(&self, slot: Slot) -> usize {
+        self.max_items_override
+            .unwrap_or_else(|| T::max_num_of_items(&self.spec, slot))
+    }
+
     /// Observe the `data_sidecar` at (`data_sidecar.block_proposer_index, data_sidecar.slot`).
     /// This will update `self` so future calls to it indicate that this `data_sidecar` is known.
     ///
@@ -103,9 +155,7 @@ impl<T: ObservableDataSidecar> ObservedDataSidecars<T> {
                 slot: data_sidecar.slot(),
                 proposer: data_sidecar.block_proposer_index(),
             })
-            .or_insert_with(|| {
-                HashSet::with_capacity(T::max_num_of_items(&self.spec, data_sidecar.slot()))
-            });
+            .or_insert_with(|| HashSet::with_capacity(self.max_num_of_items(data_sidecar.slot())));
         let did_not_exist = data_indices.insert(data_sidecar.index());
 
         Ok(!did_not_exist)
@@ -124,17 +174,51 @@ impl<T: ObservableDataSidecar> ObservedDataSidecars<T> {
         Ok(is_known)
     }
 
+    /// Returns the observed data indices of the `limit` most recent `(slot, proposer)` tuples,
+    /// ordered by descending slot. The indices of each tuple are sorted in ascending order.
+    ///
+    /// This reflects the sidecars observed on gossip within the prune window, not the sidecars
+    /// stored in the database.
+    pub fn snapshot(&self, limit: usize) -> Vec<(Slot, u64, Vec<u64>)> {
+        let mut proposals = self
+            .items
+            .iter()
+            .map(|(key, indices)| {
+                let mut indices = indices.iter().copied().collect::<Vec<_>>();
+                indices.sort_unstable();
+                (key.slot, key.proposer, indices)
+            })
+            .collect::<Vec<_>>();
+        proposals.sort_unstable_by(|(slot_a, proposer_a, _), (slot_b, proposer_b, _)| {
+            slot_b.cmp(slot_a).then(proposer_a.cmp(proposer_b))
+        });
+        proposals.truncate(limit);
+        proposals
+    }
+
     fn sanitize_data_sidecar(&self, data_sidecar: &T) -> Result<(), Error> {
-        if data_sidecar.index() >= T::max_num_of_items(&self.spec, data_sidecar.slot()) as u64 {
+        if data_sidecar.index() >= self.max_num_of_items(data_sidecar.slot()) as u64 {
             return Err(Error::InvalidDataIndex(data_sidecar.index()));
         }
         let finalized_slot = self.finalized_slot;
-        if finalized_slot > 0 && data_sidecar.slot() <= finalized_slot {
+        if !self.allow_finalized && finalized_slot > 0 && data_sidecar.slot() <= finalized_slot {
             return Err(Error::FinalizedDataSidecar {
                 slot: data_sidecar.slot(),
                 finalized_slot,
             });
         }
+        if let Some(current_slot) = self
+            .current_slot_fn
+            .as_ref()
+            .and_then(|current_slot_fn| current_slot_fn())
+        {
+            if data_sidecar.slot() > current_slot + FUTURE_SLOT_TOLERANCE {
+                return Err(Error::FutureDataSidecar {
+                    slot: data_sidecar.slot(),
+                    current_slot,
+                });
+            }
+        }
 
         Ok(())
     }
@@ -148,6 +232,85 @@ impl<T: ObservableDataSidecar> ObservedDataSidecars<T> {
         self.finalized_slot = finalized_slot;
         self.items.retain(|k, _| k.slot > finalized_slot);
     }
+
+    /// Returns the observations of `self` in a form suitable for persisting to disk.
+    pub fn to_persisted(&self) -> PersistedObservedDataSidecars {
+        PersistedObservedDataSidecars {
+            finalized_slot: self.finalized_slot,
+            proposals: self
+                .items
+                .iter()
+                .map(|(key, indices)| PersistedProposal {
+                    slot: key.slot,
+                    proposer: key.proposer,
+                    indices: indices.iter().copied().collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Instantiates `Self` from observations persisted with `Self::to_persisted`.
+    ///
+    /// Observations at or prior to the persisted finalized slot are discarded, as they would have
+    /// been pruned. Observations with an invalid index are discarded too.
+    pub fn from_persisted(persisted: PersistedObservedDataSidecars, spec: Arc<ChainSpec>) -> Self {
+        let mut cache = Self::new(spec);
+        cache.finalized_slot = persisted.finalized_slot;
+        for proposal in persisted.proposals {
+            if persisted.finalized_slot > 0 && proposal.slot <= persisted.finalized_slot {
+                continue;
+            }
+            let max_num_of_items = cache.max_num_of_items(proposal.slot) as u64;
+            let indices = proposal
+                .indices
+                .into_iter()
+                .filter(|index| *index < max_num_of_items)
+                .collect::<HashSet<_>>();
+            if !indices.is_empty() {
+                cache
+                    .items
+                    .insert(ProposalKey::new(proposal.proposer, proposal.slot), indices);
+            }
+        }
+        cache
+    }
+}
+
+/// The data indices observed for a `(slot, proposer)` tuple, as persisted to disk.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedProposal {
+    pub slot: Slot,
+    pub proposer: u64,
+    pub indices: Vec<u64>,
+}
+
+/// The observations of an `ObservedDataSidecars` cache, as persisted to disk.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedObservedDataSidecars {
+    pub finalized_slot: Slot,
+    pub proposals: Vec<PersistedProposal>,
+}
+
+/// The observed blob and data column sidecar caches, persisted to disk on shutdown so that
+/// duplicates are still rejected after a restart.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedObservedSidecars {
+    pub blob_sidecars: PersistedObservedDataSidecars,
+    pub column_sidecars: PersistedObservedDataSidecars,
+}
+
+impl StoreItem for PersistedObservedSidecars {
+    fn db_column() -> DBColumn {
+        DBColumn::ObservedSidecars
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        Self::from_ssz_bytes(bytes).map_err(Into::into)
+    }
 }
 
 /// Abstraction to control "observation" of gossip messages (currently just blobs and data columns).
@@ -193,6 +356,68 @@ mod tests {
         Arc::new(blob_sidecar)
     }
 
+    #[test]
+    fn persisted_round_trip() {
+        let spec = Arc::new(test_spec::<E>());
+        let mut cache = ObservedDataSidecars::<BlobSidecar<E>>::new(spec.clone());
+        for (slot, proposer_index, index) in [(10, 1, 0), (10, 1, 2), (11, 2, 1), (40, 3, 0)] {
+            cache
+                .observe_sidecar(&get_blob_sidecar(slot, proposer_index, index))
+                .unwrap();
+        }
+        cache.prune(Slot::new(5));
+
+        let persisted = cache.to_persisted();
+        let bytes = PersistedObservedSidecars {
+            blob_sidecars: persisted.clone(),
+            column_sidecars: persisted,
+        }
+        .as_store_bytes();
+        let restored = PersistedObservedSidecars::from_store_bytes(&bytes).unwrap();
+        let restored =
+            ObservedDataSidecars::<BlobSidecar<E>>::from_persisted(restored.blob_sidecars, spec);
+
+        assert_eq!(restored.finalized_slot, cache.finalized_slot);
+        assert_eq!(restored.items, cache.items);
+        assert_eq!(
+            restored.proposer_is_known(&get_blob_sidecar(10, 1, 2)),
+            Ok(true)
+        );
+        assert_eq!(
+            restored.proposer_is_known(&get_blob_sidecar(10, 1, 1)),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn persisted_observations_before_finalized_slot_are_discarded() {
+        let spec = Arc::new(test_spec::<E>());
+        let persisted = PersistedObservedDataSidecars {
+            finalized_slot: Slot::new(32),
+            proposals: vec![
+                PersistedProposal {
+                    slot: Slot::new(32),
+                    proposer: 1,
+                    indices: vec![0],
+                },
+                PersistedProposal {
+                    slot: Slot::new(33),
+                    proposer: 2,
+                    indices: vec![0, 1],
+                },
+            ],
+        };
+
+        let cache = ObservedDataSidecars::<BlobSidecar<E>>::from_persisted(persisted, spec);
+
+        assert_eq!(cache.finalized_slot, 32);
+        assert_eq!(cache.items.len(), 1);
+        assert_eq!(
+            cache.items.get(&ProposalKey::new(2, Slot::new(33))),
+            Some(&HashSet::from([0, 1]))
+        );
+    }
+
     #[test]
     fn pruning() {
         let spec = Arc::new(test_spec::<E>());
@@ -499,4 +724,143 @@ mod tests {
             "cannot add an index > MaxBlobsPerBlock"
         );
     }
+
+    #[test]
+    fn index_bound_can_be_overridden() {
+        let spec = Arc::new(test_spec::<E>());
+        let max_items = 2;
+        let mut cache = ObservedDataSidecars::<BlobSidecar<E>>::new(spec).with_max_items(max_items);
+
+        for index in 0..max_items as u64 {
+            assert_eq!(
+                cache.observe_sidecar(&get_blob_sidecar(0, 1, index)),
+                Ok(false),
+                "index below the override is accepted"
+            );
+        }
+        assert_eq!(
+            cache.observe_sidecar(&get_blob_sidecar(0, 1, max_items as u64)),
+            Err(Error::InvalidDataIndex(max_items as u64)),
+            "index at the override is rejected"
+        );
+        assert_eq!(
+            cache.proposer_is_known(&get_blob_sidecar(0, 1, max_items as u64)),
+            Err(Error::InvalidDataIndex(max_items as u64)),
+            "index at the override is rejected"
+        );
+    }
+
+    #[test]
+    fn finalized_sidecars_only_accepted_if_allowed() {
+        let spec = Arc::new(test_spec::<E>());
+        let finalized_slot = Slot::new(E::slots_per_epoch());
+        let sidecar = get_blob_sidecar(finalized_slot.as_u64(), 420, 0);
+
+        let mut cache = ObservedDataSidecars::<BlobSidecar<E>>::new(spec.clone());
+        cache.prune(finalized_slot);
+        assert_eq!(
+            cache.observe_sidecar(&sidecar),
+            Err(Error::FinalizedDataSidecar {
+                slot: finalized_slot,
+                finalized_slot,
+            }),
+            "finalized sidecar is rejected by default"
+        );
+
+        let mut cache =
+            ObservedDataSidecars::<BlobSidecar<E>>::new_with_allow_finalized(spec.clone(), true);
+        cache.prune(finalized_slot);
+        assert_eq!(
+            cache.observe_sidecar(&sidecar),
+            Ok(false),
+            "finalized sidecar is accepted in permissive mode"
+        );
+        assert_eq!(
+            cache.proposer_is_known(&sidecar),
+            Ok(true),
+            "finalized sidecar is observed in permissive mode"
+        );
+
+        let invalid_index = spec.max_blobs_per_block(finalized_slot.epoch(E::slots_per_epoch()));
+        let invalid_sidecar = get_blob_sidecar(finalized_slot.as_u64(), 420, invalid_index);
+        assert_eq!(
+            cache.observe_sidecar(&invalid_sidecar),
+            Err(Error::InvalidDataIndex(invalid_index)),
+            "index bound is still checked in permissive mode"
+        );
+    }
+
+    #[test]
+    fn future_sidecars_rejected_if_ceiling_set() {
+        let spec = Arc::new(test_spec::<E>());
+        let current_slot = Slot::new(E::slots_per_epoch());
+        let future_slot = current_slot + E::slots_per_epoch();
+        let future_sidecar = get_blob_sidecar(future_slot.as_u64(), 420, 0);
+
+        let mut cache = ObservedDataSidecars::<BlobSidecar<E>>::new(spec.clone());
+        assert_eq!(
+            cache.observe_sidecar(&future_sidecar),
+            Ok(false),
+            "future sidecar is accepted without a ceiling"
+        );
+
+        let mut cache = ObservedDataSidecars::<BlobSidecar<E>>::new(spec)
+            .with_current_slot_fn(Arc::new(move || Some(current_slot)));
+        assert_eq!(
+            cache.observe_sidecar(&future_sidecar),
+            Err(Error::FutureDataSidecar {
+                slot: future_slot,
+                current_slot,
+            }),
+            "future sidecar is rejected"
+        );
+        assert_eq!(
+            cache.proposer_is_known(&future_sidecar),
+            Err(Error::FutureDataSidecar {
+                slot: future_slot,
+                current_slot,
+            }),
+            "future sidecar cannot be queried"
+        );
+        assert_eq!(cache.items.len(), 0, "sidecar was not added");
+
+        let tolerated_slot = current_slot + FUTURE_SLOT_TOLERANCE;
+        let tolerated_sidecar = get_blob_sidecar(tolerated_slot.as_u64(), 420, 0);
+        assert_eq!(
+            cache.observe_sidecar(&tolerated_sidecar),
+            Ok(false),
+            "sidecar within the tolerance is accepted"
+        );
+    }
+
+    #[test]
+    fn snapshot() {
+        let spec = Arc::new(test_spec::<E>());
+        let mut cache = ObservedDataSidecars::<BlobSidecar<E>>::new(spec);
+
+        assert!(
+            cache.snapshot(10).is_empty(),
+            "empty cache has empty snapshot"
+        );
+
+        for (slot, proposer_index, index) in [(1, 421, 1), (1, 421, 0), (2, 420, 0), (3, 419, 2)] {
+            let sidecar = get_blob_sidecar(slot, proposer_index, index);
+            assert_eq!(cache.observe_sidecar(&sidecar), Ok(false));
+        }
+
+        assert_eq!(
+            cache.snapshot(10),
+            vec![
+                (Slot::new(3), 419, vec![2]),
+                (Slot::new(2), 420, vec![0]),
+                (Slot::new(1), 421, vec![0, 1]),
+            ],
+            "snapshot is ordered by descending slot with sorted indices"
+        );
+        assert_eq!(
+            cache.snapshot(2),
+            vec![(Slot::new(3), 419, vec![2]), (Slot::new(2), 420, vec![0])],
+            "snapshot is limited to the most recent proposals"
+        );
+    }
 }
@@ -1488,6 +1488,11 @@ impl<E: EthSpec> BeaconProcessor<E> {
                         &[modified_queue_id.into()],
                         queue_len as f64,
                     );
+                    metrics::set_gauge_vec(
+                        &metrics::BEACON_PROCESSOR_QUEUE_DEPTH,
+                        &[modified_queue_id.into()],
+                        queue_len as i64,
+                    );
                 }
 
                 if aggregate_queue.is_full() && aggregate_debounce.elapsed() {
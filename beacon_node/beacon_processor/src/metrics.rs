@@ -72,6 +72,13 @@ pub static BEACON_PROCESSOR_QUEUE_LENGTH: LazyLock<Result<HistogramVec>> = LazyL
         &["type"],
     )
 });
+pub static BEACON_PROCESSOR_QUEUE_DEPTH: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "beacon_processor_work_event_queue_depth",
+        "Current count of work events in queue waiting to be processed.",
+        &["type"],
+    )
+});
 
 /*
  * Attestation reprocessing queue metrics.
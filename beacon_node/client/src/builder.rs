@@ -837,30 +837,29 @@ where
             None
         };
 
-        let http_metrics_listen_addr = if self.http_metrics_config.enabled {
-            let ctx = Arc::new(http_metrics::Context {
-                config: self.http_metrics_config.clone(),
-                chain: self.beacon_chain.clone(),
-                db_path: self.db_path.clone(),
-                freezer_db_path: self.freezer_db_path.clone(),
-                gossipsub_registry: self.libp2p_registry.take().map(std::sync::Mutex::new),
-                log: log.clone(),
-            });
-
-            let exit = runtime_context.executor.exit();
-
-            let (listen_addr, server) = http_metrics::serve(ctx, exit)
-                .map_err(|e| format!("Unable to start HTTP metrics server: {:?}", e))?;
-
-            runtime_context
-                .executor
-                .spawn_without_exit(server, "http-metrics");
-
-            Some(listen_addr)
-        } else {
-            debug!(log, "Metrics server is disabled");
-            None
-        };
+        let http_metrics_ctx = Arc::new(http_metrics::Context {
+            config: self.http_metrics_config.clone(),
+            chain: self.beacon_chain.clone(),
+            db_path: self.db_path.clone(),
+            freezer_db_path: self.freezer_db_path.clone(),
+            gossipsub_registry: self.libp2p_registry.take().map(std::sync::Mutex::new),
+            previous_samples: <_>::default(),
+            log: log.clone(),
+        });
+        let http_metrics_listen_addr =
+            match http_metrics::serve(http_metrics_ctx, runtime_context.executor.exit()) {
+                Ok((listen_addr, server)) => {
+                    runtime_context
+                        .executor
+                        .spawn_without_exit(server, "http-metrics");
+                    Some(listen_addr)
+                }
+                Err(http_metrics::Error::Disabled) => {
+                    debug!(log, "Metrics server is disabled");
+                    None
+                }
+                Err(e) => return Err(format!("Unable to start HTTP metrics server: {:?}", e)),
+            };
 
         if self.slasher.is_some() {
             self.start_slasher_service()?;
@@ -440,6 +440,7 @@ impl Eth1GenesisService {
                 };
 
                 apply_deposit(&mut state, data, proof, true, spec)
+                    .map(|_| ())
                     .map_err(|e| format!("Error whilst processing deposit: {:?}", e))
             })?;
 
@@ -66,6 +66,7 @@ pub fn get_block_rewards<T: BeaconChainTypes>(
                 state,
                 &mut reward_cache,
                 query.include_attestations,
+                query.include_attestation_breakdown,
             )?;
             block_rewards.push(block_reward);
             Ok(())
@@ -175,6 +176,7 @@ pub fn compute_block_rewards<T: BeaconChainTypes>(
                 state,
                 &mut reward_cache,
                 true,
+                false,
             )
             .map_err(unhandled_error)?;
         block_rewards.push(block_reward);
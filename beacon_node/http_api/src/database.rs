@@ -1,6 +1,8 @@
+use beacon_chain::migrate::is_reconstructed;
 use beacon_chain::store::metadata::CURRENT_SCHEMA_VERSION;
 use beacon_chain::{BeaconChain, BeaconChainTypes};
-use eth2::lighthouse::DatabaseInfo;
+use eth2::lighthouse::{DatabaseInfo, StateReconstructionJob, StateReconstructionRequest};
+use std::cmp;
 use std::sync::Arc;
 
 pub fn info<T: BeaconChainTypes>(
@@ -11,6 +13,10 @@ pub fn info<T: BeaconChainTypes>(
     let config = store.get_config().clone();
     let anchor = store.get_anchor_info();
     let blob_info = store.get_blob_info();
+    let slasher_backend = chain
+        .slasher
+        .as_ref()
+        .map(|slasher| slasher.database_backend().to_string());
 
     Ok(DatabaseInfo {
         schema_version: CURRENT_SCHEMA_VERSION.as_u64(),
@@ -18,5 +24,41 @@ pub fn info<T: BeaconChainTypes>(
         split,
         anchor,
         blob_info,
+        slasher_backend,
+    })
+}
+
+/// Start reconstructing the historic states from `request.start_slot` to `request.end_slot`.
+///
+/// States at or above the split slot are always available, so the range is clamped to it.
+/// Reconstruction is not started if all the states of the range are already stored.
+pub fn reconstruct_range<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    request: StateReconstructionRequest,
+) -> Result<StateReconstructionJob, warp::Rejection> {
+    if request.start_slot > request.end_slot {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "start_slot {} is greater than end_slot {}",
+            request.start_slot, request.end_slot
+        )));
+    }
+
+    let store = &chain.store;
+    let start_slot = request.start_slot;
+    let end_slot = cmp::min(request.end_slot, store.get_split_slot());
+
+    // States at or above the upper limit are stored regardless of reconstruction.
+    let anchor = store.get_anchor_info();
+    if start_slot < anchor.state_upper_limit && !is_reconstructed(&anchor, Some(end_slot)) {
+        // Reconstruction proceeds upwards from the lowest stored state, so any missing states
+        // below `start_slot` will be reconstructed too.
+        chain
+            .store_migrator
+            .process_reconstruction_until(Some(end_slot));
+    }
+
+    Ok(StateReconstructionJob {
+        start_slot,
+        end_slot,
     })
 }
@@ -15,12 +15,16 @@ mod builder_states;
 mod database;
 mod light_client;
 mod metrics;
+mod observed_sidecars;
+mod peer_custody;
+mod pending_queues;
 mod produce_block;
 mod proposer_duties;
 mod publish_attestations;
 mod publish_blocks;
 mod standard_block_rewards;
 mod state_id;
+mod sync_committee_performance;
 mod sync_committee_rewards;
 mod sync_committees;
 mod task_spawner;
@@ -36,7 +40,7 @@ use crate::version::fork_versioned_response;
 use beacon_chain::{
     attestation_verification::VerifiedAttestation, observed_operations::ObservationOutcome,
     validator_monitor::timestamp_now, AttestationError as AttnError, BeaconChain, BeaconChainError,
-    BeaconChainTypes, WhenSlotSkipped,
+    BeaconChainTypes, EngineState, WhenSlotSkipped,
 };
 use beacon_processor::{work_reprocessing_queue::ReprocessQueueMessage, BeaconProcessorSend};
 pub use block_id::BlockId;
@@ -4150,7 +4154,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .then(
-            |request_data: ui::ValidatorMetricsRequestData,
+            |request_data: eth2::lighthouse::ValidatorMetricsRequestData,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>| {
                 task_spawner.blocking_json_task(Priority::P1, move || {
@@ -4189,8 +4193,34 @@ pub fn serve<T: BeaconChainTypes>(
             |task_spawner: TaskSpawner<T::EthSpec>,
              network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
                 task_spawner.blocking_json_task(Priority::P0, move || {
+                    Ok(eth2::lighthouse::SyncingResponse {
+                        data: network_globals.sync_state(),
+                        stall_reason: network_globals.sync_stall_reason(),
+                    })
+                })
+            },
+        );
+
+    // GET lighthouse/sync/health
+    let get_lighthouse_sync_health = warp::path("lighthouse")
+        .and(warp::path("sync"))
+        .and(warp::path("health"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P0, move || {
+                    let sync_state = network_globals.sync_state();
+                    let peers = network_globals.peers.read();
                     Ok(api_types::GenericResponse::from(
-                        network_globals.sync_state(),
+                        eth2::lighthouse::SyncHealth {
+                            sync_state,
+                            connected_peers: peers.connected_peer_ids().count(),
+                            synced_peers: peers.synced_peers().count(),
+                            advanced_peers: peers.advanced_peers().count(),
+                        },
                     ))
                 })
             },
@@ -4236,7 +4266,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("connected"))
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
-        .and(network_globals)
+        .and(network_globals.clone())
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
              network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
@@ -4254,6 +4284,25 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/peers/{peer_id}/custody
+    let get_lighthouse_peer_custody = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path::param::<String>())
+        .and(warp::path("custody"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals)
+        .then(
+            |requested_peer_id: String,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    peer_custody::peer_custody(requested_peer_id, &network_globals)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
     // GET lighthouse/proto_array
     let get_lighthouse_proto_array = warp::path("lighthouse")
         .and(warp::path("proto_array"))
@@ -4431,7 +4480,7 @@ pub fn serve<T: BeaconChainTypes>(
     let post_lighthouse_database_reconstruct = database_path
         .and(warp::path("reconstruct"))
         .and(warp::path::end())
-        .and(not_while_syncing_filter)
+        .and(not_while_syncing_filter.clone())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .then(
@@ -4446,6 +4495,298 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST lighthouse/database/reconstruct_range
+    let post_lighthouse_database_reconstruct_range = database_path
+        .and(warp::path("reconstruct_range"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(not_while_syncing_filter)
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |request: eth2::lighthouse::StateReconstructionRequest,
+             not_synced_filter: Result<(), Rejection>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    not_synced_filter?;
+                    database::reconstruct_range(chain, request)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/sync/decisions
+    let get_lighthouse_sync_decisions = warp::path("lighthouse")
+        .and(warp::path("sync"))
+        .and(warp::path("decisions"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_tx_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    let decisions = dump_sync_decision_log(&network_tx, false).await?;
+                    Ok(
+                        warp::reply::json(&api_types::GenericResponse::from(decisions))
+                            .into_response(),
+                    )
+                })
+            },
+        );
+
+    // POST lighthouse/sync/decisions/reset
+    let post_lighthouse_sync_decisions_reset = warp::path("lighthouse")
+        .and(warp::path("sync"))
+        .and(warp::path("decisions"))
+        .and(warp::path("reset"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_tx_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    let decisions = dump_sync_decision_log(&network_tx, true).await?;
+                    Ok(
+                        warp::reply::json(&api_types::GenericResponse::from(decisions))
+                            .into_response(),
+                    )
+                })
+            },
+        );
+
+    // POST lighthouse/sync/parent_lookup_denylist
+    let post_lighthouse_sync_parent_lookup_denylist = warp::path("lighthouse")
+        .and(warp::path("sync"))
+        .and(warp::path("parent_lookup_denylist"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(network_tx_filter.clone())
+        .then(
+            |roots: Vec<Hash256>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    let (sender, receiver) = oneshot::channel();
+                    publish_network_message(
+                        &network_tx,
+                        NetworkMessage::DenylistSyncParentRoots { roots, sender },
+                    )?;
+                    let denylist = receiver.await.map_err(|_| {
+                        warp_utils::reject::custom_server_error(
+                            "sync service did not respond".to_string(),
+                        )
+                    })?;
+                    Ok(
+                        warp::reply::json(&api_types::GenericResponse::from(denylist))
+                            .into_response(),
+                    )
+                })
+            },
+        );
+
+    // GET lighthouse/sync/lookups/{block_root}
+    let get_lighthouse_sync_lookup =
+        warp::path("lighthouse")
+            .and(warp::path("sync"))
+            .and(warp::path("lookups"))
+            .and(warp::path::param::<Hash256>().or_else(|_| async {
+                Err(warp_utils::reject::custom_bad_request(
+                    "Invalid block root value".to_string(),
+                ))
+            }))
+            .and(warp::path::end())
+            .and(task_spawner_filter.clone())
+            .and(network_tx_filter.clone())
+            .then(
+                |block_root: Hash256,
+                 task_spawner: TaskSpawner<T::EthSpec>,
+                 network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                    task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                        let (tx, rx) = oneshot::channel();
+                        publish_network_message(
+                            &network_tx,
+                            NetworkMessage::GetSyncLookupStatus(block_root, tx),
+                        )?;
+                        let status = rx.await.map_err(|_| {
+                            warp_utils::reject::custom_server_error(
+                                "sync service did not respond".to_string(),
+                            )
+                        })?;
+                        let lookup = status.map(|status| eth2::lighthouse::SyncLookup {
+                            id: status.id,
+                            block_root: status.block_root,
+                            awaiting_parent: status.awaiting_parent,
+                            child_lookups: status.child_lookups,
+                            peer_count: status.peer_count,
+                            block_request_state: status.block_request_state,
+                            component_requests_state: status.component_requests_state,
+                        });
+                        Ok(warp::reply::json(&api_types::GenericResponse::from(lookup))
+                            .into_response())
+                    })
+                },
+            );
+
+    // GET lighthouse/sync/engine_state
+    let get_lighthouse_sync_engine_state = warp::path("lighthouse")
+        .and(warp::path("sync"))
+        .and(warp::path("engine_state"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_tx_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    let (tx, rx) = oneshot::channel();
+                    publish_network_message(&network_tx, NetworkMessage::GetSyncEngineState(tx))?;
+                    let sync_engine_state = rx.await.map_err(|_| {
+                        warp_utils::reject::custom_server_error(
+                            "sync service did not respond".to_string(),
+                        )
+                    })?;
+                    let execution_engine_online = if let Some(el) = &chain.execution_layer {
+                        Some(!el.is_offline_or_erroring().await)
+                    } else {
+                        None
+                    };
+                    let state = eth2::lighthouse::SyncEngineState {
+                        sync_engine_online: sync_engine_state == EngineState::Online,
+                        execution_engine_online,
+                    };
+                    Ok(warp::reply::json(&api_types::GenericResponse::from(state)).into_response())
+                })
+            },
+        );
+
+    let observed_path = warp::path("lighthouse").and(warp::path("observed"));
+
+    // GET lighthouse/observed/blob_sidecars
+    let get_lighthouse_observed_blob_sidecars = observed_path
+        .and(warp::path("blob_sidecars"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    observed_sidecars::blob_sidecars(chain).map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/observed/data_columns
+    let get_lighthouse_observed_data_columns = observed_path
+        .and(warp::path("data_columns"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    observed_sidecars::data_columns(chain).map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    let lighthouse_states_path = warp::path("lighthouse")
+        .and(warp::path("states"))
+        .and(warp::path::param::<StateId>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid state ID".to_string(),
+            ))
+        }))
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone());
+
+    // GET lighthouse/states/{state_id}/pending_queues
+    let get_lighthouse_pending_queues = lighthouse_states_path
+        .clone()
+        .and(warp::path("pending_queues"))
+        .and(warp::path::end())
+        .then(
+            |state_id: StateId,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    pending_queues::pending_queues(state_id, &chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/states/{state_id}/activation_queue
+    let get_lighthouse_activation_queue = lighthouse_states_path
+        .clone()
+        .and(warp::path("activation_queue"))
+        .and(warp::path::end())
+        .and(warp::query::<eth2::lighthouse::ActivationQueueQuery>())
+        .then(
+            |state_id: StateId,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             query: eth2::lighthouse::ActivationQueueQuery| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    pending_queues::activation_queue(state_id, query.validator_id, &chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/states/{state_id}/pending_deposits
+    let get_lighthouse_pending_deposits = lighthouse_states_path
+        .clone()
+        .and(warp::path("pending_deposits"))
+        .and(warp::path::end())
+        .then(
+            |state_id: StateId,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    pending_queues::pending_deposits(state_id, &chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/states/{state_id}/pending_partial_withdrawals
+    let get_lighthouse_pending_partial_withdrawals = lighthouse_states_path
+        .clone()
+        .and(warp::path("pending_partial_withdrawals"))
+        .and(warp::path::end())
+        .then(
+            |state_id: StateId,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    pending_queues::pending_partial_withdrawals(state_id, &chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/states/{state_id}/pending_consolidations
+    let get_lighthouse_pending_consolidations = lighthouse_states_path
+        .clone()
+        .and(warp::path("pending_consolidations"))
+        .and(warp::path::end())
+        .then(
+            |state_id: StateId,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    pending_queues::pending_consolidations(state_id, &chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
     // GET lighthouse/analysis/block_rewards
     let get_lighthouse_block_rewards = warp::path("lighthouse")
         .and(warp::path("analysis"))
@@ -4495,6 +4836,25 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/analysis/sync_committee_performance/{targets}
+    let get_lighthouse_sync_committee_performance = warp::path("lighthouse")
+        .and(warp::path("analysis"))
+        .and(warp::path("sync_committee_performance"))
+        .and(warp::path::param::<String>())
+        .and(warp::query::<eth2::lighthouse::SyncCommitteePerformanceQuery>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |targets, query, task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    sync_committee_performance::get_sync_committee_performance(
+                        targets, query, chain,
+                    )
+                })
+            },
+        );
+
     // GET lighthouse/analysis/block_packing_efficiency
     let get_lighthouse_block_packing_efficiency = warp::path("lighthouse")
         .and(warp::path("analysis"))
@@ -4734,9 +5094,11 @@ pub fn serve<T: BeaconChainTypes>(
                 .uor(get_lighthouse_ui_health)
                 .uor(get_lighthouse_ui_validator_count)
                 .uor(get_lighthouse_syncing)
+                .uor(get_lighthouse_sync_health)
                 .uor(get_lighthouse_nat)
                 .uor(get_lighthouse_peers)
                 .uor(get_lighthouse_peers_connected)
+                .uor(get_lighthouse_peer_custody)
                 .uor(get_lighthouse_proto_array)
                 .uor(get_lighthouse_validator_inclusion_global)
                 .uor(get_lighthouse_validator_inclusion)
@@ -4745,8 +5107,19 @@ pub fn serve<T: BeaconChainTypes>(
                 .uor(get_lighthouse_eth1_deposit_cache)
                 .uor(get_lighthouse_staking)
                 .uor(get_lighthouse_database_info)
+                .uor(get_lighthouse_observed_blob_sidecars)
+                .uor(get_lighthouse_observed_data_columns)
+                .uor(get_lighthouse_sync_decisions)
+                .uor(get_lighthouse_sync_lookup)
+                .uor(get_lighthouse_sync_engine_state)
+                .uor(get_lighthouse_pending_queues)
+                .uor(get_lighthouse_activation_queue)
+                .uor(get_lighthouse_pending_deposits)
+                .uor(get_lighthouse_pending_partial_withdrawals)
+                .uor(get_lighthouse_pending_consolidations)
                 .uor(get_lighthouse_block_rewards)
                 .uor(get_lighthouse_attestation_performance)
+                .uor(get_lighthouse_sync_committee_performance)
                 .uor(
                     enable(ctx.config.enable_light_client_server)
                         .and(get_beacon_light_client_optimistic_update),
@@ -4807,7 +5180,10 @@ pub fn serve<T: BeaconChainTypes>(
                     .uor(post_validator_liveness_epoch)
                     .uor(post_lighthouse_liveness)
                     .uor(post_lighthouse_database_reconstruct)
+                    .uor(post_lighthouse_database_reconstruct_range)
                     .uor(post_lighthouse_block_rewards)
+                    .uor(post_lighthouse_sync_decisions_reset)
+                    .uor(post_lighthouse_sync_parent_lookup_denylist)
                     .uor(post_lighthouse_ui_validator_metrics)
                     .uor(post_lighthouse_ui_validator_info)
                     .recover(warp_utils::reject::handle_rejection),
@@ -4920,3 +5296,25 @@ fn publish_network_message<E: EthSpec>(
         ))
     })
 }
+
+/// Fetch the recent decisions taken by sync, clearing the sync decision log if `reset` is `true`.
+async fn dump_sync_decision_log<E: EthSpec>(
+    network_tx: &UnboundedSender<NetworkMessage<E>>,
+    reset: bool,
+) -> Result<Vec<eth2::lighthouse::SyncDecision>, warp::Rejection> {
+    let (sender, receiver) = oneshot::channel();
+    publish_network_message(
+        network_tx,
+        NetworkMessage::DumpSyncDecisionLog { reset, sender },
+    )?;
+    let entries = receiver.await.map_err(|_| {
+        warp_utils::reject::custom_server_error("sync service did not respond".to_string())
+    })?;
+    Ok(entries
+        .into_iter()
+        .map(|entry| eth2::lighthouse::SyncDecision {
+            timestamp_ms: entry.timestamp.as_millis() as u64,
+            decision: entry.decision.to_string(),
+        })
+        .collect())
+}
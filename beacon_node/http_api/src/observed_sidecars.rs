@@ -0,0 +1,41 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::ObservedSidecars;
+use std::sync::Arc;
+use types::Slot;
+
+/// The maximum number of block proposals returned by the `lighthouse/observed` endpoints.
+///
+/// The gossip observation caches are only pruned upon finalization, so the response is bounded to
+/// the most recent proposals to avoid large responses during periods of non-finality.
+const MAX_OBSERVED_PROPOSALS: usize = 256;
+
+pub fn blob_sidecars<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+) -> Result<Vec<ObservedSidecars>, warp::Rejection> {
+    let snapshot = chain
+        .observed_blob_sidecars
+        .read()
+        .snapshot(MAX_OBSERVED_PROPOSALS);
+    Ok(into_observed_sidecars(snapshot))
+}
+
+pub fn data_columns<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+) -> Result<Vec<ObservedSidecars>, warp::Rejection> {
+    let snapshot = chain
+        .observed_column_sidecars
+        .read()
+        .snapshot(MAX_OBSERVED_PROPOSALS);
+    Ok(into_observed_sidecars(snapshot))
+}
+
+fn into_observed_sidecars(snapshot: Vec<(Slot, u64, Vec<u64>)>) -> Vec<ObservedSidecars> {
+    snapshot
+        .into_iter()
+        .map(|(slot, proposer_index, indices)| ObservedSidecars {
+            slot,
+            proposer_index,
+            indices,
+        })
+        .collect()
+}
@@ -0,0 +1,51 @@
+use eth2::lighthouse::{CustodyGroupCountSource, PeerCustody};
+use lighthouse_network::{Eth2Enr, NetworkGlobals, PeerId};
+use std::str::FromStr;
+use types::{DataColumnSubnetId, EthSpec};
+
+/// Returns the custody group count advertised by `peer_id` and the columns that the node
+/// attributes to it, i.e. the columns for which it is returned by `custody_peers_for_column`.
+pub fn peer_custody<E: EthSpec>(
+    requested_peer_id: String,
+    network_globals: &NetworkGlobals<E>,
+) -> Result<PeerCustody, warp::Rejection> {
+    let peer_id = PeerId::from_str(&requested_peer_id)
+        .map_err(|e| warp_utils::reject::custom_bad_request(format!("invalid peer id: {e}")))?;
+    let spec = &network_globals.spec;
+    let peers = network_globals.peers.read();
+    let peer_info = peers
+        .peer_info(&peer_id)
+        .ok_or_else(|| warp_utils::reject::custom_not_found("peer not found.".to_string()))?;
+
+    // The metadata is more recent than the ENR, and it's what the custody subnets are computed
+    // from.
+    let metadata_custody_group_count = peer_info
+        .meta_data()
+        .and_then(|meta_data| meta_data.custody_group_count().ok().copied());
+    let (custody_group_count, custody_group_count_source) = match metadata_custody_group_count {
+        Some(count) => (Some(count), Some(CustodyGroupCountSource::Metadata)),
+        None => match peer_info
+            .enr()
+            .and_then(|enr| enr.custody_group_count::<E>(spec).ok())
+        {
+            Some(count) => (Some(count), Some(CustodyGroupCountSource::Enr)),
+            None => (None, None),
+        },
+    };
+
+    let custody_columns = (0..spec.number_of_columns)
+        .filter(|&column_index| {
+            peer_info.is_assigned_to_custody_subnet(&DataColumnSubnetId::from_column_index(
+                column_index,
+                spec,
+            ))
+        })
+        .collect();
+
+    Ok(PeerCustody {
+        peer_id: peer_id.to_string(),
+        custody_group_count,
+        custody_group_count_source,
+        custody_columns,
+    })
+}
@@ -0,0 +1,263 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{ActivationQueue, ActivationQueuePosition, PendingQueue, PendingQueues};
+use eth2::types::ValidatorId;
+use types::{
+    BeaconState, ChainSpec, EthSpec, PendingConsolidation, PendingDeposit, PendingPartialWithdrawal,
+};
+
+use crate::StateId;
+
+pub fn pending_queues<T: BeaconChainTypes>(
+    state_id: StateId,
+    chain: &BeaconChain<T>,
+) -> Result<PendingQueues, warp::Rejection> {
+    state_id.map_state_and_execution_optimistic_and_finalized(chain, |state, _, _| {
+        summarize_pending_queues(state, &chain.spec)
+    })
+}
+
+pub fn activation_queue<T: BeaconChainTypes>(
+    state_id: StateId,
+    validator_id: Option<ValidatorId>,
+    chain: &BeaconChain<T>,
+) -> Result<ActivationQueue, warp::Rejection> {
+    state_id.map_state_and_execution_optimistic_and_finalized(chain, |state, _, _| {
+        summarize_activation_queue(state, validator_id.as_ref(), &chain.spec)
+    })
+}
+
+pub fn pending_deposits<T: BeaconChainTypes>(
+    state_id: StateId,
+    chain: &BeaconChain<T>,
+) -> Result<Vec<PendingDeposit>, warp::Rejection> {
+    state_id.map_state_and_execution_optimistic_and_finalized(chain, |state, _, _| {
+        Ok(state
+            .pending_deposits()
+            .map_err(pre_electra)?
+            .iter()
+            .cloned()
+            .collect())
+    })
+}
+
+pub fn pending_partial_withdrawals<T: BeaconChainTypes>(
+    state_id: StateId,
+    chain: &BeaconChain<T>,
+) -> Result<Vec<PendingPartialWithdrawal>, warp::Rejection> {
+    state_id.map_state_and_execution_optimistic_and_finalized(chain, |state, _, _| {
+        Ok(state
+            .pending_partial_withdrawals()
+            .map_err(pre_electra)?
+            .iter()
+            .cloned()
+            .collect())
+    })
+}
+
+pub fn pending_consolidations<T: BeaconChainTypes>(
+    state_id: StateId,
+    chain: &BeaconChain<T>,
+) -> Result<Vec<PendingConsolidation>, warp::Rejection> {
+    state_id.map_state_and_execution_optimistic_and_finalized(chain, |state, _, _| {
+        Ok(state
+            .pending_consolidations()
+            .map_err(pre_electra)?
+            .iter()
+            .cloned()
+            .collect())
+    })
+}
+
+fn summarize_pending_queues<E: EthSpec>(
+    state: &BeaconState<E>,
+    spec: &ChainSpec,
+) -> Result<PendingQueues, warp::Rejection> {
+    let deposits = state.pending_deposits().map_err(pre_electra)?;
+    let partial_withdrawals = state.pending_partial_withdrawals().map_err(pre_electra)?;
+    let consolidations = state.pending_consolidations().map_err(pre_electra)?;
+
+    // Deposits are limited both by the activation churn and by the number of deposits processed
+    // per epoch.
+    let deposits_total = deposits.iter().map(|deposit| deposit.amount).sum::<u64>();
+    let activation_churn = state
+        .get_activation_exit_churn_limit(spec)
+        .map_err(warp_utils::reject::beacon_state_error)?;
+    let deposits_epochs = epochs_to_process(deposits_total, activation_churn).map(|epochs| {
+        epochs.max(
+            epochs_to_process(
+                deposits.len() as u64,
+                E::max_pending_deposits_per_epoch() as u64,
+            )
+            .unwrap_or(0),
+        )
+    });
+
+    // Partial withdrawals are only limited by the number processed per withdrawals sweep, of
+    // which there is one per slot.
+    let withdrawals_total = partial_withdrawals
+        .iter()
+        .map(|withdrawal| withdrawal.amount)
+        .sum::<u64>();
+    let withdrawals_per_epoch = spec
+        .max_pending_partials_per_withdrawals_sweep
+        .saturating_mul(E::slots_per_epoch());
+    let withdrawals_epochs =
+        epochs_to_process(partial_withdrawals.len() as u64, withdrawals_per_epoch);
+
+    // Consolidations move the effective balance of their source validator and are limited by the
+    // consolidation churn.
+    let mut consolidations_total = 0u64;
+    for consolidation in consolidations.iter() {
+        let source = state
+            .get_validator(consolidation.source_index as usize)
+            .map_err(warp_utils::reject::beacon_state_error)?;
+        consolidations_total = consolidations_total.saturating_add(source.effective_balance);
+    }
+    let consolidation_churn = state
+        .get_consolidation_churn_limit(spec)
+        .map_err(warp_utils::reject::beacon_state_error)?;
+    let consolidations_epochs = epochs_to_process(consolidations_total, consolidation_churn);
+
+    Ok(PendingQueues {
+        pending_deposits: PendingQueue {
+            length: deposits.len() as u64,
+            total_gwei: deposits_total,
+            estimated_epochs: deposits_epochs,
+        },
+        pending_partial_withdrawals: PendingQueue {
+            length: partial_withdrawals.len() as u64,
+            total_gwei: withdrawals_total,
+            estimated_epochs: withdrawals_epochs,
+        },
+        pending_consolidations: PendingQueue {
+            length: consolidations.len() as u64,
+            total_gwei: consolidations_total,
+            estimated_epochs: consolidations_epochs,
+        },
+    })
+}
+
+fn summarize_activation_queue<E: EthSpec>(
+    state: &BeaconState<E>,
+    validator_id: Option<&ValidatorId>,
+    spec: &ChainSpec,
+) -> Result<ActivationQueue, warp::Rejection> {
+    let current_epoch = state.current_epoch();
+
+    // The queue is ordered by eligibility epoch and then by index, as in `process_registry_updates`.
+    let mut queue = state
+        .validators()
+        .iter()
+        .enumerate()
+        .filter(|(_, validator)| {
+            validator.activation_eligibility_epoch != spec.far_future_epoch
+                && validator.activation_epoch == spec.far_future_epoch
+        })
+        .map(|(index, validator)| (validator.activation_eligibility_epoch, index as u64))
+        .collect::<Vec<_>>();
+    queue.sort_unstable();
+
+    let validator_index = validator_id
+        .map(|validator_id| {
+            let index = match validator_id {
+                ValidatorId::Index(index) => {
+                    Some(*index).filter(|index| (*index as usize) < state.validators().len())
+                }
+                ValidatorId::PublicKey(pubkey) => state
+                    .validators()
+                    .iter()
+                    .position(|validator| validator.pubkey == *pubkey)
+                    .map(|index| index as u64),
+            };
+            index.ok_or_else(|| {
+                warp_utils::reject::custom_not_found(format!("unknown validator: {validator_id}"))
+            })
+        })
+        .transpose()?;
+    let position = validator_index.and_then(|validator_index| {
+        queue
+            .iter()
+            .position(|(_, index)| *index == validator_index)
+            .map(|position| (validator_index, position as u64))
+    });
+
+    let validator = position
+        .map(|(validator_index, position)| {
+            let (eligibility_epoch, _) = queue[position as usize];
+            // A validator is only activated once its eligibility epoch is finalized.
+            let finalized_epoch = state.finalized_checkpoint().epoch;
+            let first_dequeue_epoch = if eligibility_epoch <= finalized_epoch {
+                current_epoch
+            } else {
+                std::cmp::max(current_epoch, eligibility_epoch + 1)
+            };
+            // Since Electra the churn limits the processing of deposits rather than activations,
+            // all the eligible validators are activated at once.
+            let dequeue_epoch = if state.fork_name_unchecked().electra_enabled() {
+                Some(first_dequeue_epoch)
+            } else {
+                position
+                    .checked_div(activation_churn_limit(state, spec))
+                    .map(|epochs| std::cmp::max(current_epoch + epochs, first_dequeue_epoch))
+            };
+            let estimated_activation_epoch = dequeue_epoch
+                .map(|epoch| spec.compute_activation_exit_epoch(epoch))
+                .transpose()
+                .map_err(|e| {
+                    warp_utils::reject::custom_server_error(format!(
+                        "unable to compute activation epoch: {e:?}"
+                    ))
+                })?;
+            Ok(ActivationQueuePosition {
+                validator_index,
+                position,
+                estimated_activation_epoch,
+            })
+        })
+        .transpose()?;
+
+    Ok(ActivationQueue {
+        length: queue.len() as u64,
+        validator,
+    })
+}
+
+/// Returns the number of validators which may be activated per epoch prior to Electra.
+///
+/// Computed from the active validators rather than from the committee cache, which may not be
+/// built for the requested state.
+fn activation_churn_limit<E: EthSpec>(state: &BeaconState<E>, spec: &ChainSpec) -> u64 {
+    let current_epoch = state.current_epoch();
+    let active_validators = state
+        .validators()
+        .iter()
+        .filter(|validator| validator.is_active_at(current_epoch))
+        .count() as u64;
+    let churn_limit = std::cmp::max(
+        spec.min_per_epoch_churn_limit,
+        active_validators
+            .checked_div(spec.churn_limit_quotient)
+            .unwrap_or(0),
+    );
+    if state.fork_name_unchecked().deneb_enabled() {
+        std::cmp::min(spec.max_per_epoch_activation_churn_limit, churn_limit)
+    } else {
+        churn_limit
+    }
+}
+
+/// Returns the number of epochs required to process `total` at `per_epoch`, or `None` if no
+/// progress can be made.
+fn epochs_to_process(total: u64, per_epoch: u64) -> Option<u64> {
+    if total == 0 {
+        Some(0)
+    } else if per_epoch == 0 {
+        None
+    } else {
+        Some(total.div_ceil(per_epoch))
+    }
+}
+
+fn pre_electra(e: types::BeaconStateError) -> warp::Rejection {
+    warp_utils::reject::custom_bad_request(format!("state has no pending queues: {e:?}"))
+}
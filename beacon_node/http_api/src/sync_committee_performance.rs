@@ -0,0 +1,218 @@
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::{SyncCommitteePerformance, SyncCommitteePerformanceQuery};
+use safe_arith::ArithError;
+use state_processing::{BlockReplayError, BlockReplayer};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use types::{BeaconState, BeaconStateError, BlindedPayload, EthSpec, Hash256, SignedBeaconBlock};
+use warp_utils::reject::{custom_bad_request, custom_server_error, unhandled_error};
+
+const MAX_REQUEST_RANGE_EPOCHS: usize = 100;
+const BLOCK_ROOT_CHUNK_SIZE: usize = 100;
+
+#[derive(Debug)]
+// We don't use the inner values directly, but they're used in the Debug impl.
+enum SyncCommitteePerformanceError {
+    BeaconState(#[allow(dead_code)] BeaconStateError),
+    BlockReplay(#[allow(dead_code)] BlockReplayError),
+    Arith(#[allow(dead_code)] ArithError),
+    MissingCommittee(#[allow(dead_code)] u64),
+}
+
+impl From<BeaconStateError> for SyncCommitteePerformanceError {
+    fn from(e: BeaconStateError) -> Self {
+        Self::BeaconState(e)
+    }
+}
+
+impl From<BlockReplayError> for SyncCommitteePerformanceError {
+    fn from(e: BlockReplayError) -> Self {
+        Self::BlockReplay(e)
+    }
+}
+
+impl From<ArithError> for SyncCommitteePerformanceError {
+    fn from(e: ArithError) -> Self {
+        Self::Arith(e)
+    }
+}
+
+pub fn get_sync_committee_performance<T: BeaconChainTypes>(
+    targets: String,
+    query: SyncCommitteePerformanceQuery,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<Vec<SyncCommitteePerformance>, warp::Rejection> {
+    let spec = &chain.spec;
+    let start_slot = query.start_epoch.start_slot(T::EthSpec::slots_per_epoch());
+    let end_slot = query.end_epoch.end_slot(T::EthSpec::slots_per_epoch());
+
+    // Ensure end_epoch is smaller than the current epoch.
+    let current_epoch = chain.epoch().map_err(unhandled_error)?;
+    if query.end_epoch >= current_epoch {
+        return Err(custom_bad_request(format!(
+            "end_epoch must be less than the current epoch. current: {}, end: {}",
+            current_epoch, query.end_epoch
+        )));
+    }
+
+    // Check query is valid.
+    if query.start_epoch > query.end_epoch {
+        return Err(custom_bad_request(format!(
+            "start_epoch must not be larger than end_epoch. start: {}, end: {}",
+            query.start_epoch, query.end_epoch
+        )));
+    }
+
+    // The response size can grow exceptionally large therefore we should check that the
+    // query is within permitted bounds to prevent potential OOM errors.
+    if (query.end_epoch - query.start_epoch).as_usize() > MAX_REQUEST_RANGE_EPOCHS {
+        return Err(custom_bad_request(format!(
+            "end_epoch must not exceed start_epoch by more than {} epochs. start: {}, end: {}",
+            MAX_REQUEST_RANGE_EPOCHS, query.start_epoch, query.end_epoch
+        )));
+    }
+
+    // Either report on every validator that was in a sync committee during the range, or only on
+    // the specified indices.
+    //
+    // Does no further validation of the indices, so in the event an index was never part of a sync
+    // committee it will be returned without any statistics.
+    let mut perfs: BTreeMap<u64, SyncCommitteePerformance> = BTreeMap::new();
+    let is_global = targets.to_lowercase() == "global";
+    if !is_global {
+        let indices = targets
+            .split(',')
+            .map(|index| {
+                index.trim().parse::<u64>().map_err(|_| {
+                    custom_bad_request(format!("Invalid validator index: {:?}", index))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        perfs.extend(
+            SyncCommitteePerformance::initialize(indices)
+                .into_iter()
+                .map(|perf| (perf.index, perf)),
+        );
+    }
+
+    // Load block roots.
+    let mut block_roots: Vec<Hash256> = chain
+        .forwards_iter_block_roots_until(start_slot, end_slot)
+        .map_err(unhandled_error)?
+        .map(|res| res.map(|(root, _)| root))
+        .collect::<Result<Vec<Hash256>, _>>()
+        .map_err(unhandled_error)?;
+    block_roots.dedup();
+
+    // Load first block so we can get its parent.
+    let first_block_root = block_roots.first().ok_or_else(|| {
+        custom_server_error(
+            "No blocks roots could be loaded. Ensure the beacon node is synced.".to_string(),
+        )
+    })?;
+    let first_block = chain
+        .get_blinded_block(first_block_root)
+        .and_then(|maybe_block| {
+            maybe_block.ok_or(BeaconChainError::MissingBeaconBlock(*first_block_root))
+        })
+        .map_err(unhandled_error)?;
+
+    // Load the state of the parent block which will be used as the starting state.
+    let prior_block = chain
+        .get_blinded_block(&first_block.parent_root())
+        .and_then(|maybe_block| {
+            maybe_block
+                .ok_or_else(|| BeaconChainError::MissingBeaconBlock(first_block.parent_root()))
+        })
+        .map_err(unhandled_error)?;
+
+    let state_root = prior_block.state_root();
+    let state = chain
+        .get_state(&state_root, Some(prior_block.slot()))
+        .and_then(|maybe_state| maybe_state.ok_or(BeaconChainError::MissingBeaconState(state_root)))
+        .map_err(unhandled_error)?;
+
+    // Resolving the committee's pubkeys to indices is expensive, so cache the indices of the
+    // current sync committee for each sync committee period.
+    let mut committee_cache: HashMap<u64, Vec<usize>> = HashMap::new();
+
+    let pre_block_hook = |state: &mut BeaconState<T::EthSpec>,
+                          block: &SignedBeaconBlock<T::EthSpec, BlindedPayload<T::EthSpec>>|
+     -> Result<(), SyncCommitteePerformanceError> {
+        // Blocks prior to Altair do not contain a sync aggregate.
+        let Ok(sync_aggregate) = block.message().body().sync_aggregate() else {
+            return Ok(());
+        };
+
+        let epoch = block.epoch();
+        let period = epoch.sync_committee_period(spec)?;
+        if !committee_cache.contains_key(&period) {
+            let committee = state.current_sync_committee()?.clone();
+            let indices = state.get_sync_committee_indices(&committee)?;
+            committee_cache.insert(period, indices);
+        }
+        let committee_indices = committee_cache
+            .get(&period)
+            .ok_or(SyncCommitteePerformanceError::MissingCommittee(period))?;
+
+        for (position, validator_index) in committee_indices.iter().enumerate() {
+            let index = *validator_index as u64;
+            let perf = if is_global {
+                perfs
+                    .entry(index)
+                    .or_insert_with(|| SyncCommitteePerformance {
+                        index,
+                        ..Default::default()
+                    })
+            } else if let Some(perf) = perfs.get_mut(&index) {
+                perf
+            } else {
+                continue;
+            };
+
+            let stats = perf.epochs.entry(epoch.as_u64()).or_default();
+            stats.in_sync_committee = true;
+            if sync_aggregate
+                .sync_committee_bits
+                .get(position)
+                .map_err(BeaconStateError::from)?
+            {
+                stats.participated += 1;
+            } else {
+                stats.missed += 1;
+            }
+        }
+        Ok(())
+    };
+
+    // Initialize block replayer
+    let mut replayer = BlockReplayer::new(state, spec)
+        .no_state_root_iter()
+        .no_signature_verification()
+        .minimal_block_root_verification()
+        .pre_block_hook(Box::new(pre_block_hook));
+
+    // Iterate through block roots in chunks to reduce load on memory.
+    for block_root_chunks in block_roots.chunks(BLOCK_ROOT_CHUNK_SIZE) {
+        // Load blocks from the block root chunks.
+        let blocks = block_root_chunks
+            .iter()
+            .map(|root| {
+                chain
+                    .get_blinded_block(root)
+                    .and_then(|maybe_block| {
+                        maybe_block.ok_or(BeaconChainError::MissingBeaconBlock(*root))
+                    })
+                    .map_err(unhandled_error)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        replayer = replayer
+            .apply_blocks(blocks, None)
+            .map_err(|e: SyncCommitteePerformanceError| custom_server_error(format!("{:?}", e)))?;
+    }
+
+    drop(replayer);
+
+    Ok(perfs.into_values().collect())
+}
@@ -1,6 +1,7 @@
 use beacon_chain::{
     validator_monitor::HISTORIC_EPOCHS, BeaconChain, BeaconChainError, BeaconChainTypes,
 };
+use eth2::lighthouse::{ValidatorMetrics, ValidatorMetricsRequestData, ValidatorMetricsResponse};
 use eth2::types::{Epoch, ValidatorStatus};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -149,30 +150,6 @@ pub fn get_validator_info<T: BeaconChainTypes>(
     Ok(ValidatorInfoResponse { validators })
 }
 
-#[derive(PartialEq, Serialize, Deserialize)]
-pub struct ValidatorMetricsRequestData {
-    indices: Vec<u64>,
-}
-
-#[derive(PartialEq, Serialize, Deserialize)]
-pub struct ValidatorMetrics {
-    attestation_hits: u64,
-    attestation_misses: u64,
-    attestation_hit_percentage: f64,
-    attestation_head_hits: u64,
-    attestation_head_misses: u64,
-    attestation_head_hit_percentage: f64,
-    attestation_target_hits: u64,
-    attestation_target_misses: u64,
-    attestation_target_hit_percentage: f64,
-    latest_attestation_inclusion_distance: u64,
-}
-
-#[derive(PartialEq, Serialize, Deserialize)]
-pub struct ValidatorMetricsResponse {
-    validators: HashMap<String, ValidatorMetrics>,
-}
-
 pub fn post_validator_monitor_metrics<T: BeaconChainTypes>(
     request_data: ValidatorMetricsRequestData,
     chain: Arc<BeaconChain<T>>,
@@ -1,14 +1,21 @@
 //! Generic tests that make use of the (newer) `InteractiveApiTester`
 use beacon_chain::{
     chain_config::{DisallowedReOrgOffsets, ReOrgThreshold},
-    test_utils::{AttestationStrategy, BlockStrategy, LightClientStrategy, SyncCommitteeStrategy},
+    test_utils::{
+        AttestationStrategy, BlockStrategy, LightClientStrategy, SyncCommitteeStrategy,
+        DEFAULT_ETH1_BLOCK_HASH, HARNESS_GENESIS_TIME,
+    },
     ChainConfig,
 };
 use beacon_processor::work_reprocessing_queue::ReprocessQueueMessage;
 use either::Either;
+use eth2::lighthouse::ActivationQueuePosition;
 use eth2::types::ProduceBlockV3Response;
-use eth2::types::{DepositContractData, StateId};
+use eth2::types::{DepositContractData, StateId, ValidatorId};
+use eth2::StatusCode;
+use execution_layer::test_utils::generate_genesis_header;
 use execution_layer::{ForkchoiceState, PayloadAttributes};
+use genesis::interop_genesis_state;
 use http_api::test_utils::InteractiveTester;
 use parking_lot::Mutex;
 use slot_clock::SlotClock;
@@ -19,6 +26,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use types::{
+    test_utils::{generate_deterministic_keypair, generate_deterministic_keypairs},
     Address, Epoch, EthSpec, ExecPayload, ExecutionBlockHash, FixedBytesExtended, ForkName,
     Hash256, MainnetEthSpec, MinimalEthSpec, ProposerPreparationData, Slot, Uint256,
 };
@@ -957,3 +965,110 @@ async fn queue_attestations_from_http() {
 
     attestation_future.await.unwrap();
 }
+
+// Test the position and estimated activation epoch of validators in the activation queue.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn activation_queue_positions() {
+    type E = MinimalEthSpec;
+    let validator_count = 32;
+    let spec = ForkName::Electra.make_genesis_spec(E::default_spec());
+
+    // Queue the last validators at genesis, the last one being eligible in an unfinalized epoch.
+    let keypairs = generate_deterministic_keypairs(validator_count);
+    let header = generate_genesis_header(&spec, true);
+    let mut genesis_state = interop_genesis_state::<E>(
+        &keypairs,
+        HARNESS_GENESIS_TIME,
+        Hash256::from_slice(DEFAULT_ETH1_BLOCK_HASH),
+        header,
+        &spec,
+    )
+    .unwrap();
+    let queued = [
+        validator_count - 3,
+        validator_count - 2,
+        validator_count - 1,
+    ];
+    for (index, eligibility_epoch) in queued.into_iter().zip([0, 0, 1]) {
+        let validator = genesis_state.get_validator_mut(index).unwrap();
+        validator.activation_eligibility_epoch = Epoch::new(eligibility_epoch);
+        validator.activation_epoch = spec.far_future_epoch;
+    }
+    genesis_state.drop_all_caches().unwrap();
+
+    let tester = InteractiveTester::<E>::new_with_initializer_and_mutator(
+        Some(spec.clone()),
+        validator_count,
+        Some(Box::new(|harness_builder| {
+            harness_builder
+                .keypairs(keypairs)
+                .genesis_state_ephemeral_store(genesis_state)
+        })),
+        None,
+        Default::default(),
+    )
+    .await;
+    let client = &tester.client;
+
+    let queue = client
+        .get_lighthouse_activation_queue(StateId::Head, None)
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(queue.length, queued.len() as u64);
+    assert_eq!(queue.validator, None);
+
+    // Eligible in the finalized genesis epoch, the validator is activated by the first epoch
+    // transition.
+    let position = client
+        .get_lighthouse_activation_queue(StateId::Head, Some(ValidatorId::Index(queued[1] as u64)))
+        .await
+        .unwrap()
+        .data
+        .validator;
+    assert_eq!(
+        position,
+        Some(ActivationQueuePosition {
+            validator_index: queued[1] as u64,
+            position: 1,
+            estimated_activation_epoch: Some(
+                spec.compute_activation_exit_epoch(Epoch::new(0)).unwrap()
+            ),
+        })
+    );
+
+    // Eligible in epoch 1, the validator waits for that epoch to be finalized at the end of epoch 2.
+    let pubkey = generate_deterministic_keypair(queued[2]).pk.compress();
+    let position = client
+        .get_lighthouse_activation_queue(StateId::Head, Some(ValidatorId::PublicKey(pubkey)))
+        .await
+        .unwrap()
+        .data
+        .validator;
+    assert_eq!(
+        position,
+        Some(ActivationQueuePosition {
+            validator_index: queued[2] as u64,
+            position: 2,
+            estimated_activation_epoch: Some(
+                spec.compute_activation_exit_epoch(Epoch::new(2)).unwrap()
+            ),
+        })
+    );
+
+    // Unknown validators are not found.
+    for validator_id in [
+        ValidatorId::Index(validator_count as u64),
+        ValidatorId::PublicKey(
+            generate_deterministic_keypair(validator_count)
+                .pk
+                .compress(),
+        ),
+    ] {
+        let error = client
+            .get_lighthouse_activation_queue(StateId::Head, Some(validator_id))
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+    }
+}
@@ -5,6 +5,7 @@ use beacon_chain::{
 };
 use either::Either;
 use eth2::{
+    lighthouse::ObservedSidecars,
     mixin::{RequestAccept, ResponseForkName, ResponseOptional},
     reqwest::RequestBuilder,
     types::{
@@ -5628,6 +5629,15 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_sync_health(self) -> Self {
+        let health = self.client.get_lighthouse_sync_health().await.unwrap().data;
+
+        assert!(health.synced_peers <= health.connected_peers);
+        assert!(health.advanced_peers <= health.connected_peers);
+
+        self
+    }
+
     pub async fn test_get_lighthouse_proto_array(self) -> Self {
         self.client.get_lighthouse_proto_array().await.unwrap();
 
@@ -5654,6 +5664,28 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_is_validator_slashed(self) -> Self {
+        let epoch = self.chain.epoch().unwrap() - 1;
+        let is_slashed = self
+            .client
+            .is_validator_slashed(epoch, ValidatorId::Index(0))
+            .await
+            .unwrap();
+
+        assert!(!is_slashed);
+
+        let unknown_validator = ValidatorId::Index(self.validator_keypairs().len() as u64);
+        assert!(matches!(
+            self.client
+                .is_validator_slashed(epoch, unknown_validator)
+                .await
+                .unwrap_err(),
+            eth2::Error::UnknownValidator(_)
+        ));
+
+        self
+    }
+
     pub async fn test_get_lighthouse_eth1_syncing(self) -> Self {
         self.client.get_lighthouse_eth1_syncing().await.unwrap();
 
@@ -5697,6 +5729,218 @@ impl ApiTester {
             info.schema_version,
             store::metadata::CURRENT_SCHEMA_VERSION.as_u64()
         );
+        assert_eq!(
+            info.slasher_backend,
+            self.chain
+                .slasher
+                .as_ref()
+                .map(|slasher| slasher.database_backend().to_string())
+        );
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_observed_blob_sidecars(self) -> Self {
+        let slot = self.chain.slot().unwrap();
+        let mut blob_sidecar = BlobSidecar::<E>::empty();
+        blob_sidecar.signed_block_header.message.slot = slot;
+        blob_sidecar.signed_block_header.message.proposer_index = 3;
+        blob_sidecar.index = 1;
+        self.chain
+            .observed_blob_sidecars
+            .write()
+            .observe_sidecar(&blob_sidecar)
+            .unwrap();
+
+        let observed = self
+            .client
+            .get_lighthouse_observed_blob_sidecars()
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(
+            observed.first(),
+            Some(&ObservedSidecars {
+                slot,
+                proposer_index: 3,
+                indices: vec![1],
+            })
+        );
+
+        // Integers are quoted, like in the rest of the API.
+        let url = format!(
+            "{}/lighthouse/observed/blob_sidecars",
+            self.client.as_ref().trim_end_matches('/')
+        );
+        let json = self
+            .client
+            .get_response(url, |builder| builder)
+            .await
+            .unwrap()
+            .json::<serde_json::Value>()
+            .await
+            .unwrap();
+        assert_eq!(json["data"][0]["proposer_index"], "3");
+        assert_eq!(json["data"][0]["indices"], serde_json::json!(["1"]));
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_observed_data_columns(self) -> Self {
+        let slot = self.chain.slot().unwrap();
+        let data_column = DataColumnSidecar::<E> {
+            index: 2,
+            column: VariableList::empty(),
+            kzg_commitments: VariableList::empty(),
+            kzg_proofs: VariableList::empty(),
+            signed_block_header: SignedBeaconBlockHeader {
+                message: BeaconBlockHeader {
+                    slot,
+                    proposer_index: 5,
+                    ..BeaconBlockHeader::empty()
+                },
+                signature: Signature::empty(),
+            },
+            kzg_commitments_inclusion_proof: Default::default(),
+        };
+        self.chain
+            .observed_column_sidecars
+            .write()
+            .observe_sidecar(&data_column)
+            .unwrap();
+
+        let observed = self
+            .client
+            .get_lighthouse_observed_data_columns()
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(
+            observed.first(),
+            Some(&ObservedSidecars {
+                slot,
+                proposer_index: 5,
+                indices: vec![2],
+            })
+        );
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_peer_custody(self) -> Self {
+        // The external peer has advertised neither an ENR nor metadata.
+        let custody = self
+            .client
+            .get_lighthouse_peer_custody(self.external_peer_id)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(
+            custody,
+            eth2::lighthouse::PeerCustody {
+                peer_id: self.external_peer_id.to_string(),
+                custody_group_count: None,
+                custody_group_count_source: None,
+                custody_columns: vec![],
+            }
+        );
+
+        let unknown_peer = self
+            .client
+            .get_lighthouse_peer_custody(PeerId::random())
+            .await
+            .unwrap_err();
+        assert_eq!(unknown_peer.status(), Some(StatusCode::NOT_FOUND));
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_pending_queues(self) -> Self {
+        let state = self.chain.head_beacon_state_cloned();
+        let result = self
+            .client
+            .get_lighthouse_pending_queues(CoreStateId::Head)
+            .await;
+
+        if !state.fork_name_unchecked().electra_enabled() {
+            assert_eq!(result.unwrap_err().status(), Some(StatusCode::BAD_REQUEST));
+            return self;
+        }
+
+        let queues = result.unwrap().data;
+        let pending_deposits = state.pending_deposits().unwrap();
+        assert_eq!(
+            queues.pending_deposits.length,
+            pending_deposits.len() as u64
+        );
+        assert_eq!(
+            queues.pending_deposits.total_gwei,
+            pending_deposits
+                .iter()
+                .map(|deposit| deposit.amount)
+                .sum::<u64>()
+        );
+        assert_eq!(
+            queues.pending_partial_withdrawals.length,
+            state.pending_partial_withdrawals().unwrap().len() as u64
+        );
+        assert_eq!(
+            queues.pending_consolidations.length,
+            state.pending_consolidations().unwrap().len() as u64
+        );
+
+        let deposits = self
+            .client
+            .get_lighthouse_pending_deposits(CoreStateId::Head)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(
+            deposits,
+            pending_deposits.iter().cloned().collect::<Vec<_>>()
+        );
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_activation_queue(self) -> Self {
+        let state = self.chain.head_beacon_state_cloned();
+        let expected_length = state
+            .validators()
+            .iter()
+            .filter(|validator| {
+                validator.activation_eligibility_epoch != self.chain.spec.far_future_epoch
+                    && validator.activation_epoch == self.chain.spec.far_future_epoch
+            })
+            .count() as u64;
+
+        let queue = self
+            .client
+            .get_lighthouse_activation_queue(CoreStateId::Head, None)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(queue.length, expected_length);
+        assert_eq!(queue.validator, None);
+
+        // Validators active since genesis are not in the queue.
+        let validator = state.validators().get(0).unwrap();
+        assert!(validator.is_active_at(state.current_epoch()));
+        for validator_id in [
+            ValidatorId::Index(0),
+            ValidatorId::PublicKey(validator.pubkey),
+        ] {
+            let queue = self
+                .client
+                .get_lighthouse_activation_queue(CoreStateId::Head, Some(validator_id))
+                .await
+                .unwrap()
+                .data;
+            assert_eq!(queue.length, expected_length);
+            assert_eq!(queue.validator, None);
+        }
 
         self
     }
@@ -5711,6 +5955,42 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_lighthouse_database_reconstruct_range(self) -> Self {
+        let split_slot = self.chain.store.get_split_slot();
+
+        // The genesis state is always stored, so nothing is reconstructed.
+        let anchor = self.chain.store.get_anchor_info();
+        let job = self
+            .client
+            .post_lighthouse_database_reconstruct_range(Slot::new(0), Slot::new(0))
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(job.start_slot, Slot::new(0));
+        assert_eq!(job.end_slot, Slot::new(0));
+        assert_eq!(self.chain.store.get_anchor_info(), anchor);
+
+        // The requested start slot is returned as is and the end slot is clamped to the split.
+        let start_slot = Slot::new(1);
+        let job = self
+            .client
+            .post_lighthouse_database_reconstruct_range(start_slot, split_slot + 64)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(job.start_slot, start_slot);
+        assert_eq!(job.end_slot, split_slot);
+
+        // An inverted range is rejected.
+        let result = self
+            .client
+            .post_lighthouse_database_reconstruct_range(Slot::new(2), Slot::new(1))
+            .await;
+        assert_eq!(result.unwrap_err().status(), Some(StatusCode::BAD_REQUEST));
+
+        self
+    }
+
     pub async fn test_post_lighthouse_liveness(self) -> Self {
         let epoch = self.chain.epoch().unwrap();
         let head_state = self.chain.head_beacon_state_cloned();
@@ -6259,6 +6539,20 @@ async fn get_events_electra() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn lighthouse_pending_queues_electra() {
+    let mut config = ApiTesterConfig::default();
+    config.spec.altair_fork_epoch = Some(Epoch::new(0));
+    config.spec.bellatrix_fork_epoch = Some(Epoch::new(0));
+    config.spec.capella_fork_epoch = Some(Epoch::new(0));
+    config.spec.deneb_fork_epoch = Some(Epoch::new(0));
+    config.spec.electra_fork_epoch = Some(Epoch::new(0));
+    ApiTester::new_from_config(config)
+        .await
+        .test_get_lighthouse_pending_queues()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn get_events_from_genesis() {
     ApiTester::new_from_genesis()
@@ -7225,12 +7519,16 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_syncing()
         .await
+        .test_get_lighthouse_sync_health()
+        .await
         .test_get_lighthouse_proto_array()
         .await
         .test_get_lighthouse_validator_inclusion()
         .await
         .test_get_lighthouse_validator_inclusion_global()
         .await
+        .test_is_validator_slashed()
+        .await
         .test_get_lighthouse_eth1_syncing()
         .await
         .test_get_lighthouse_eth1_block_cache()
@@ -7241,8 +7539,20 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_database_info()
         .await
+        .test_get_lighthouse_observed_blob_sidecars()
+        .await
+        .test_get_lighthouse_observed_data_columns()
+        .await
+        .test_get_lighthouse_pending_queues()
+        .await
+        .test_get_lighthouse_activation_queue()
+        .await
+        .test_get_lighthouse_peer_custody()
+        .await
         .test_post_lighthouse_database_reconstruct()
         .await
+        .test_post_lighthouse_database_reconstruct_range()
+        .await
         .test_post_lighthouse_liveness()
         .await;
 }
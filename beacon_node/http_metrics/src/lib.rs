@@ -7,15 +7,18 @@ use beacon_chain::{BeaconChain, BeaconChainTypes};
 use lighthouse_network::prometheus_client::registry::Registry;
 use lighthouse_version::version_with_platform;
 use serde::{Deserialize, Serialize};
-use slog::{crit, info, Logger};
+use slog::{info, Logger};
+use std::collections::HashSet;
 use std::future::Future;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::path::PathBuf;
 use std::sync::Arc;
-use warp::{http::Response, Filter};
+use warp::{http::Response, Filter, Reply};
 
 #[derive(Debug)]
 pub enum Error {
+    /// The server is disabled by `Config::enabled`, so it was not started.
+    Disabled,
     Warp(warp::Error),
     Other(String),
 }
@@ -41,6 +44,9 @@ pub struct Context<T: BeaconChainTypes> {
     pub db_path: Option<PathBuf>,
     pub freezer_db_path: Option<PathBuf>,
     pub gossipsub_registry: Option<std::sync::Mutex<Registry>>,
+    /// The sample lines served by the previous scrape, only used when `Config::delta_mode` is
+    /// enabled.
+    pub previous_samples: std::sync::Mutex<HashSet<String>>,
     pub log: Logger,
 }
 
@@ -52,6 +58,15 @@ pub struct Config {
     pub listen_port: u16,
     pub allow_origin: Option<String>,
     pub allocator_metrics_enabled: bool,
+    /// Experimental: only serve the samples which have changed since the previous scrape.
+    ///
+    /// The resulting exposition is non-standard: unchanged samples are omitted (the `# HELP` and
+    /// `# TYPE` lines are kept for any family with a changed sample) and removed series are not
+    /// reported. The previous scrape is shared between all clients, so this mode is only suitable
+    /// for a single scraper. Responses are marked with the `DELTA_MODE_HEADER` header.
+    pub delta_mode: bool,
+    /// Whether to apply a CORS layer to responses. Disable this when a reverse proxy handles CORS.
+    pub cors_enabled: bool,
 }
 
 impl Default for Config {
@@ -62,10 +77,16 @@ impl Default for Config {
             listen_port: 5054,
             allow_origin: None,
             allocator_metrics_enabled: true,
+            delta_mode: false,
+            cors_enabled: true,
         }
     }
 }
 
+/// Response header which is set when the body only contains the samples changed since the
+/// previous scrape (see `Config::delta_mode`).
+pub const DELTA_MODE_HEADER: &str = "X-Lighthouse-Metrics-Delta";
+
 /// Creates a server that will serve requests using information from `ctx`.
 ///
 /// The server will shut down gracefully when the `shutdown` future resolves.
@@ -80,7 +101,8 @@ impl Default for Config {
 /// ## Errors
 ///
 /// Returns an error if the server is unable to bind or there is another error during
-/// configuration.
+/// configuration. Returns `Error::Disabled` without starting anything if the server is disabled
+/// in the config, so that it can be called regardless of `Config::enabled`.
 pub fn serve<T: BeaconChainTypes>(
     ctx: Arc<Context<T>>,
     shutdown: impl Future<Output = ()> + Send + Sync + 'static,
@@ -88,6 +110,10 @@ pub fn serve<T: BeaconChainTypes>(
     let config = &ctx.config;
     let log = ctx.log.clone();
 
+    if !config.enabled {
+        return Err(Error::Disabled);
+    }
+
     // Configure CORS.
     let cors_builder = {
         let builder = warp::cors()
@@ -101,14 +127,6 @@ pub fn serve<T: BeaconChainTypes>(
         )?
     };
 
-    // Sanity check.
-    if !config.enabled {
-        crit!(log, "Cannot start disabled metrics HTTP server");
-        return Err(Error::Other(
-            "A disabled metrics server should not be started".to_string(),
-        ));
-    }
-
     let inner_ctx = ctx.clone();
     let routes = warp::get()
         .and(warp::path("metrics"))
@@ -117,11 +135,13 @@ pub fn serve<T: BeaconChainTypes>(
             Ok::<_, warp::Rejection>(
                 metrics::gather_prometheus_metrics(&ctx)
                     .map(|body| {
-                        Response::builder()
+                        let mut builder = Response::builder()
                             .status(200)
-                            .header("Content-Type", "text/plain")
-                            .body(body)
-                            .unwrap()
+                            .header("Content-Type", "text/plain");
+                        if ctx.config.delta_mode {
+                            builder = builder.header(DELTA_MODE_HEADER, "true");
+                        }
+                        builder.body(body).unwrap()
                     })
                     .unwrap_or_else(|e| {
                         Response::builder()
@@ -133,8 +153,16 @@ pub fn serve<T: BeaconChainTypes>(
             )
         })
         // Add a `Server` header.
-        .map(|reply| warp::reply::with_header(reply, "Server", &version_with_platform()))
-        .with(cors_builder.build());
+        .map(|reply| warp::reply::with_header(reply, "Server", &version_with_platform()));
+
+    let routes = if config.cors_enabled {
+        routes
+            .with(cors_builder.build())
+            .map(Reply::into_response)
+            .boxed()
+    } else {
+        routes.map(Reply::into_response).boxed()
+    };
 
     let (listening_socket, server) = warp::serve(routes).try_bind_with_graceful_shutdown(
         SocketAddr::new(config.listen_addr, config.listen_port),
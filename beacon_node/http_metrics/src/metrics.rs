@@ -1,12 +1,30 @@
 use crate::Context;
 use beacon_chain::BeaconChainTypes;
+use health_metrics::gather::catch_scrape_panic;
+pub use health_metrics::gather::MetricsGatherError;
 use lighthouse_network::prometheus_client::encoding::text::encode;
 use malloc_utils::scrape_allocator_metrics;
 use metrics::TextEncoder;
+use std::collections::HashSet;
+
+/// Errors returned when gathering the metrics served by the beacon node.
+#[derive(Debug)]
+pub enum GatherError {
+    /// Gathering the metrics failed in a way shared with the validator client.
+    Gather(MetricsGatherError),
+    /// The lock on the samples of the previous scrape was poisoned (see `Config::delta_mode`).
+    PreviousSamplesLockPoisoned,
+}
+
+impl From<MetricsGatherError> for GatherError {
+    fn from(e: MetricsGatherError) -> Self {
+        GatherError::Gather(e)
+    }
+}
 
 pub fn gather_prometheus_metrics<T: BeaconChainTypes>(
     ctx: &Context<T>,
-) -> std::result::Result<String, String> {
+) -> std::result::Result<String, GatherError> {
     let mut buffer = String::new();
     let encoder = TextEncoder::new();
 
@@ -39,17 +57,19 @@ pub fn gather_prometheus_metrics<T: BeaconChainTypes>(
 
     lighthouse_network::scrape_discovery_metrics();
 
-    health_metrics::metrics::scrape_health_metrics();
+    catch_scrape_panic(health_metrics::metrics::scrape_health_metrics)
+        .map_err(MetricsGatherError::HealthScrape)?;
 
     // It's important to ensure these metrics are explicitly enabled in the case that users aren't
     // using glibc and this function causes panics.
     if ctx.config.allocator_metrics_enabled {
-        scrape_allocator_metrics();
+        catch_scrape_panic(scrape_allocator_metrics)
+            .map_err(MetricsGatherError::AllocatorScrape)?;
     }
 
     encoder
         .encode_utf8(&metrics::gather(), &mut buffer)
-        .unwrap();
+        .map_err(|e| MetricsGatherError::Encode(format!("{e:?}")))?;
     // encode gossipsub metrics also if they exist
     if let Some(registry) = ctx.gossipsub_registry.as_ref() {
         if let Ok(registry_locked) = registry.lock() {
@@ -57,5 +77,50 @@ pub fn gather_prometheus_metrics<T: BeaconChainTypes>(
         }
     }
 
+    if ctx.config.delta_mode {
+        let mut previous_samples = ctx
+            .previous_samples
+            .lock()
+            .map_err(|_| GatherError::PreviousSamplesLockPoisoned)?;
+        return Ok(delta_exposition(&buffer, &mut previous_samples));
+    }
+
     Ok(buffer)
 }
+
+/// Returns the lines of `exposition` which were not present in `previous_samples`, along with the
+/// `# HELP` and `# TYPE` lines of their families. `previous_samples` is updated to contain all the
+/// sample lines of `exposition`.
+fn delta_exposition(exposition: &str, previous_samples: &mut HashSet<String>) -> String {
+    let mut output = String::new();
+    let mut current_samples = HashSet::new();
+    // The comments of the current family which have not been emitted yet.
+    let mut pending_comments = vec![];
+    let mut in_samples = false;
+
+    for line in exposition.lines() {
+        if line.starts_with('#') {
+            // Comments following samples belong to a new family.
+            if in_samples {
+                pending_comments.clear();
+                in_samples = false;
+            }
+            pending_comments.push(line);
+            continue;
+        }
+
+        in_samples = true;
+        if !previous_samples.contains(line) {
+            for comment in pending_comments.drain(..) {
+                output.push_str(comment);
+                output.push('\n');
+            }
+            output.push_str(line);
+            output.push('\n');
+        }
+        current_samples.insert(line.to_string());
+    }
+
+    *previous_samples = current_samples;
+    output
+}
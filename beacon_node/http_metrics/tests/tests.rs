@@ -22,11 +22,14 @@ async fn returns_200_ok() {
                 listen_port: 0,
                 allow_origin: None,
                 allocator_metrics_enabled: true,
+                delta_mode: false,
+                cors_enabled: true,
             },
             chain: None,
             db_path: None,
             freezer_db_path: None,
             gossipsub_registry: None,
+            previous_samples: <_>::default(),
             log,
         });
 
@@ -56,3 +59,186 @@ async fn returns_200_ok() {
     }
     .await
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn delta_mode_omits_unchanged_samples() {
+    async {
+        let log = test_logger();
+
+        let context = Arc::new(Context {
+            config: Config {
+                enabled: true,
+                listen_addr: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+                listen_port: 0,
+                allow_origin: None,
+                allocator_metrics_enabled: true,
+                delta_mode: true,
+                cors_enabled: true,
+            },
+            chain: None,
+            db_path: None,
+            freezer_db_path: None,
+            gossipsub_registry: None,
+            previous_samples: <_>::default(),
+            log,
+        });
+
+        let ctx = context.clone();
+        let (_shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let server_shutdown = async {
+            let _ = shutdown_rx.await;
+        };
+        let (listening_socket, server) = http_metrics::serve(ctx, server_shutdown).unwrap();
+
+        tokio::spawn(server);
+
+        let url = format!(
+            "http://{}:{}/metrics",
+            listening_socket.ip(),
+            listening_socket.port()
+        );
+
+        let first = reqwest::get(&url).await.unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(
+            first
+                .headers()
+                .get(http_metrics::DELTA_MODE_HEADER)
+                .unwrap(),
+            &HeaderValue::from_str("true").unwrap()
+        );
+        let first = first.text().await.unwrap();
+
+        let second = reqwest::get(&url).await.unwrap().text().await.unwrap();
+
+        // Samples which do not change between scrapes are only served by the first one.
+        assert!(!first.is_empty());
+        assert!(second.len() < first.len());
+    }
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn cors_headers_omitted_when_disabled() {
+    async {
+        let log = test_logger();
+
+        let context = Arc::new(Context {
+            config: Config {
+                enabled: true,
+                listen_addr: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+                listen_port: 0,
+                allow_origin: Some("*".to_string()),
+                allocator_metrics_enabled: true,
+                delta_mode: false,
+                cors_enabled: false,
+            },
+            chain: None,
+            db_path: None,
+            freezer_db_path: None,
+            gossipsub_registry: None,
+            previous_samples: <_>::default(),
+            log,
+        });
+
+        let ctx = context.clone();
+        let (_shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let server_shutdown = async {
+            let _ = shutdown_rx.await;
+        };
+        let (listening_socket, server) = http_metrics::serve(ctx, server_shutdown).unwrap();
+
+        tokio::spawn(server);
+
+        let url = format!(
+            "http://{}:{}/metrics",
+            listening_socket.ip(),
+            listening_socket.port()
+        );
+
+        let response = reqwest::Client::new()
+            .get(&url)
+            .header("Origin", "http://example.com")
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response
+            .headers()
+            .get("Access-Control-Allow-Origin")
+            .is_none());
+    }
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn disabled_server_is_not_started() {
+    let context = Arc::new(Context {
+        config: Config {
+            enabled: false,
+            ..Config::default()
+        },
+        chain: None,
+        db_path: None,
+        freezer_db_path: None,
+        gossipsub_registry: None,
+        previous_samples: <_>::default(),
+        log: test_logger(),
+    });
+
+    let result = http_metrics::serve(context, async {});
+
+    assert!(matches!(result, Err(http_metrics::Error::Disabled)));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn returns_500_if_previous_samples_lock_is_poisoned() {
+    let context = Arc::new(Context {
+        config: Config {
+            enabled: true,
+            listen_addr: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+            listen_port: 0,
+            allocator_metrics_enabled: true,
+            delta_mode: true,
+            ..Config::default()
+        },
+        chain: None,
+        db_path: None,
+        freezer_db_path: None,
+        gossipsub_registry: None,
+        previous_samples: <_>::default(),
+        log: test_logger(),
+    });
+
+    // Poison the lock by panicking while holding it.
+    let poison_ctx = context.clone();
+    std::thread::spawn(move || {
+        let _previous_samples = poison_ctx.previous_samples.lock().unwrap();
+        panic!("poison the previous samples lock");
+    })
+    .join()
+    .unwrap_err();
+
+    let (_shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let server_shutdown = async {
+        let _ = shutdown_rx.await;
+    };
+    let (listening_socket, server) = http_metrics::serve(context, server_shutdown).unwrap();
+
+    tokio::spawn(server);
+
+    let url = format!(
+        "http://{}:{}/metrics",
+        listening_socket.ip(),
+        listening_socket.port()
+    );
+
+    let response = reqwest::get(&url).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(
+        response.text().await.unwrap(),
+        "Unable to gather metrics: PreviousSamplesLockPoisoned"
+    );
+}
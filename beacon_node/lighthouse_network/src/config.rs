@@ -13,13 +13,27 @@ use std::num::NonZeroU16;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
-use types::{ForkContext, ForkName};
+use types::{ForkContext, ForkName, Hash256};
 
 pub const DEFAULT_IPV4_ADDRESS: Ipv4Addr = Ipv4Addr::UNSPECIFIED;
 pub const DEFAULT_TCP_PORT: u16 = 9000u16;
 pub const DEFAULT_DISC_PORT: u16 = 9000u16;
 pub const DEFAULT_QUIC_PORT: u16 = 9001u16;
 pub const DEFAULT_IDONTWANT_MESSAGE_SIZE_THRESHOLD: usize = 1000usize;
+pub const DEFAULT_CUSTODY_PEERS_GRACE_PERIOD: Duration = Duration::from_secs(30);
+pub const DEFAULT_SYNC_DECISION_LOG_CAPACITY: usize = 256;
+/// The default maximum depth we will search for a parent block. In principle we should have sync'd
+/// any canonical chain to its head once the peer connects. A chain should not appear where it's
+/// depth is further back than the most recent head slot.
+///
+/// Have the same value as range's sync tolerance to consider a peer synced. Once sync lookup
+/// reaches the maximum depth it will force trigger range sync.
+pub const DEFAULT_MAX_PARENT_LOOKUP_DEPTH: usize = 32;
+pub const DEFAULT_MAX_CONCURRENT_PARENT_LOOKUPS: usize = 16;
+pub const DEFAULT_MAX_CONCURRENT_CUSTODY_REQUESTS: usize = 32;
+pub const DEFAULT_ADVANCED_STATUS_GRACE: usize = 2;
+pub const DEFAULT_RANGE_SYNC_MAX_BATCH_DOWNLOAD_ATTEMPTS: u8 = 5;
+pub const DEFAULT_MAX_CONCURRENT_BACKFILL_BATCHES: usize = 20;
 
 /// The maximum size of gossip messages.
 pub fn gossip_max_size(is_merge_enabled: bool, gossip_max_size: usize) -> usize {
@@ -147,6 +161,51 @@ pub struct Config {
     /// Configuration for the minimum message size for which IDONTWANT messages are send in the mesh.
     /// Lower the value reduces the optimization effect of the IDONTWANT messages.
     pub idontwant_message_size_threshold: usize,
+
+    /// How long range sync holds a batch request while no peer serves some of the sampling
+    /// columns, giving the peer manager time to find peers on those subnets before the request is
+    /// failed.
+    pub custody_peers_grace_period: Duration,
+
+    /// The number of recent sync decisions kept in memory for debugging sync stalls. Set to zero
+    /// to disable the log.
+    pub sync_decision_log_capacity: usize,
+
+    /// The maximum length of a chain of parent lookups. Once a lookup chain would grow beyond this
+    /// length, sync stops looking up parents and syncs to the chain's tip with range sync.
+    pub max_parent_lookup_depth: usize,
+
+    /// The maximum number of parent lookup chains searched concurrently. Triggers for new chains
+    /// beyond this limit are ignored, while existing chains continue to make progress.
+    pub max_concurrent_parent_lookups: usize,
+
+    /// Block roots which never trigger a lookup when received as the unknown parent of a block,
+    /// e.g. the roots of an abandoned fork replayed by misbehaving peers. More roots can be added
+    /// at runtime via the HTTP API.
+    pub parent_lookup_denylist: Vec<Hash256>,
+
+    /// The maximum number of custody requests for the columns of a block active concurrently.
+    /// Lookups needing custody columns beyond this limit wait for an active request to finish.
+    pub max_concurrent_custody_requests: usize,
+
+    /// The number of consecutive advanced statuses required from a synced peer before it is
+    /// considered advanced and may trigger range sync. Absorbs the jitter of a synced peer
+    /// appearing ahead due to the timing of periodic statuses. A value of 1 considers it advanced
+    /// on its first advanced status.
+    pub advanced_status_grace: usize,
+
+    /// The minimum number of synced peers required to subscribe to the core topics once synced.
+    /// Until then the node stays synced but unsubscribed. Zero subscribes immediately.
+    pub min_synced_peers_for_core_topics: usize,
+
+    /// The number of failed downloads of a range sync batch after which the batch, and its chain,
+    /// are considered failed. Each retry is requested from the peer with the fewest failed
+    /// attempts for the batch.
+    pub range_sync_max_batch_download_attempts: u8,
+
+    /// The maximum number of backfill batches downloaded concurrently. Lower values throttle the
+    /// bandwidth used by backfill sync, at the cost of a slower backfill.
+    pub max_concurrent_backfill_batches: usize,
 }
 
 impl Config {
@@ -359,6 +418,16 @@ impl Default for Config {
             invalid_block_storage: None,
             inbound_rate_limiter_config: None,
             idontwant_message_size_threshold: DEFAULT_IDONTWANT_MESSAGE_SIZE_THRESHOLD,
+            custody_peers_grace_period: DEFAULT_CUSTODY_PEERS_GRACE_PERIOD,
+            sync_decision_log_capacity: DEFAULT_SYNC_DECISION_LOG_CAPACITY,
+            max_parent_lookup_depth: DEFAULT_MAX_PARENT_LOOKUP_DEPTH,
+            max_concurrent_parent_lookups: DEFAULT_MAX_CONCURRENT_PARENT_LOOKUPS,
+            parent_lookup_denylist: vec![],
+            max_concurrent_custody_requests: DEFAULT_MAX_CONCURRENT_CUSTODY_REQUESTS,
+            advanced_status_grace: DEFAULT_ADVANCED_STATUS_GRACE,
+            min_synced_peers_for_core_topics: 0,
+            range_sync_max_batch_download_attempts: DEFAULT_RANGE_SYNC_MAX_BATCH_DOWNLOAD_ATTEMPTS,
+            max_concurrent_backfill_batches: DEFAULT_MAX_CONCURRENT_BACKFILL_BATCHES,
         }
     }
 }
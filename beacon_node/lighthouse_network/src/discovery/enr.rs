@@ -16,6 +16,7 @@ use std::fs::File;
 use std::io::prelude::*;
 use std::path::Path;
 use std::str::FromStr;
+use strum::IntoStaticStr;
 use types::{ChainSpec, EnrForkId, EthSpec};
 
 use super::enr_ext::{EnrExt, QUIC6_ENR_KEY, QUIC_ENR_KEY};
@@ -300,6 +301,62 @@ fn compare_enr(local_enr: &Enr, disk_enr: &Enr) -> bool {
         && local_enr.get_decodable::<Bytes>(PEERDAS_CUSTODY_GROUP_COUNT_ENR_KEY) == disk_enr.get_decodable(PEERDAS_CUSTODY_GROUP_COUNT_ENR_KEY)
 }
 
+/// An ENR field relevant to Eth2 that may differ between two ENRs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum EnrField {
+    Eth2,
+    Attnets,
+    Syncnets,
+    Cgc,
+    Ip4,
+    Ip6,
+    Tcp4,
+    Tcp6,
+    Udp4,
+    Udp6,
+    Quic4,
+    Quic6,
+}
+
+/// Returns the Eth2 and networking fields which differ between `a` and `b`.
+///
+/// Unlike `compare_enr`, this comparison is symmetric: a field that is absent or undecodable in
+/// one ENR and present in the other is reported as differing. This is useful for explaining why
+/// the ENR sequence number was incremented on startup.
+pub fn diff_enr<E: EthSpec>(a: &Enr, b: &Enr, spec: &ChainSpec) -> Vec<EnrField> {
+    let mut diff = vec![];
+    let mut check = |field, differs: bool| {
+        if differs {
+            diff.push(field);
+        }
+    };
+
+    check(EnrField::Eth2, a.eth2().ok() != b.eth2().ok());
+    check(
+        EnrField::Attnets,
+        a.attestation_bitfield::<E>().ok() != b.attestation_bitfield::<E>().ok(),
+    );
+    check(
+        EnrField::Syncnets,
+        a.sync_committee_bitfield::<E>().ok() != b.sync_committee_bitfield::<E>().ok(),
+    );
+    check(
+        EnrField::Cgc,
+        a.custody_group_count::<E>(spec).ok() != b.custody_group_count::<E>(spec).ok(),
+    );
+    check(EnrField::Ip4, a.ip4() != b.ip4());
+    check(EnrField::Ip6, a.ip6() != b.ip6());
+    check(EnrField::Tcp4, a.tcp4() != b.tcp4());
+    check(EnrField::Tcp6, a.tcp6() != b.tcp6());
+    check(EnrField::Udp4, a.udp4() != b.udp4());
+    check(EnrField::Udp6, a.udp6() != b.udp6());
+    check(EnrField::Quic4, a.quic4() != b.quic4());
+    check(EnrField::Quic6, a.quic6() != b.quic6());
+
+    diff
+}
+
 /// Loads enr from the given directory
 pub fn load_enr_from_disk(dir: &Path) -> Result<Enr, String> {
     let enr_f = dir.join(ENR_FILENAME);
@@ -384,6 +441,34 @@ mod test {
         );
     }
 
+    #[test]
+    fn diff_enr_reports_changed_fields() {
+        let spec = make_fulu_spec();
+        let (enr, enr_key) = build_enr_with_config(NetworkConfig::default(), &spec);
+        assert!(diff_enr::<E>(&enr, &enr, &spec).is_empty());
+
+        let mut attnets = BitVector::<<E as EthSpec>::SubnetBitfieldLength>::new();
+        attnets.set(0, true).unwrap();
+        let mut other = enr.clone();
+        other
+            .insert(
+                ATTESTATION_BITFIELD_ENR_KEY,
+                &Bytes::from(attnets.as_ssz_bytes()),
+                &enr_key,
+            )
+            .unwrap();
+        other.set_tcp4(9999, &enr_key).unwrap();
+
+        assert_eq!(
+            diff_enr::<E>(&enr, &other, &spec),
+            vec![EnrField::Attnets, EnrField::Tcp4]
+        );
+        assert_eq!(
+            diff_enr::<E>(&other, &enr, &spec),
+            vec![EnrField::Attnets, EnrField::Tcp4]
+        );
+    }
+
     #[test]
     fn test_encode_decode_eth2_enr() {
         let (enr, _key) = build_enr_with_config(NetworkConfig::default(), &E::default_spec());
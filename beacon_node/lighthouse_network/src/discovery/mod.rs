@@ -11,7 +11,9 @@ use crate::service::TARGET_SUBNET_PEERS;
 use crate::{metrics, ClearDialError};
 use crate::{Enr, NetworkConfig, NetworkGlobals, Subnet, SubnetDiscovery};
 use discv5::{enr::NodeId, Discv5};
-pub use enr::{build_enr, load_enr_from_disk, use_or_load_enr, CombinedKey, Eth2Enr};
+pub use enr::{
+    build_enr, diff_enr, load_enr_from_disk, use_or_load_enr, CombinedKey, EnrField, Eth2Enr,
+};
 pub use enr_ext::{peer_id_to_node_id, CombinedKeyExt, EnrExt};
 pub use libp2p::identity::{Keypair, PublicKey};
 
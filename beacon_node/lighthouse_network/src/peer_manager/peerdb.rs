@@ -729,6 +729,11 @@ impl<E: EthSpec> PeerDB<E> {
         peer_id
     }
 
+    /// Sets the peer as disconnected. MUST ONLY BE USED IN TESTS.
+    pub fn __disconnect_peer_testing_only(&mut self, peer_id: &PeerId) {
+        let _ = self.inject_disconnect(peer_id);
+    }
+
     /// The connection state of the peer has been changed. Modify the peer in the db to ensure all
     /// variables are in sync with libp2p.
     /// Updating the state can lead to a `BanOperation` which needs to be processed via the peer
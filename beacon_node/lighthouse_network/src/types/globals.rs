@@ -2,7 +2,7 @@
 use super::TopicConfig;
 use crate::peer_manager::peerdb::PeerDB;
 use crate::rpc::{MetaData, MetaDataV3};
-use crate::types::{BackFillState, SyncState};
+use crate::types::{BackFillState, SyncStallReason, SyncState};
 use crate::{Client, Enr, EnrExt, GossipTopic, Multiaddr, NetworkConfig, PeerId};
 use parking_lot::RwLock;
 use slog::error;
@@ -28,6 +28,8 @@ pub struct NetworkGlobals<E: EthSpec> {
     pub gossipsub_subscriptions: RwLock<HashSet<GossipTopic>>,
     /// The current sync status of the node.
     pub sync_state: RwLock<SyncState>,
+    /// The reason why sync is not making progress, if any.
+    pub sync_stall_reason: RwLock<Option<SyncStallReason>>,
     /// The current state of the backfill sync.
     pub backfill_state: RwLock<BackFillState>,
     /// The computed sampling subnets and columns is stored to avoid re-computing.
@@ -99,6 +101,7 @@ impl<E: EthSpec> NetworkGlobals<E> {
             peers: RwLock::new(PeerDB::new(trusted_peers, disable_peer_scoring, log)),
             gossipsub_subscriptions: RwLock::new(HashSet::new()),
             sync_state: RwLock::new(SyncState::Stalled),
+            sync_stall_reason: RwLock::new(None),
             backfill_state: RwLock::new(BackFillState::Paused),
             sampling_subnets,
             sampling_columns,
@@ -148,6 +151,11 @@ impl<E: EthSpec> NetworkGlobals<E> {
         self.sync_state.read().clone()
     }
 
+    /// Returns the reason why sync is not making progress, if any.
+    pub fn sync_stall_reason(&self) -> Option<SyncStallReason> {
+        *self.sync_stall_reason.read()
+    }
+
     /// Returns the current backfill state.
     pub fn backfill_state(&self) -> BackFillState {
         self.backfill_state.read().clone()
@@ -169,6 +177,16 @@ impl<E: EthSpec> NetworkGlobals<E> {
         std::mem::replace(&mut *self.sync_state.write(), new_state)
     }
 
+    /// Updates the reason why sync is not making progress.
+    ///
+    /// The old reason is returned
+    pub fn set_sync_stall_reason(
+        &self,
+        new_reason: Option<SyncStallReason>,
+    ) -> Option<SyncStallReason> {
+        std::mem::replace(&mut *self.sync_stall_reason.write(), new_reason)
+    }
+
     /// Returns a connected peer that:
     /// 1. is connected
     /// 2. assigned to custody the column based on it's `custody_subnet_count` from ENR or metadata
@@ -203,7 +221,7 @@ impl<E: EthSpec> NetworkGlobals<E> {
             seq_number: 0,
             attnets: Default::default(),
             syncnets: Default::default(),
-            custody_group_count: spec.custody_requirement,
+            custody_group_count: spec.custody_group_count(config.subscribe_all_data_column_subnets),
         });
         Self::new_test_globals_with_metadata(trusted_peers, metadata, log, config, spec)
     }
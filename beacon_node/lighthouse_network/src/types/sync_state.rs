@@ -26,6 +26,18 @@ pub enum SyncState {
     Stalled,
 }
 
+/// The reason why the node is not making sync progress, reported alongside the `SyncState`.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum SyncStallReason {
+    /// No synced or advanced peers are connected to sync from.
+    NoPeers,
+    /// The execution engine is offline, so blocks can't be imported.
+    ExecutionEngineOffline,
+    /// The node is synced to the head but historical blocks are still required and backfill sync
+    /// is not running, e.g. waiting for synced peers or after a failure.
+    AwaitingBackfill,
+}
+
 #[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 /// The state of the backfill sync.
 pub enum BackFillState {
@@ -97,6 +109,27 @@ impl SyncState {
     pub fn is_stalled(&self) -> bool {
         matches!(self, SyncState::Stalled)
     }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncState::SyncingFinalized { .. } => "SyncingFinalized",
+            SyncState::SyncingHead { .. } => "SyncingHead",
+            SyncState::BackFillSyncing { .. } => "BackFillSyncing",
+            SyncState::SyncTransition => "SyncTransition",
+            SyncState::Synced => "Synced",
+            SyncState::Stalled => "Stalled",
+        }
+    }
+}
+
+impl std::fmt::Display for SyncStallReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SyncStallReason::NoPeers => write!(f, "No useful peers"),
+            SyncStallReason::ExecutionEngineOffline => write!(f, "Execution engine offline"),
+            SyncStallReason::AwaitingBackfill => write!(f, "Awaiting backfill sync"),
+        }
+    }
 }
 
 impl std::fmt::Display for SyncState {
@@ -14,3 +14,4 @@ pub use lighthouse_network::NetworkConfig;
 pub use service::{
     NetworkMessage, NetworkReceivers, NetworkSenders, NetworkService, ValidatorSubscriptionMessage,
 };
+pub use sync::{LookupStatus, SyncDecision, SyncDecisionEntry};
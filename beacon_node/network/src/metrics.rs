@@ -7,8 +7,9 @@ use beacon_chain::{
 };
 use fnv::FnvHashMap;
 use lighthouse_network::{
-    peer_manager::peerdb::client::ClientKind, types::GossipKind, GossipTopic, Gossipsub,
-    NetworkGlobals,
+    peer_manager::peerdb::client::ClientKind,
+    types::{GossipKind, SyncState},
+    GossipTopic, Gossipsub, NetworkGlobals,
 };
 pub use metrics::*;
 use std::sync::{Arc, LazyLock};
@@ -432,6 +433,86 @@ pub static SYNCING_CHAIN_BATCH_AWAITING_PROCESSING: LazyLock<Result<Histogram>>
             ]),
         )
     });
+pub static SYNC_RANGE_FINALIZED_START_SLOT: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "sync_range_finalized_start_slot",
+        "Start slot of the current finalized range sync, or zero if not syncing a finalized chain",
+    )
+});
+pub static SYNC_RANGE_FINALIZED_TARGET_SLOT: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "sync_range_finalized_target_slot",
+        "Target slot of the current finalized range sync, or zero if not syncing a finalized chain",
+    )
+});
+pub static SYNC_RANGE_HEAD_START_SLOT: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "sync_range_head_start_slot",
+        "Start slot of the current head range sync, or zero if not syncing a head chain",
+    )
+});
+pub static SYNC_RANGE_HEAD_TARGET_SLOT: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "sync_range_head_target_slot",
+        "Target slot of the current head range sync, or zero if not syncing a head chain",
+    )
+});
+pub static SYNC_RANGE_ESTIMATED_SECONDS_TO_SYNC: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "sync_range_estimated_seconds_to_sync",
+        "Estimated seconds until range sync reaches its target slot, zero if not range syncing or \
+         -1 if there is no estimate as the local head is not advancing",
+    )
+});
+pub static SYNC_STATE_TRANSITIONS: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "sync_state_transitions_total",
+        "Total count of sync state transitions",
+        &["from", "to"],
+    )
+});
+pub static SYNC_STATE: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "sync_state",
+        "Current sync state: 0 syncing finalized, 1 syncing head, 2 backfill syncing, \
+         3 sync transition, 4 synced, 5 stalled",
+    )
+});
+pub static SYNC_CORE_TOPICS_SUBSCRIPTION_PENDING: LazyLock<Result<IntGauge>> = LazyLock::new(
+    || {
+        try_create_int_gauge(
+        "sync_core_topics_subscription_pending",
+        "Set to 1 if the node is synced but waiting for more synced peers to subscribe to the core topics",
+    )
+    },
+);
+pub static SYNC_RANGE_BATCH_DOWNLOAD_ATTEMPTS_EXHAUSTED: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "sync_range_batch_download_attempts_exhausted_total",
+            "Total count of range sync batches that failed after exhausting their download attempts",
+        )
+    });
+pub static SYNC_PARENT_LOOKUPS_DENYLISTED: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
+    try_create_int_counter(
+        "sync_parent_lookups_denylisted_total",
+        "Total count of parent lookups not created as the unknown parent root is denylisted",
+    )
+});
+pub static SYNC_FORCE_RANGE_SYNC_ASSUMED_HEAD_SLOT: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "sync_force_range_sync_assumed_head_slot_total",
+            "Total count of range syncs forced by lookups without a known remote head slot",
+        )
+    });
+pub static SYNC_BLOCK_COMPONENT_COUPLING_ERRORS: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "sync_block_component_coupling_errors_total",
+            "Total count of by range requests whose blocks, blobs or columns could not be coupled",
+        )
+    });
 pub static SYNC_SINGLE_BLOCK_LOOKUPS: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
     try_create_int_gauge(
         "sync_single_block_lookups",
@@ -451,12 +532,66 @@ pub static SYNC_LOOKUP_DROPPED: LazyLock<Result<IntCounterVec>> = LazyLock::new(
         &["reason"],
     )
 });
+pub static SYNC_REQUESTS_DROPPED_EXECUTION_ENGINE_OFFLINE: LazyLock<Result<IntCounterVec>> =
+    LazyLock::new(|| {
+        try_create_int_counter_vec(
+            "sync_requests_dropped_execution_engine_offline_total",
+            "Total count of sync requests dropped when the execution engine goes offline",
+            &["component"],
+        )
+    });
+pub static SYNC_PARENT_LOOKUPS_REJECTED: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
+    try_create_int_counter(
+        "sync_parent_lookups_rejected_total",
+        "Total count of new parent lookups rejected for exceeding the max concurrent parent lookups",
+    )
+});
 pub static SYNC_LOOKUP_COMPLETED: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
     try_create_int_counter(
         "sync_lookups_completed_total",
         "Total count of sync lookups completed",
     )
 });
+pub static SYNC_LOOKUP_COMPONENT_PEERS: LazyLock<Result<HistogramVec>> = LazyLock::new(|| {
+    try_create_histogram_vec_with_buckets(
+        "sync_lookup_component_peers",
+        "Count of distinct peers that served a successfully processed lookup component",
+        Ok(vec![
+            1.0, 2.0, 3.0, 4.0, 6.0, 8.0, 12.0, 16.0, 24.0, 32.0, 64.0,
+        ]),
+        &["component"],
+    )
+});
+pub static SYNC_PEER_FORK_DIGEST_MISMATCH: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
+    try_create_int_counter(
+        "sync_peer_fork_digest_mismatch_total",
+        "Total count of peers removed from range sync for changing their fork digest outside of a \
+         fork transition",
+    )
+});
+pub static SYNC_LOOKUP_PROCESSOR_SEND_DROPPED: LazyLock<Result<IntCounterVec>> =
+    LazyLock::new(|| {
+        try_create_int_counter_vec(
+            "sync_lookup_processor_send_dropped_total",
+            "Total count of downloaded lookup components dropped after repeatedly failing to be \
+             sent to the beacon processor",
+            &["component"],
+        )
+    });
+pub static SYNC_LOOKUP_BLOBS_CACHE_HITS: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
+    try_create_int_counter(
+        "sync_lookup_blobs_cache_hits_total",
+        "Total count of blob lookup requests trimmed or avoided because some blobs were already \
+         cached by the data availability checker",
+    )
+});
+pub static SYNC_LOOKUP_COLUMNS_CACHE_HITS: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
+    try_create_int_counter(
+        "sync_lookup_columns_cache_hits_total",
+        "Total count of custody lookup requests trimmed or avoided because some columns were \
+         already cached by the data availability checker",
+    )
+});
 pub static SYNC_LOOKUPS_STUCK: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
     try_create_int_counter(
         "sync_lookups_stuck_total",
@@ -470,6 +605,40 @@ pub static SYNC_ACTIVE_NETWORK_REQUESTS: LazyLock<Result<IntGaugeVec>> = LazyLoc
         &["type"],
     )
 });
+pub static SYNC_CUSTODY_COLUMN_PEERS: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "sync_custody_column_peers",
+        "Current count of connected peers that can serve each column the node needs",
+        &["column_index"],
+    )
+});
+pub static SYNC_BACKFILL_DOWNLOADING_BATCHES: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "sync_backfill_downloading_batches",
+        "Current count of backfill batches being downloaded",
+    )
+});
+pub static SYNC_IS_SUPERNODE: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "sync_is_supernode",
+        "Set to 1 if the node custodies all columns, 0 otherwise",
+    )
+});
+pub static SYNC_BLOCK_PROCESSING_STAGE_TIMES: LazyLock<Result<HistogramVec>> =
+    LazyLock::new(|| {
+        try_create_histogram_vec(
+            "sync_block_processing_stage_seconds",
+            "Time spent in each stage of processing blocks imported by sync",
+            &["stage"],
+        )
+    });
+pub static SYNC_LEAKED_COMPONENTS_BY_RANGE_REQUESTS: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "sync_leaked_components_by_range_requests_total",
+            "Total count of components_by_range requests removed for never completing",
+        )
+    });
 pub static SYNC_UNKNOWN_NETWORK_REQUESTS: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
     try_create_int_counter_vec(
         "sync_unknwon_network_request",
@@ -477,6 +646,13 @@ pub static SYNC_UNKNOWN_NETWORK_REQUESTS: LazyLock<Result<IntCounterVec>> = Lazy
         &["type"],
     )
 });
+pub static SYNC_RESPONSE_COUNT_VIOLATIONS: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "sync_response_count_violations_total",
+        "Total count of sync requests to which a peer sent fewer or more responses than requested",
+        &["type", "reason"],
+    )
+});
 
 /*
  * Block Delay Metrics
@@ -666,6 +842,26 @@ pub(crate) fn register_process_result_metrics(
     }
 }
 
+pub fn register_sync_state_transition(old_state: &SyncState, new_state: &SyncState) {
+    inc_counter_vec(
+        &SYNC_STATE_TRANSITIONS,
+        &[old_state.as_str(), new_state.as_str()],
+    );
+    set_sync_state(new_state);
+}
+
+pub fn set_sync_state(state: &SyncState) {
+    let value = match state {
+        SyncState::SyncingFinalized { .. } => 0,
+        SyncState::SyncingHead { .. } => 1,
+        SyncState::BackFillSyncing { .. } => 2,
+        SyncState::SyncTransition => 3,
+        SyncState::Synced => 4,
+        SyncState::Stalled => 5,
+    };
+    set_gauge(&SYNC_STATE, value);
+}
+
 pub fn from_result<T, E>(result: &std::result::Result<T, E>) -> &str {
     match result {
         Ok(_) => SUCCESS,
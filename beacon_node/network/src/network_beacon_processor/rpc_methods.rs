@@ -125,7 +125,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                     finalized_epoch: status.finalized_epoch,
                     finalized_root: status.finalized_root,
                 };
-                self.send_sync_message(SyncMessage::AddPeer(peer_id, info));
+                self.send_sync_message(SyncMessage::AddPeer(peer_id, info, status.fork_digest));
             }
             Err(e) => error!(self.log, "Could not process status message";
                 "peer" => %peer_id,
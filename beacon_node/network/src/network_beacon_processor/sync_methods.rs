@@ -2,7 +2,7 @@ use crate::metrics::{self, register_process_result_metrics};
 use crate::network_beacon_processor::{NetworkBeaconProcessor, FUTURE_SLOT_TOLERANCE};
 use crate::sync::BatchProcessResult;
 use crate::sync::{
-    manager::{BlockProcessType, SyncMessage},
+    manager::{BlockProcessType, BlockProcessingStageTimings, SyncMessage},
     ChainId,
 };
 use beacon_chain::block_verification_types::{AsBlock, RpcBlock};
@@ -19,6 +19,7 @@ use beacon_processor::{
 };
 use lighthouse_network::PeerAction;
 use slog::{debug, error, info, warn};
+use slot_clock::SlotClock;
 use std::sync::Arc;
 use std::time::Duration;
 use store::KzgCommitment;
@@ -93,6 +94,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             self.send_sync_message(SyncMessage::BlockComponentProcessed {
                 process_type,
                 result: crate::sync::manager::BlockProcessingResult::Ignored,
+                stage_timings: None,
             });
         };
         (process_fn, Box::new(ignore_fn))
@@ -154,6 +156,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         );
 
         let signed_beacon_block = block.block_cloned();
+        let processing_started = self.chain.slot_clock.now_duration();
         let result = self
             .chain
             .process_block_with_early_caching(
@@ -164,6 +167,11 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             )
             .await;
         register_process_result_metrics(&result, metrics::BlockSource::Rpc, "block");
+        let stage_timings = self.block_processing_stage_timings(
+            block_root,
+            processing_started,
+            matches!(result, Ok(AvailabilityProcessingStatus::Imported(_))),
+        );
 
         // RPC block imported, regardless of process type
         match result.as_ref() {
@@ -214,12 +222,54 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         self.send_sync_message(SyncMessage::BlockComponentProcessed {
             process_type,
             result: result.into(),
+            stage_timings,
         });
 
         // Drop the handle to remove the entry from the cache
         drop(handle);
     }
 
+    /// Computes the time spent in each stage of processing `block_root` from the timestamps
+    /// recorded by the beacon chain in the block times cache.
+    ///
+    /// Timestamps prior to `processing_started` belong to a previous attempt to process the same
+    /// block and are ignored.
+    fn block_processing_stage_timings(
+        &self,
+        block_root: Hash256,
+        processing_started: Option<Duration>,
+        imported: bool,
+    ) -> Option<BlockProcessingStageTimings> {
+        let processing_started = processing_started?;
+        let processing_finished = self.chain.slot_clock.now_duration()?;
+        let timestamps = self
+            .chain
+            .block_times_cache
+            .read()
+            .cache
+            .get(&block_root)?
+            .timestamps
+            .clone();
+
+        let consensus_verified = timestamps
+            .consensus_verified
+            .filter(|timestamp| *timestamp >= processing_started);
+        let executed = timestamps
+            .executed
+            .filter(|timestamp| *timestamp >= processing_started);
+
+        Some(BlockProcessingStageTimings {
+            consensus_verification: consensus_verified
+                .map(|timestamp| timestamp.saturating_sub(processing_started)),
+            execution_verification: consensus_verified
+                .zip(executed)
+                .map(|(verified, executed)| executed.saturating_sub(verified)),
+            import: executed
+                .filter(|_| imported)
+                .map(|executed| processing_finished.saturating_sub(executed)),
+        })
+    }
+
     /// Returns an async closure which processes a list of blobs received via RPC.
     ///
     /// This separate function was required to prevent a cycle during compiler
@@ -329,6 +379,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         self.send_sync_message(SyncMessage::BlockComponentProcessed {
             process_type,
             result: result.into(),
+            stage_timings: None,
         });
     }
 
@@ -391,6 +442,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         self.send_sync_message(SyncMessage::BlockComponentProcessed {
             process_type,
             result: result.into(),
+            stage_timings: None,
         });
     }
 
@@ -1078,6 +1078,28 @@ async fn test_rpc_block_reprocessing() {
     assert_eq!(next_block_root, rig.head_root());
 }
 
+/// Ensure that the result of processing an rpc block carries the timings of each processing stage.
+#[tokio::test]
+async fn test_rpc_block_stage_timings() {
+    let mut rig = TestRig::new(SMALL_CHAIN).await;
+    rig.enqueue_single_lookup_rpc_block();
+    rig.assert_event_journal_completes(&[WorkType::RpcBlock])
+        .await;
+
+    loop {
+        match rig._sync_rx.recv().await {
+            Some(SyncMessage::BlockComponentProcessed { stage_timings, .. }) => {
+                let stage_timings = stage_timings.expect("should have stage timings");
+                assert!(stage_timings.consensus_verification.is_some());
+                assert!(stage_timings.execution_verification.is_some());
+                break;
+            }
+            Some(_) => continue,
+            None => panic!("sync channel closed"),
+        }
+    }
+}
+
 /// Ensure that backfill batches get rate-limited and processing is scheduled at specified intervals.
 #[tokio::test]
 async fn test_backfill_sync_processing() {
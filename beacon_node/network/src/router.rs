@@ -8,8 +8,8 @@
 use crate::network_beacon_processor::{InvalidBlockStorage, NetworkBeaconProcessor};
 use crate::service::NetworkMessage;
 use crate::status::status_message;
-use crate::sync::SyncMessage;
-use beacon_chain::{BeaconChain, BeaconChainTypes};
+use crate::sync::{LookupStatus, SyncDecisionEntry, SyncMessage};
+use beacon_chain::{BeaconChain, BeaconChainTypes, EngineState};
 use beacon_processor::{
     work_reprocessing_queue::ReprocessQueueMessage, BeaconProcessorSend, DuplicateCache,
 };
@@ -26,9 +26,9 @@ use slog::{crit, debug, o, trace};
 use slog::{error, warn};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
-use types::{BlobSidecar, DataColumnSidecar, EthSpec, ForkContext, SignedBeaconBlock};
+use types::{BlobSidecar, DataColumnSidecar, EthSpec, ForkContext, Hash256, SignedBeaconBlock};
 
 /// Handles messages from the network and routes them to the appropriate service to be handled.
 pub struct Router<T: BeaconChainTypes> {
@@ -77,6 +77,21 @@ pub enum RouterMessage<E: EthSpec> {
     PubsubMessage(MessageId, PeerId, PubsubMessage<E>, bool),
     /// The peer manager has requested we re-status a peer.
     StatusPeer(PeerId),
+    /// Requests the recent significant decisions taken by sync, oldest first, optionally clearing
+    /// them.
+    DumpSyncDecisionLog {
+        reset: bool,
+        sender: oneshot::Sender<Vec<SyncDecisionEntry>>,
+    },
+    /// Requests the status of the sync lookup for a block root.
+    GetSyncLookupStatus(Hash256, oneshot::Sender<Option<LookupStatus>>),
+    /// Requests sync's view on the execution engine state.
+    GetSyncEngineState(oneshot::Sender<EngineState>),
+    /// Adds block roots to the sync parent lookup denylist, responding with the full denylist.
+    DenylistSyncParentRoots {
+        roots: Vec<Hash256>,
+        sender: oneshot::Sender<Vec<Hash256>>,
+    },
 }
 
 impl<T: BeaconChainTypes> Router<T> {
@@ -164,6 +179,18 @@ impl<T: BeaconChainTypes> Router<T> {
             RouterMessage::PeerDisconnected(peer_id) => {
                 self.send_to_sync(SyncMessage::Disconnect(peer_id));
             }
+            RouterMessage::DumpSyncDecisionLog { reset, sender } => {
+                self.send_to_sync(SyncMessage::DumpDecisionLog { reset, sender });
+            }
+            RouterMessage::GetSyncLookupStatus(block_root, sender) => {
+                self.send_to_sync(SyncMessage::IsLookupActive(block_root, sender));
+            }
+            RouterMessage::GetSyncEngineState(sender) => {
+                self.send_to_sync(SyncMessage::GetEngineStateView(sender));
+            }
+            RouterMessage::DenylistSyncParentRoots { roots, sender } => {
+                self.send_to_sync(SyncMessage::DenylistParentRoots { roots, sender });
+            }
             RouterMessage::RPCRequestReceived {
                 peer_id,
                 id,
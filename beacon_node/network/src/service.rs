@@ -4,8 +4,9 @@ use crate::network_beacon_processor::InvalidBlockStorage;
 use crate::persisted_dht::{clear_dht, load_dht, persist_dht};
 use crate::router::{Router, RouterMessage};
 use crate::subnet_service::{SubnetService, SubnetServiceMessage, Subscription};
+use crate::sync::{LookupStatus, SyncDecisionEntry};
 use crate::NetworkConfig;
-use beacon_chain::{BeaconChain, BeaconChainTypes};
+use beacon_chain::{BeaconChain, BeaconChainTypes, EngineState};
 use beacon_processor::{work_reprocessing_queue::ReprocessQueueMessage, BeaconProcessorSend};
 use futures::channel::mpsc::Sender;
 use futures::future::OptionFuture;
@@ -18,6 +19,7 @@ use lighthouse_network::{prometheus_client::registry::Registry, MessageAcceptanc
 use lighthouse_network::{
     rpc::{GoodbyeReason, RpcErrorResponse},
     Context, PeerAction, PeerRequestId, PubsubMessage, ReportSource, Response, Subnet,
+    SubnetDiscovery,
 };
 use lighthouse_network::{
     service::api_types::AppRequestId,
@@ -30,11 +32,11 @@ use std::{collections::HashSet, pin::Pin, sync::Arc, time::Duration};
 use store::HotColdDB;
 use strum::IntoStaticStr;
 use task_executor::ShutdownReason;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tokio::time::Sleep;
 use types::{
-    ChainSpec, EthSpec, ForkContext, Slot, SubnetId, SyncCommitteeSubscription, SyncSubnetId,
-    Unsigned, ValidatorSubscription,
+    ChainSpec, DataColumnSubnetId, EthSpec, ForkContext, Hash256, Slot, SubnetId,
+    SyncCommitteeSubscription, SyncSubnetId, Unsigned, ValidatorSubscription,
 };
 
 mod tests;
@@ -101,6 +103,23 @@ pub enum NetworkMessage<E: EthSpec> {
         reason: GoodbyeReason,
         source: ReportSource,
     },
+    /// Requests the recent significant decisions taken by sync, oldest first, optionally clearing
+    /// them.
+    DumpSyncDecisionLog {
+        reset: bool,
+        sender: oneshot::Sender<Vec<SyncDecisionEntry>>,
+    },
+    /// Requests the status of the sync lookup for a block root.
+    GetSyncLookupStatus(Hash256, oneshot::Sender<Option<LookupStatus>>),
+    /// Requests sync's view on the execution engine state.
+    GetSyncEngineState(oneshot::Sender<EngineState>),
+    /// Adds block roots to the sync parent lookup denylist, responding with the full denylist.
+    DenylistSyncParentRoots {
+        roots: Vec<Hash256>,
+        sender: oneshot::Sender<Vec<Hash256>>,
+    },
+    /// Discover and dial peers on a data column subnet that sync has no peers for.
+    DiscoverDataColumnPeers(DataColumnSubnetId),
 }
 
 /// Messages triggered by validators that may trigger a subscription to a subnet.
@@ -688,6 +707,24 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 reason,
                 source,
             } => self.libp2p.goodbye_peer(&peer_id, reason, source),
+            NetworkMessage::DumpSyncDecisionLog { reset, sender } => {
+                self.send_to_router(RouterMessage::DumpSyncDecisionLog { reset, sender })
+            }
+            NetworkMessage::GetSyncLookupStatus(block_root, sender) => {
+                self.send_to_router(RouterMessage::GetSyncLookupStatus(block_root, sender))
+            }
+            NetworkMessage::GetSyncEngineState(sender) => {
+                self.send_to_router(RouterMessage::GetSyncEngineState(sender))
+            }
+            NetworkMessage::DenylistSyncParentRoots { roots, sender } => {
+                self.send_to_router(RouterMessage::DenylistSyncParentRoots { roots, sender })
+            }
+            NetworkMessage::DiscoverDataColumnPeers(subnet_id) => {
+                self.libp2p.discover_subnet_peers(vec![SubnetDiscovery {
+                    subnet: Subnet::DataColumn(subnet_id),
+                    min_ttl: None,
+                }]);
+            }
             NetworkMessage::SubscribeCoreTopics => {
                 if self.subscribed_core_topics() {
                     return;
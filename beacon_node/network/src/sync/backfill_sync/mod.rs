@@ -8,6 +8,7 @@
 //! If a batch fails, the backfill sync cannot progress. In this scenario, we mark the backfill
 //! sync as failed, log an error and attempt to retry once a new peer joins the node.
 
+use crate::metrics;
 use crate::network_beacon_processor::ChainSegmentProcessId;
 use crate::sync::manager::BatchProcessResult;
 use crate::sync::network_context::RangeRequestId;
@@ -1031,7 +1032,12 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
 
         idle_peers.shuffle(&mut rng);
 
+        let max_downloading_batches = self.network_globals.config.max_concurrent_backfill_batches;
         while let Some(peer) = idle_peers.pop() {
+            if self.downloading_batches() >= max_downloading_batches {
+                // Throttle backfill, more batches are requested as the current ones complete
+                return Ok(());
+            }
             if let Some(batch_id) = self.include_next_batch(network) {
                 // send the batch
                 self.send_batch(network, batch_id, peer)?;
@@ -1043,6 +1049,21 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
         Ok(())
     }
 
+    /// Returns the number of batches currently being downloaded.
+    fn downloading_batches(&self) -> usize {
+        self.batches
+            .values()
+            .filter(|batch| matches!(batch.state(), BatchState::Downloading(..)))
+            .count()
+    }
+
+    pub(crate) fn register_metrics(&self) {
+        metrics::set_gauge(
+            &metrics::SYNC_BACKFILL_DOWNLOADING_BATCHES,
+            self.downloading_batches() as i64,
+        );
+    }
+
     /// Creates the next required batch from the chain. If there are no more batches required,
     /// `false` is returned.
     fn include_next_batch(&mut self, network: &mut SyncNetworkContext<T>) -> Option<BatchId> {
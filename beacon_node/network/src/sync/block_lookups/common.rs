@@ -12,13 +12,15 @@ use lighthouse_network::service::api_types::Id;
 use parking_lot::RwLock;
 use std::collections::HashSet;
 use std::sync::Arc;
+use strum::IntoStaticStr;
 use types::blob_sidecar::FixedBlobSidecarList;
 use types::{DataColumnSidecarList, SignedBeaconBlock};
 
 use super::single_block_lookup::{ComponentRequests, DownloadResult};
 use super::SingleLookupId;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
 pub enum ResponseType {
     Block,
     Blob,
@@ -35,7 +37,7 @@ pub enum ResponseType {
 /// state.
 pub trait RequestState<T: BeaconChainTypes> {
     /// The type created after validation.
-    type VerifiedResponseType: Clone;
+    type VerifiedResponseType: Clone + PartialEq;
 
     /// Request the network context to prepare a request of a component of `block_root`. If the
     /// request is not necessary because the component is already known / processed, return false.
@@ -23,7 +23,8 @@
 use self::parent_chain::{compute_parent_chains, NodeChain};
 pub use self::single_block_lookup::DownloadResult;
 use self::single_block_lookup::{LookupRequestError, LookupResult, SingleBlockLookup};
-use super::manager::{BlockProcessType, BlockProcessingResult, SLOT_IMPORT_TOLERANCE};
+use super::decision_log::SyncDecision;
+use super::manager::{BlockProcessType, BlockProcessingResult};
 use super::network_context::{PeerGroup, RpcResponseError, SyncNetworkContext};
 use crate::metrics;
 use crate::sync::block_lookups::common::ResponseType;
@@ -37,14 +38,17 @@ use beacon_chain::{AvailabilityProcessingStatus, BeaconChainTypes, BlockError};
 pub use common::RequestState;
 use fnv::FnvHashMap;
 use itertools::Itertools;
+use lighthouse_network::config::DEFAULT_MAX_PARENT_LOOKUP_DEPTH;
 use lighthouse_network::service::api_types::SingleLookupReqId;
 use lighthouse_network::{PeerAction, PeerId};
 use lru_cache::LRUTimeCache;
 pub use single_block_lookup::{BlobRequestState, BlockRequestState, CustodyRequestState};
 use slog::{debug, error, warn, Logger};
 use std::collections::hash_map::Entry;
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use store::Hash256;
 use types::{BlobSidecar, DataColumnSidecar, EthSpec, SignedBeaconBlock};
 
@@ -52,21 +56,16 @@ pub mod common;
 pub mod parent_chain;
 mod single_block_lookup;
 
-/// The maximum depth we will search for a parent block. In principle we should have sync'd any
-/// canonical chain to its head once the peer connects. A chain should not appear where it's depth
-/// is further back than the most recent head slot.
-///
-/// Have the same value as range's sync tolerance to consider a peer synced. Once sync lookup
-/// reaches the maximum depth it will force trigger range sync.
-pub(crate) const PARENT_DEPTH_TOLERANCE: usize = SLOT_IMPORT_TOLERANCE;
-
 const FAILED_CHAINS_CACHE_EXPIRY_SECONDS: u64 = 60;
 pub const SINGLE_BLOCK_LOOKUP_MAX_ATTEMPTS: u8 = 4;
+/// Maximum number of times a downloaded lookup component is sent to the beacon processor again
+/// after the send failed, e.g. because the channel was full, before it is dropped.
+pub const SINGLE_BLOCK_LOOKUP_MAX_PROCESSOR_SEND_RETRIES: u8 = 3;
 
 /// Maximum time we allow a lookup to exist before assuming it is stuck and will never make
 /// progress. Assume the worse case processing time per block component set * times max depth.
 /// 15 * 2 * 32 = 16 minutes.
-const LOOKUP_MAX_DURATION_STUCK_SECS: u64 = 15 * PARENT_DEPTH_TOLERANCE as u64;
+const LOOKUP_MAX_DURATION_STUCK_SECS: u64 = 15 * DEFAULT_MAX_PARENT_LOOKUP_DEPTH as u64;
 /// The most common case of child-lookup without peers is receiving block components before the
 /// attestation deadline when the node is lagging behind. Once peers start attesting for the child
 /// lookup at most after 4 seconds, the lookup should gain peers.
@@ -78,6 +77,10 @@ const LOOKUP_MAX_DURATION_NO_PEERS_SECS: u64 = 10;
 /// take at most 2 GB. 200 lookups allow 3 parallel chains of depth 64 (current maximum).
 const MAX_LOOKUPS: usize = 200;
 
+/// Minimum interval between logs of parent lookups rejected for exceeding the max concurrent
+/// parent lookups, to not flood the logs under adversarial gossip.
+const PARENT_LOOKUPS_REJECTED_LOG_INTERVAL: Duration = Duration::from_secs(30);
+
 pub enum BlockComponent<E: EthSpec> {
     Block(DownloadResult<Arc<SignedBeaconBlock<E>>>),
     Blob(DownloadResult<Arc<BlobSidecar<E>>>),
@@ -117,6 +120,22 @@ pub struct BlockLookups<T: BeaconChainTypes> {
     // TODO: Why not index lookups by block_root?
     single_block_lookups: FnvHashMap<SingleLookupId, SingleBlockLookup<T>>,
 
+    /// The maximum length of a parent lookup chain before transitioning to range sync.
+    max_parent_depth: usize,
+
+    /// The maximum number of parent lookup chains searched concurrently.
+    max_parent_lookups: usize,
+
+    /// The last time a rejected parent lookup was logged.
+    last_parent_lookup_rejected_log: Option<Instant>,
+
+    /// Block roots which never trigger a lookup when found to be an unknown parent.
+    parent_lookup_denylist: HashSet<Hash256>,
+
+    /// The number of distinct peers that must return the same block or blobs before they are sent
+    /// for processing.
+    min_peer_agreement: NonZeroUsize,
+
     /// The logger for the import manager.
     log: Logger,
 }
@@ -129,17 +148,72 @@ use lighthouse_network::service::api_types::Id;
 /// and list of peers that claim to have imported this set of block components.
 pub(crate) type BlockLookupSummary = (Id, Hash256, Option<Hash256>, Vec<PeerId>);
 
+/// The status of an active lookup, as returned by `SyncMessage::IsLookupActive`.
+#[derive(Debug, Clone)]
+pub struct LookupStatus {
+    pub id: SingleLookupId,
+    pub block_root: Hash256,
+    pub awaiting_parent: Option<Hash256>,
+    /// Number of lookups awaiting this lookup as their parent.
+    pub child_lookups: usize,
+    pub peer_count: usize,
+    pub block_request_state: String,
+    pub component_requests_state: String,
+}
+
 impl<T: BeaconChainTypes> BlockLookups<T> {
-    pub fn new(log: Logger) -> Self {
+    pub fn new(
+        max_parent_depth: usize,
+        max_parent_lookups: usize,
+        parent_lookup_denylist: HashSet<Hash256>,
+        log: Logger,
+    ) -> Self {
         Self {
             failed_chains: LRUTimeCache::new(Duration::from_secs(
                 FAILED_CHAINS_CACHE_EXPIRY_SECONDS,
             )),
             single_block_lookups: Default::default(),
+            max_parent_depth,
+            max_parent_lookups,
+            last_parent_lookup_rejected_log: None,
+            parent_lookup_denylist,
+            min_peer_agreement: NonZeroUsize::MIN,
             log,
         }
     }
 
+    /// Sets the number of distinct peers that must return the same block or blobs before they are
+    /// sent for processing. Applies to the downloads of active lookups too.
+    pub fn set_min_peer_agreement(&mut self, min_peer_agreement: NonZeroUsize) {
+        self.min_peer_agreement = min_peer_agreement;
+    }
+
+    /// Adds `roots` to the parent lookup denylist, returning the full sorted denylist.
+    pub fn extend_parent_lookup_denylist(&mut self, roots: Vec<Hash256>) -> Vec<Hash256> {
+        self.parent_lookup_denylist.extend(roots);
+        let mut denylist = self
+            .parent_lookup_denylist
+            .iter()
+            .copied()
+            .collect::<Vec<_>>();
+        denylist.sort();
+        denylist
+    }
+
+    /// Returns true if `parent_root` must not be looked up, logging why the lookup is ignored.
+    fn is_parent_denylisted(&self, parent_root: Hash256, block_root: Hash256) -> bool {
+        if !self.parent_lookup_denylist.contains(&parent_root) {
+            return false;
+        }
+        debug!(self.log, "Ignoring unknown parent request";
+            "block_root" => ?block_root,
+            "parent_root" => ?parent_root,
+            "reason" => "parent root is denylisted",
+        );
+        metrics::inc_counter(&metrics::SYNC_PARENT_LOOKUPS_DENYLISTED);
+        true
+    }
+
     #[cfg(test)]
     pub(crate) fn insert_failed_chain(&mut self, block_root: Hash256) {
         self.failed_chains.insert(block_root);
@@ -169,6 +243,40 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
         )
     }
 
+    /// Returns the number of parent lookup chains, counting chains that fork from the same ancestor
+    /// once.
+    fn parent_lookups_count(&self) -> usize {
+        self.active_parent_lookups()
+            .iter()
+            .map(|chain| chain.ancestor())
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    /// Returns the status of the lookup for `block_root`, if any. A lookup that is the parent of
+    /// other lookups is reported with its number of child lookups.
+    pub fn lookup_status(&self, block_root: Hash256) -> Option<LookupStatus> {
+        let (id, lookup) = self
+            .single_block_lookups
+            .iter()
+            .find(|(_, lookup)| lookup.is_for_block(block_root))?;
+        let child_lookups = self
+            .single_block_lookups
+            .values()
+            .filter(|child| child.awaiting_parent() == Some(block_root))
+            .count();
+        let (block_request_state, component_requests_state) = lookup.request_states();
+        Some(LookupStatus {
+            id: *id,
+            block_root,
+            awaiting_parent: lookup.awaiting_parent(),
+            child_lookups,
+            peer_count: lookup.all_peers().len(),
+            block_request_state,
+            component_requests_state,
+        })
+    }
+
     /* Lookup requests */
 
     /// Creates a parent lookup for the block with the given `block_root` and immediately triggers it.
@@ -182,6 +290,31 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
     ) {
         let parent_root = block_component.parent_root();
 
+        if self.is_parent_denylisted(parent_root, block_root) {
+            return;
+        }
+
+        // Bound the number of concurrent parent chains. Existing chains are still allowed to gain
+        // peers and child components.
+        let is_new_chain = !self
+            .single_block_lookups
+            .values()
+            .any(|lookup| lookup.is_for_block(parent_root));
+        if is_new_chain && self.parent_lookups_count() >= self.max_parent_lookups {
+            metrics::inc_counter(&metrics::SYNC_PARENT_LOOKUPS_REJECTED);
+            if self.last_parent_lookup_rejected_log.map_or(true, |last| {
+                last.elapsed() >= PARENT_LOOKUPS_REJECTED_LOG_INTERVAL
+            }) {
+                self.last_parent_lookup_rejected_log = Some(Instant::now());
+                warn!(self.log, "Ignoring parent lookup, reached max";
+                    "block_root" => ?block_root,
+                    "parent_root" => ?parent_root,
+                    "max_parent_lookups" => self.max_parent_lookups,
+                );
+            }
+            return;
+        }
+
         let parent_lookup_exists =
             self.search_parent_of_child(parent_root, block_root, &[peer_id], cx);
         // Only create the child lookup if the parent exists
@@ -236,7 +369,7 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
             let trigger_is_chain_tip = parent_chain.tip == child_block_root_trigger;
 
             if (block_would_extend_chain || trigger_is_chain_tip)
-                && parent_chain.len() >= PARENT_DEPTH_TOLERANCE
+                && parent_chain.len() >= self.max_parent_depth
             {
                 debug!(self.log, "Parent lookup chain too long"; "block_root" => ?block_root_to_search);
 
@@ -246,7 +379,7 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
 
                 // Note: Drop only the chain that's too long until it merges with another chain
                 // that's not too long. Consider this attack: there's a chain of valid unknown
-                // blocks A -> B. A malicious peer builds `PARENT_DEPTH_TOLERANCE` garbage
+                // blocks A -> B. A malicious peer builds `max_parent_depth` garbage
                 // blocks on top of A forming A -> C. The malicious peer forces us to fetch C
                 // from it, which will result in parent A hitting the chain_too_long error. Then
                 // the valid chain A -> B is dropped too.
@@ -401,6 +534,7 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
             "id" => lookup.id,
         );
         metrics::inc_counter(&metrics::SYNC_LOOKUP_CREATED);
+        cx.record_decision(SyncDecision::LookupCreated { id, block_root });
 
         let result = lookup.continue_requests(cx);
         if self.on_lookup_result(id, result, "new_current_lookup", cx) {
@@ -442,6 +576,12 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
         };
 
         let block_root = lookup.block_root();
+        // Custody columns are served by many peers, agreement is only required for components
+        // served by a single peer.
+        let min_peer_agreement = match response_type {
+            ResponseType::Block | ResponseType::Blob => self.min_peer_agreement,
+            ResponseType::CustodyColumn => NonZeroUsize::MIN,
+        };
         let request_state = R::request_state_mut(lookup)
             .map_err(|e| LookupRequestError::BadState(e.to_owned()))?
             .get_state_mut();
@@ -467,7 +607,7 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
                 // Register the download peer here. Once we have received some data over the wire we
                 // attribute it to this peer for scoring latter regardless of how the request was
                 // done.
-                request_state.on_download_success(
+                let disagreeing_peers = request_state.on_download_success(
                     id.req_id,
                     DownloadResult {
                         value: response,
@@ -475,7 +615,21 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
                         seen_timestamp,
                         peer_group,
                     },
+                    min_peer_agreement,
                 )?;
+                for peer_id in disagreeing_peers {
+                    debug!(self.log,
+                        "Lookup peer disagreed with agreed download";
+                        "block_root" => ?block_root,
+                        "peer_id" => %peer_id,
+                        "response_type" => ?response_type,
+                    );
+                    cx.report_peer(
+                        peer_id,
+                        PeerAction::MidToleranceError,
+                        "lookup_peer_disagreement",
+                    );
+                }
                 // continue_request will send for  processing as the request state is AwaitingProcessing
             }
             Err(e) => {
@@ -556,7 +710,12 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
             BlockProcessingResult::Ok(AvailabilityProcessingStatus::Imported(_))
             | BlockProcessingResult::Err(BlockError::DuplicateFullyImported(..)) => {
                 // Successfully imported
-                request_state.on_processing_success()?;
+                let peer_group = request_state.on_processing_success()?;
+                metrics::observe_vec(
+                    &metrics::SYNC_LOOKUP_COMPONENT_PEERS,
+                    &[R::response_type().into()],
+                    peer_group.all().count() as f64,
+                );
                 Action::Continue
             }
 
@@ -565,7 +724,12 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
             }) => {
                 // `on_processing_success` is called here to ensure the request state is updated prior to checking
                 // if both components have been processed.
-                request_state.on_processing_success()?;
+                let peer_group = request_state.on_processing_success()?;
+                metrics::observe_vec(
+                    &metrics::SYNC_LOOKUP_COMPONENT_PEERS,
+                    &[R::response_type().into()],
+                    peer_group.all().count() as f64,
+                );
 
                 if lookup.all_components_processed() {
                     // We don't request for other block components until being sure that the block has
@@ -686,6 +850,10 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
                 let peers = lookup.all_peers();
                 lookup.set_awaiting_parent(parent_root);
                 debug!(self.log, "Marking lookup as awaiting parent"; "id" => lookup.id, "block_root" => ?block_root, "parent_root" => ?parent_root);
+                if self.is_parent_denylisted(parent_root, block_root) {
+                    // The lookup can never complete without its parent, drop it
+                    return Err(LookupRequestError::Failed);
+                }
                 self.search_parent_of_child(parent_root, block_root, &peers, cx);
                 Ok(LookupResult::Pending)
             }
@@ -794,7 +962,9 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
             Err(LookupRequestError::UnknownLookup) => false,
             Err(error) => {
                 debug!(self.log, "Dropping lookup on request error"; "id" => id, "source" => source, "error" => ?error);
-                metrics::inc_counter_vec(&metrics::SYNC_LOOKUP_DROPPED, &[error.into()]);
+                let reason: &'static str = error.into();
+                metrics::inc_counter_vec(&metrics::SYNC_LOOKUP_DROPPED, &[reason]);
+                cx.record_decision(SyncDecision::LookupDropped { id, reason });
                 self.drop_lookup_and_children(id);
                 self.update_metrics();
                 false
@@ -804,11 +974,48 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
 
     /* Helper functions */
 
-    /// Drops all the single block requests and returns how many requests were dropped.
-    pub fn drop_single_block_requests(&mut self) -> usize {
-        let requests_to_drop = self.single_block_lookups.len();
-        self.single_block_lookups.clear();
-        requests_to_drop
+    /// Drops all the single block requests and returns how many requests were dropped, as a tuple
+    /// of `(single_block_lookups, parent_lookups)`. Lookups awaiting a parent are counted as part
+    /// of a parent lookup chain.
+    ///
+    /// Lookups that are not awaiting a parent and are downloading or holding custody columns are
+    /// retained, so that the columns can be processed once the execution engine is back online
+    /// without downloading them again.
+    pub fn drop_single_block_requests(&mut self) -> (usize, usize) {
+        let mut single_block_requests_to_drop = 0;
+        let mut parent_requests_to_drop = 0;
+        self.single_block_lookups.retain(|_, lookup| {
+            if lookup.awaiting_parent().is_some() {
+                parent_requests_to_drop += 1;
+                false
+            } else if lookup.has_pending_custody_columns() {
+                true
+            } else {
+                single_block_requests_to_drop += 1;
+                false
+            }
+        });
+        (single_block_requests_to_drop, parent_requests_to_drop)
+    }
+
+    /// Returns the ids of the lookups that are currently active.
+    pub fn active_lookup_ids(&self) -> HashSet<SingleLookupId> {
+        self.single_block_lookups.keys().copied().collect()
+    }
+
+    /// Attempts to make progress on all lookups, for example to send for processing the
+    /// components retained while the execution engine was offline.
+    pub fn continue_all_lookups(&mut self, cx: &mut SyncNetworkContext<T>) {
+        let mut lookup_results = vec![]; // < need to buffer lookup results to not re-borrow &mut self
+
+        for (id, lookup) in self.single_block_lookups.iter_mut() {
+            let result = lookup.continue_requests(cx);
+            lookup_results.push((*id, result));
+        }
+
+        for (id, result) in lookup_results {
+            self.on_lookup_result(id, result, "continue_all_lookups", cx);
+        }
     }
 
     pub fn update_metrics(&self) {
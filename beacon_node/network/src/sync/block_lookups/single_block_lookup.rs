@@ -1,4 +1,8 @@
-use super::{BlockComponent, PeerId, SINGLE_BLOCK_LOOKUP_MAX_ATTEMPTS};
+use super::{
+    BlockComponent, PeerId, SINGLE_BLOCK_LOOKUP_MAX_ATTEMPTS,
+    SINGLE_BLOCK_LOOKUP_MAX_PROCESSOR_SEND_RETRIES,
+};
+use crate::metrics;
 use crate::sync::block_lookups::common::RequestState;
 use crate::sync::network_context::{
     LookupRequestResult, PeerGroup, ReqId, RpcRequestSendError, SendErrorProcessor,
@@ -10,6 +14,7 @@ use lighthouse_network::service::api_types::Id;
 use parking_lot::RwLock;
 use std::collections::HashSet;
 use std::fmt::Debug;
+use std::num::NonZeroUsize;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use store::Hash256;
@@ -108,6 +113,20 @@ impl<T: BeaconChainTypes> SingleBlockLookup<T> {
             .map(|block| block.slot())
     }
 
+    /// Returns true if this lookup is downloading custody columns or holds downloaded custody
+    /// columns that have not been sent for processing yet.
+    pub fn has_pending_custody_columns(&self) -> bool {
+        match &self.component_requests {
+            ComponentRequests::ActiveCustodyRequest(request) => matches!(
+                request.state.state,
+                State::Downloading { .. } | State::AwaitingProcess { .. }
+            ),
+            ComponentRequests::WaitingForBlock
+            | ComponentRequests::ActiveBlobRequest { .. }
+            | ComponentRequests::NotNeeded { .. } => false,
+        }
+    }
+
     /// Get the block root that is being requested.
     pub fn block_root(&self) -> Hash256 {
         self.block_root
@@ -283,7 +302,19 @@ impl<T: BeaconChainTypes> SingleBlockLookup<T> {
                 return Err(LookupRequestError::TooManyAttempts { cannot_process });
             }
 
-            let peers = self.peers.clone();
+            // Request a component awaiting agreement from a peer that has not returned it yet.
+            let responded_peers = request_state.responded_peers();
+            let peers = if responded_peers.is_empty() {
+                self.peers.clone()
+            } else {
+                Arc::new(RwLock::new(
+                    self.peers
+                        .read()
+                        .difference(&responded_peers)
+                        .copied()
+                        .collect(),
+                ))
+            };
             let request = R::request_state_mut(self)
                 .map_err(|e| LookupRequestError::BadState(e.to_owned()))?;
 
@@ -321,7 +352,33 @@ impl<T: BeaconChainTypes> SingleBlockLookup<T> {
                 // Lookup sync event safety: If `send_for_processing` returns Ok() we are guaranteed
                 // that `BlockLookups::on_processing_result` will be called exactly once with this
                 // lookup_id
-                return R::send_for_processing(id, result, cx);
+                return match R::send_for_processing(id, result, cx) {
+                    // The execution engine is offline. Retain the downloaded result instead of
+                    // dropping it. Lookup sync event safety: `BlockLookups::continue_all_lookups`
+                    // is called once the execution engine is back online.
+                    Err(LookupRequestError::SendFailedProcessor(
+                        SendErrorProcessor::ProcessorNotAvailable,
+                    )) => request.get_state_mut().revert_to_awaiting_processing(),
+                    // The beacon processor channel may be temporarily full. Retain the downloaded
+                    // result to send it again instead of downloading it again. Lookup sync event
+                    // safety: `BlockLookups::continue_all_lookups` is called on the next prune
+                    // interval, see `SyncNetworkContext::take_deferred_processor_sends`.
+                    Err(LookupRequestError::SendFailedProcessor(SendErrorProcessor::SendError)) => {
+                        if request.get_state_mut().on_processor_send_failure()? {
+                            cx.defer_processor_send();
+                            Ok(())
+                        } else {
+                            metrics::inc_counter_vec(
+                                &metrics::SYNC_LOOKUP_PROCESSOR_SEND_DROPPED,
+                                &[R::response_type().into()],
+                            );
+                            Err(LookupRequestError::SendFailedProcessor(
+                                SendErrorProcessor::SendError,
+                            ))
+                        }
+                    }
+                    result => result,
+                };
             }
             // Lookup sync event safety: If the request is not in `AwaitingDownload` or
             // `AwaitingProcessing` state it is guaranteed to receive some event to make progress.
@@ -334,6 +391,24 @@ impl<T: BeaconChainTypes> SingleBlockLookup<T> {
         Ok(())
     }
 
+    /// Returns a description of the state of the block request and of the block components
+    /// requests.
+    pub fn request_states(&self) -> (String, String) {
+        let block_request_state = format!("{:?}", self.block_request_state.state.state);
+        let component_requests_state = match &self.component_requests {
+            ComponentRequests::WaitingForBlock => "WaitingForBlock".to_owned(),
+            ComponentRequests::ActiveBlobRequest(request, expected_blobs) => format!(
+                "Blobs({:?}, expected {expected_blobs})",
+                request.state.state
+            ),
+            ComponentRequests::ActiveCustodyRequest(request) => {
+                format!("CustodyColumns({:?})", request.state.state)
+            }
+            ComponentRequests::NotNeeded(reason) => format!("NotNeeded({reason})"),
+        };
+        (block_request_state, component_requests_state)
+    }
+
     /// Get all unique peers that claim to have imported this set of block components
     pub fn all_peers(&self) -> Vec<PeerId> {
         self.peers.read().iter().copied().collect()
@@ -430,22 +505,31 @@ pub enum State<T: Clone> {
 }
 
 /// Object representing the state of a single block or blob lookup request.
-#[derive(Debug)]
+#[derive(Derivative)]
+#[derivative(Debug)]
 pub struct SingleLookupRequestState<T: Clone> {
     /// State of this request.
     state: State<T>,
+    /// Distinct downloaded results not yet returned by enough peers to be sent for processing,
+    /// each with the peers that returned it.
+    #[derivative(Debug = "ignore")]
+    pending_agreement: Vec<(DownloadResult<T>, HashSet<PeerId>)>,
     /// How many times have we attempted to process this block or blob.
     failed_processing: u8,
     /// How many times have we attempted to download this block or blob.
     failed_downloading: u8,
+    /// How many times have we failed to send this block or blob to the beacon processor.
+    failed_processor_sends: u8,
 }
 
 impl<T: Clone> SingleLookupRequestState<T> {
     pub fn new() -> Self {
         Self {
             state: State::AwaitingDownload("not started"),
+            pending_agreement: vec![],
             failed_processing: 0,
             failed_downloading: 0,
+            failed_processor_sends: 0,
         }
     }
 
@@ -550,11 +634,30 @@ impl<T: Clone> SingleLookupRequestState<T> {
         }
     }
 
+    /// Returns the peers that returned a downloaded result still awaiting agreement.
+    pub fn responded_peers(&self) -> HashSet<PeerId> {
+        self.pending_agreement
+            .iter()
+            .flat_map(|(_, peers)| peers.iter().copied())
+            .collect()
+    }
+
+    /// Registers a successful download. The result is only considered for processing once
+    /// `min_peer_agreement` distinct peers have returned it, otherwise the request returns to
+    /// `AwaitingDownload` to fetch it from another peer without counting as a failed attempt.
+    ///
+    /// Results that disagree are tracked separately, so a peer answering first cannot veto the
+    /// result of the other peers. Once a result is agreed upon, the others are dropped and the
+    /// peers that returned them are returned to be penalized.
     pub fn on_download_success(
         &mut self,
         req_id: ReqId,
         result: DownloadResult<T>,
-    ) -> Result<(), LookupRequestError> {
+        min_peer_agreement: NonZeroUsize,
+    ) -> Result<Vec<PeerId>, LookupRequestError>
+    where
+        T: PartialEq,
+    {
         match &self.state {
             State::Downloading(expected_req_id) => {
                 if req_id != *expected_req_id {
@@ -563,8 +666,36 @@ impl<T: Clone> SingleLookupRequestState<T> {
                         req_id,
                     });
                 }
-                self.state = State::AwaitingProcess(result);
-                Ok(())
+
+                let response_peers: HashSet<PeerId> = result.peer_group.all().copied().collect();
+                let index = match self
+                    .pending_agreement
+                    .iter()
+                    .position(|(candidate, _)| candidate.value == result.value)
+                {
+                    Some(index) => {
+                        self.pending_agreement[index].1.extend(response_peers);
+                        index
+                    }
+                    None => {
+                        self.pending_agreement.push((result, response_peers));
+                        self.pending_agreement.len() - 1
+                    }
+                };
+
+                if self.pending_agreement[index].1.len() < min_peer_agreement.get() {
+                    self.state = State::AwaitingDownload("awaiting peer agreement");
+                    return Ok(vec![]);
+                }
+
+                let (agreed, _) = self.pending_agreement.swap_remove(index);
+                let disagreeing_peers = self
+                    .pending_agreement
+                    .drain(..)
+                    .flat_map(|(_, peers)| peers)
+                    .collect();
+                self.state = State::AwaitingProcess(agreed);
+                Ok(disagreeing_peers)
             }
             other => Err(LookupRequestError::BadState(format!(
                 "Bad state on_download_success expected Downloading got {other}"
@@ -599,6 +730,25 @@ impl<T: Clone> SingleLookupRequestState<T> {
         }
     }
 
+    /// Registers a failure to send the downloaded result to the beacon processor. Returns `true`
+    /// if the result is retained in `AwaitingProcess` to be sent again, which is the case for the
+    /// first `SINGLE_BLOCK_LOOKUP_MAX_PROCESSOR_SEND_RETRIES` failures.
+    pub fn on_processor_send_failure(&mut self) -> Result<bool, LookupRequestError> {
+        match &self.state {
+            State::Processing(result) => {
+                if self.failed_processor_sends >= SINGLE_BLOCK_LOOKUP_MAX_PROCESSOR_SEND_RETRIES {
+                    return Ok(false);
+                }
+                self.failed_processor_sends = self.failed_processor_sends.saturating_add(1);
+                self.state = State::AwaitingProcess(result.clone());
+                Ok(true)
+            }
+            other => Err(LookupRequestError::BadState(format!(
+                "Bad state on_processor_send_failure expected Processing got {other}"
+            ))),
+        }
+    }
+
     /// Registers a failure in processing a block.
     pub fn on_processing_failure(&mut self) -> Result<PeerGroup, LookupRequestError> {
         match &self.state {
@@ -614,11 +764,13 @@ impl<T: Clone> SingleLookupRequestState<T> {
         }
     }
 
-    pub fn on_processing_success(&mut self) -> Result<(), LookupRequestError> {
+    /// Registers a successful processing, returning the peers that served the processed result.
+    pub fn on_processing_success(&mut self) -> Result<PeerGroup, LookupRequestError> {
         match &self.state {
-            State::Processing(_) => {
+            State::Processing(result) => {
+                let peers_source = result.peer_group.clone();
                 self.state = State::Processed("processing success");
-                Ok(())
+                Ok(peers_source)
             }
             other => Err(LookupRequestError::BadState(format!(
                 "Bad state on_processing_success expected Processing got {other}"
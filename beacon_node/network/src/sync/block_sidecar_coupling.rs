@@ -4,6 +4,7 @@ use beacon_chain::{
 use std::{
     collections::{HashMap, VecDeque},
     sync::Arc,
+    time::{Duration, Instant},
 };
 use types::{
     BlobSidecar, ChainSpec, ColumnIndex, DataColumnSidecar, EthSpec, Hash256, RuntimeVariableList,
@@ -28,6 +29,8 @@ pub struct RangeBlockComponentsRequest<E: EthSpec> {
     /// Used to determine if the number of data columns stream termination this accumulator should
     /// wait for. This may be less than the number of `expects_custody_columns` due to request batching.
     num_custody_column_requests: Option<usize>,
+    /// When the request was created, to detect requests that never complete.
+    created: Instant,
 }
 
 impl<E: EthSpec> RangeBlockComponentsRequest<E> {
@@ -46,9 +49,15 @@ impl<E: EthSpec> RangeBlockComponentsRequest<E> {
             expects_blobs,
             expects_custody_columns,
             num_custody_column_requests,
+            created: Instant::now(),
         }
     }
 
+    /// Returns how long this request has been active.
+    pub fn age(&self) -> Duration {
+        self.created.elapsed()
+    }
+
     pub fn add_blocks(&mut self, blocks: Vec<Arc<SignedBeaconBlock<E>>>) {
         for block in blocks {
             self.blocks.push_back(block);
@@ -211,6 +220,26 @@ impl<E: EthSpec> RangeBlockComponentsRequest<E> {
         Ok(rpc_blocks)
     }
 
+    /// Returns the components whose streams have not terminated yet.
+    pub fn outstanding_components(&self) -> Vec<String> {
+        let mut outstanding = vec![];
+        if !self.is_blocks_stream_terminated {
+            outstanding.push("blocks".to_owned());
+        }
+        if self.expects_blobs && !self.is_sidecars_stream_terminated {
+            outstanding.push("blobs".to_owned());
+        }
+        if let Some(expects_custody_column_responses) = self.num_custody_column_requests {
+            if self.custody_columns_streams_terminated < expects_custody_column_responses {
+                outstanding.push(format!(
+                    "custody_columns({}/{expects_custody_column_responses})",
+                    self.custody_columns_streams_terminated
+                ));
+            }
+        }
+        outstanding
+    }
+
     pub fn is_finished(&self) -> bool {
         if !self.is_blocks_stream_terminated {
             return false;
@@ -0,0 +1,188 @@
+//! A bounded in-memory log of the most recent significant decisions taken by sync.
+//!
+//! Sync stalls are often intermittent and the logs explaining them may have rolled over by the
+//! time they are noticed. This log keeps the last few decisions so that they can be dumped via the
+//! HTTP API after the fact.
+
+use super::block_lookups::SingleLookupId;
+use super::range_sync::ChainId;
+use beacon_chain::validator_monitor::timestamp_now;
+use beacon_chain::EngineState;
+use lighthouse_network::PeerId;
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::Duration;
+use types::{Epoch, Hash256, Slot};
+
+/// A significant decision taken by sync.
+#[derive(Debug, Clone)]
+pub enum SyncDecision {
+    /// A peer's sync status changed. Statuses which do not change the peer's sync status are not
+    /// recorded, as peers re-status periodically and would flood the log.
+    PeerSyncStatusChanged {
+        peer_id: PeerId,
+        sync_status: &'static str,
+    },
+    ChainAdded {
+        id: ChainId,
+        sync_type: &'static str,
+        start_epoch: Epoch,
+        target_head_slot: Slot,
+        target_head_root: Hash256,
+    },
+    ChainRemoved {
+        id: ChainId,
+        sync_type: &'static str,
+        reason: String,
+    },
+    LookupCreated {
+        id: SingleLookupId,
+        block_root: Hash256,
+    },
+    LookupDropped {
+        id: SingleLookupId,
+        reason: &'static str,
+    },
+    ExecutionEngineState(EngineState),
+}
+
+impl fmt::Display for SyncDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyncDecision::PeerSyncStatusChanged {
+                peer_id,
+                sync_status,
+            } => write!(f, "peer {peer_id} is now {sync_status}"),
+            SyncDecision::ChainAdded {
+                id,
+                sync_type,
+                start_epoch,
+                target_head_slot,
+                target_head_root,
+            } => write!(
+                f,
+                "{sync_type} chain {id} added from epoch {start_epoch} to slot {target_head_slot} \
+                 root {target_head_root:?}"
+            ),
+            SyncDecision::ChainRemoved {
+                id,
+                sync_type,
+                reason,
+            } => write!(f, "{sync_type} chain {id} removed: {reason}"),
+            SyncDecision::LookupCreated { id, block_root } => {
+                write!(f, "lookup {id} created for block {block_root:?}")
+            }
+            SyncDecision::LookupDropped { id, reason } => {
+                write!(f, "lookup {id} dropped: {reason}")
+            }
+            SyncDecision::ExecutionEngineState(state) => {
+                write!(f, "execution engine {state:?}")
+            }
+        }
+    }
+}
+
+/// A `SyncDecision` with the time at which it was taken.
+#[derive(Debug, Clone)]
+pub struct SyncDecisionEntry {
+    /// Duration since the UNIX epoch.
+    pub timestamp: Duration,
+    pub decision: SyncDecision,
+}
+
+/// A fixed-size ring buffer of the most recent `SyncDecision`s. A capacity of zero disables it.
+pub struct SyncDecisionLog {
+    capacity: usize,
+    entries: VecDeque<SyncDecisionEntry>,
+}
+
+impl SyncDecisionLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Records a decision, evicting the oldest one if the log is full.
+    pub fn record(&mut self, decision: SyncDecision) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(SyncDecisionEntry {
+            timestamp: timestamp_now(),
+            decision,
+        });
+    }
+
+    /// Returns the recorded decisions, oldest first.
+    pub fn entries(&self) -> Vec<SyncDecisionEntry> {
+        self.entries.iter().cloned().collect()
+    }
+
+    /// Returns the recorded decisions, oldest first, clearing the log if `reset` is `true`.
+    pub fn dump(&mut self, reset: bool) -> Vec<SyncDecisionEntry> {
+        if reset {
+            self.entries.drain(..).collect()
+        } else {
+            self.entries()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup_created(id: SingleLookupId) -> SyncDecision {
+        SyncDecision::LookupCreated {
+            id,
+            block_root: Hash256::ZERO,
+        }
+    }
+
+    fn recorded_ids(log: &SyncDecisionLog) -> Vec<SingleLookupId> {
+        log.entries()
+            .into_iter()
+            .map(|entry| match entry.decision {
+                SyncDecision::LookupCreated { id, .. } => id,
+                other => panic!("unexpected decision {other}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn evicts_oldest_decisions() {
+        let mut log = SyncDecisionLog::new(3);
+        for id in 0..5 {
+            log.record(lookup_created(id));
+        }
+        assert_eq!(recorded_ids(&log), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn dump_with_reset_clears_log() {
+        let mut log = SyncDecisionLog::new(3);
+        log.record(lookup_created(0));
+        log.record(lookup_created(1));
+
+        assert_eq!(log.dump(false).len(), 2);
+        assert_eq!(recorded_ids(&log), vec![0, 1]);
+
+        assert_eq!(log.dump(true).len(), 2);
+        assert!(log.entries().is_empty());
+
+        log.record(lookup_created(2));
+        assert_eq!(recorded_ids(&log), vec![2]);
+    }
+
+    #[test]
+    fn zero_capacity_is_disabled() {
+        let mut log = SyncDecisionLog::new(0);
+        log.record(lookup_created(0));
+        assert!(log.entries().is_empty());
+    }
+}
@@ -34,13 +34,15 @@
 //! search for the block and subsequently search for parents if needed.
 
 use super::backfill_sync::{BackFillSync, ProcessResult, SyncStart};
-use super::block_lookups::BlockLookups;
+use super::block_lookups::{BlockLookups, LookupStatus};
+use super::decision_log::{SyncDecision, SyncDecisionEntry};
 use super::network_context::{
     CustodyByRootResult, RangeBlockComponent, RangeRequestId, RpcEvent, SyncNetworkContext,
 };
 use super::peer_sampling::{Sampling, SamplingConfig, SamplingResult};
 use super::peer_sync_info::{remote_sync_type, PeerSyncType};
-use super::range_sync::{RangeSync, RangeSyncType, EPOCHS_PER_BATCH};
+use super::range_sync::{RangeSync, RangeSyncProgress, RangeSyncType, EPOCHS_PER_BATCH};
+use crate::metrics;
 use crate::network_beacon_processor::{ChainSegmentProcessId, NetworkBeaconProcessor};
 use crate::service::NetworkMessage;
 use crate::status::ToStatusMessage;
@@ -60,22 +62,22 @@ use lighthouse_network::service::api_types::{
     DataColumnsByRangeRequestId, DataColumnsByRootRequestId, DataColumnsByRootRequester, Id,
     SamplingId, SamplingRequester, SingleLookupReqId, SyncRequestId,
 };
-use lighthouse_network::types::{NetworkGlobals, SyncState};
+use lighthouse_network::types::{BackFillState, NetworkGlobals, SyncStallReason, SyncState};
 use lighthouse_network::SyncInfo;
 use lighthouse_network::{PeerAction, PeerId};
 use lru_cache::LRUTimeCache;
 use slog::{crit, debug, error, info, o, trace, warn, Logger};
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
 use std::ops::Sub;
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::mpsc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, oneshot};
 use types::{
-    BlobSidecar, DataColumnSidecar, EthSpec, ForkContext, Hash256, SignedBeaconBlock, Slot,
+    BlobSidecar, ColumnIndex, DataColumnSidecar, EthSpec, ForkContext, Hash256, SignedBeaconBlock,
+    Slot,
 };
 
-#[cfg(test)]
-use types::ColumnIndex;
-
 /// The number of slots ahead of us that is allowed before requesting a long-range (batch)  Sync
 /// from a peer. If a peer is within this tolerance (forwards or backwards), it is treated as a
 /// fully sync'd peer.
@@ -90,11 +92,20 @@ pub const SLOT_IMPORT_TOLERANCE: usize = 32;
 /// arbitrary number that covers a full slot, but allows recovery if sync get stuck for a few slots.
 const NOTIFIED_UNKNOWN_ROOT_EXPIRY_SECONDS: u64 = 30;
 
+/// The maximum number of distinct unknown roots from attestations a single peer may trigger
+/// lookups for within a slot. Deduplication alone does not protect against a peer sending
+/// thousands of distinct roots. Honest peers rarely forward more than a few unknown roots per slot.
+pub const MAX_UNKNOWN_ROOT_LOOKUPS_PER_PEER_PER_SLOT: usize = 16;
+
+/// A components_by_range request is assumed to be leaked if still active after this duration. Its
+/// individual requests are subject to RPC timeouts, so it should complete much sooner.
+const LEAKED_RANGE_REQUEST_MAX_AGE: Duration = Duration::from_secs(300);
+
 #[derive(Debug)]
 /// A message that can be sent to the sync manager thread.
 pub enum SyncMessage<E: EthSpec> {
-    /// A useful peer has been discovered.
-    AddPeer(PeerId, SyncInfo),
+    /// A useful peer has been discovered, with the fork digest of its latest status.
+    AddPeer(PeerId, SyncInfo, [u8; 4]),
 
     /// Force trigger range sync for a set of peers given a head they claim to have imported. Used
     /// by block lookup to trigger range sync if a parent chain grows too large.
@@ -146,6 +157,14 @@ pub enum SyncMessage<E: EthSpec> {
     /// the request.
     SampleBlock(Hash256, Slot),
 
+    /// Replace the sampling config used by new sampling requests. Requests already in flight keep
+    /// their original config.
+    SetSamplingConfig(SamplingConfig),
+
+    /// Set the number of distinct peers that must return the same block or blobs before a lookup
+    /// sends them for processing. A value of 1 trusts the first peer to respond.
+    SetLookupPeerAgreement(NonZeroUsize),
+
     /// A peer has disconnected.
     Disconnect(PeerId),
 
@@ -166,6 +185,9 @@ pub enum SyncMessage<E: EthSpec> {
     BlockComponentProcessed {
         process_type: BlockProcessType,
         result: BlockProcessingResult,
+        /// Time spent in each processing stage, only known for blocks that reached the beacon
+        /// chain.
+        stage_timings: Option<BlockProcessingStageTimings>,
     },
 
     /// Sample data column verified
@@ -176,6 +198,33 @@ pub enum SyncMessage<E: EthSpec> {
 
     /// A block from gossip has completed processing,
     GossipBlockProcessResult { block_root: Hash256, imported: bool },
+
+    /// Requests the recent significant decisions taken by sync, oldest first. If `reset` is
+    /// `true` the decision log is cleared once dumped.
+    DumpDecisionLog {
+        reset: bool,
+        sender: oneshot::Sender<Vec<SyncDecisionEntry>>,
+    },
+
+    /// Requests the status of the lookup for a block root, `None` if there is no such lookup.
+    IsLookupActive(Hash256, oneshot::Sender<Option<LookupStatus>>),
+
+    /// Sync has no connected peers custodying this column, request the network service to
+    /// discover peers on its subnet.
+    RequestPeersForColumn(ColumnIndex),
+
+    /// Requests the columns this node needs which no connected peer can serve.
+    GetColumnsWithoutCustodyPeers(oneshot::Sender<Vec<ColumnIndex>>),
+
+    /// Requests sync's view on the execution engine state, which lookups and range sync are paused
+    /// upon.
+    GetEngineStateView(oneshot::Sender<EngineState>),
+
+    /// Adds block roots to the parent lookup denylist, responding with the full denylist.
+    DenylistParentRoots {
+        roots: Vec<Hash256>,
+        sender: oneshot::Sender<Vec<Hash256>>,
+    },
 }
 
 /// The type of processing specified for a received block.
@@ -203,6 +252,36 @@ pub enum BlockProcessingResult {
     Ignored,
 }
 
+/// Time spent by the beacon chain in each stage of processing a block received via RPC.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BlockProcessingStageTimings {
+    /// Time to complete gossip and consensus verification, including the state transition.
+    pub consensus_verification: Option<Duration>,
+    /// Additional time waiting for the execution layer to verify the payload.
+    pub execution_verification: Option<Duration>,
+    /// Time to check data availability and import the block, including the database write.
+    pub import: Option<Duration>,
+}
+
+impl BlockProcessingStageTimings {
+    /// Records the known stage timings in the `SYNC_BLOCK_PROCESSING_STAGE_TIMES` histogram.
+    pub fn observe(&self) {
+        for (stage, duration) in [
+            ("consensus_verification", self.consensus_verification),
+            ("execution_verification", self.execution_verification),
+            ("import", self.import),
+        ] {
+            if let Some(duration) = duration {
+                metrics::observe_timer_vec(
+                    &metrics::SYNC_BLOCK_PROCESSING_STAGE_TIMES,
+                    &[stage],
+                    duration,
+                );
+            }
+        }
+    }
+}
+
 /// The result of processing multiple blocks (a chain segment).
 #[derive(Debug)]
 pub enum BatchProcessResult {
@@ -236,6 +315,9 @@ pub struct SyncManager<T: BeaconChainTypes> {
     /// The object handling long-range batch load-balanced syncing.
     range_sync: RangeSync<T>,
 
+    /// Observations of the local head while range syncing, to estimate the time to sync.
+    range_sync_progress: RangeSyncProgress,
+
     /// Backfill syncing.
     backfill_sync: BackFillSync<T>,
 
@@ -245,8 +327,27 @@ pub struct SyncManager<T: BeaconChainTypes> {
     /// one event is useful, the rest generating log noise and wasted cycles
     notified_unknown_roots: LRUTimeCache<(PeerId, Hash256)>,
 
+    /// The slot and number of unknown root lookups triggered by each peer in that slot, to
+    /// rate-limit `UnknownBlockHashFromAttestation` events per peer.
+    unknown_root_lookups_per_peer: HashMap<PeerId, (Slot, usize)>,
+
     sampling: Sampling<T>,
 
+    /// The fork digest of the latest status of each peer, to detect peers changing forks.
+    peer_fork_digests: HashMap<PeerId, [u8; 4]>,
+
+    /// The number of consecutive advanced statuses of synced peers which have not yet reached
+    /// `NetworkConfig::advanced_status_grace`.
+    advanced_statuses: HashMap<PeerId, usize>,
+
+    /// Whether the columns without custody peers have been reported after the first status
+    /// exchange with peers.
+    reported_columns_without_custody_peers: bool,
+
+    /// Whether we became synced but are holding off subscribing to the core topics until
+    /// `NetworkConfig::min_synced_peers_for_core_topics` synced peers are connected.
+    core_topics_subscription_pending: bool,
+
     /// The logger for the import manager.
     log: Logger,
 }
@@ -284,6 +385,25 @@ pub fn spawn<T: BeaconChainTypes>(
     executor.spawn(async move { Box::pin(sync_manager.main()).await }, "sync");
 }
 
+/// Exports the start and target slots of the range sync chain being synced, separately for
+/// finalized and head chains. The gauges of the type not being synced are reset to zero.
+fn update_range_sync_slot_metrics(state: Option<(RangeSyncType, Slot, Slot)>) {
+    let slots_of = |sync_type: RangeSyncType| match state {
+        Some((state_type, start_slot, target_slot)) if state_type == sync_type => {
+            (start_slot.as_u64() as i64, target_slot.as_u64() as i64)
+        }
+        _ => (0, 0),
+    };
+
+    let (start_slot, target_slot) = slots_of(RangeSyncType::Finalized);
+    metrics::set_gauge(&metrics::SYNC_RANGE_FINALIZED_START_SLOT, start_slot);
+    metrics::set_gauge(&metrics::SYNC_RANGE_FINALIZED_TARGET_SLOT, target_slot);
+
+    let (start_slot, target_slot) = slots_of(RangeSyncType::Head);
+    metrics::set_gauge(&metrics::SYNC_RANGE_HEAD_START_SLOT, start_slot);
+    metrics::set_gauge(&metrics::SYNC_RANGE_HEAD_TARGET_SLOT, target_slot);
+}
+
 impl<T: BeaconChainTypes> SyncManager<T> {
     pub(crate) fn new(
         beacon_chain: Arc<BeaconChain<T>>,
@@ -295,6 +415,7 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         log: slog::Logger,
     ) -> Self {
         let network_globals = beacon_processor.network_globals.clone();
+        metrics::set_sync_state(&network_globals.sync_state());
         Self {
             chain: beacon_chain.clone(),
             input_channel: sync_recv,
@@ -309,16 +430,39 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                 beacon_chain.clone(),
                 log.new(o!("service" => "range_sync")),
             ),
+            range_sync_progress: RangeSyncProgress::default(),
             backfill_sync: BackFillSync::new(
                 beacon_chain.clone(),
                 network_globals,
                 log.new(o!("service" => "backfill_sync")),
             ),
-            block_lookups: BlockLookups::new(log.new(o!("service"=> "lookup_sync"))),
+            block_lookups: BlockLookups::new(
+                beacon_processor
+                    .network_globals
+                    .config
+                    .max_parent_lookup_depth,
+                beacon_processor
+                    .network_globals
+                    .config
+                    .max_concurrent_parent_lookups,
+                beacon_processor
+                    .network_globals
+                    .config
+                    .parent_lookup_denylist
+                    .iter()
+                    .copied()
+                    .collect(),
+                log.new(o!("service"=> "lookup_sync")),
+            ),
             notified_unknown_roots: LRUTimeCache::new(Duration::from_secs(
                 NOTIFIED_UNKNOWN_ROOT_EXPIRY_SECONDS,
             )),
+            unknown_root_lookups_per_peer: HashMap::new(),
             sampling: Sampling::new(sampling_config, log.new(o!("service" => "sampling"))),
+            peer_fork_digests: HashMap::new(),
+            advanced_statuses: HashMap::new(),
+            reported_columns_without_custody_peers: false,
+            core_topics_subscription_pending: false,
             log: log.clone(),
         }
     }
@@ -368,6 +512,46 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         self.sampling.get_request_status(block_root, index)
     }
 
+    /// Recreates backfill sync from the current anchor of the store.
+    #[cfg(test)]
+    pub(crate) fn restart_backfill_sync(&mut self) {
+        self.backfill_sync = BackFillSync::new(
+            self.chain.clone(),
+            self.network.beacon_processor().network_globals.clone(),
+            self.log.new(o!("service" => "backfill_sync")),
+        );
+    }
+
+    #[cfg(test)]
+    pub(crate) fn is_supernode(&self) -> bool {
+        self.network.is_supernode()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn custody_peer_coverage(&self) -> Vec<(ColumnIndex, usize)> {
+        self.network.custody_peer_coverage()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_preferred_custody_peer(
+        &mut self,
+        column_index: ColumnIndex,
+        peer_id: PeerId,
+    ) {
+        self.network
+            .set_preferred_custody_peer(column_index, peer_id)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn preferred_custody_peer(&self, column_index: ColumnIndex) -> Option<PeerId> {
+        self.network.preferred_custody_peer(column_index)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn get_random_custodial_peer(&self, column_index: ColumnIndex) -> Option<PeerId> {
+        self.network.get_random_custodial_peer(column_index)
+    }
+
     #[cfg(test)]
     pub(crate) fn range_sync_state(&self) -> super::range_sync::SyncChainStatus {
         self.range_sync.state()
@@ -393,7 +577,9 @@ impl<T: BeaconChainTypes> SyncManager<T> {
     ///
     /// If the peer is within the `SLOT_IMPORT_TOLERANCE`, then it's head is sufficiently close to
     /// ours that we consider it fully sync'd with respect to our current chain.
-    fn add_peer(&mut self, peer_id: PeerId, remote: SyncInfo) {
+    fn add_peer(&mut self, peer_id: PeerId, remote: SyncInfo, fork_digest: [u8; 4]) {
+        self.check_peer_fork_digest(peer_id, fork_digest);
+
         // ensure the beacon chain still exists
         let status = self.chain.status_message();
         let local = SyncInfo {
@@ -404,7 +590,7 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         };
 
         let sync_type = remote_sync_type(&local, &remote, &self.chain);
-
+        let sync_type = self.apply_advanced_status_grace(peer_id, sync_type);
         // update the state of the peer.
         let is_still_connected = self.update_peer_sync_state(&peer_id, &local, &remote, &sync_type);
         if is_still_connected {
@@ -440,6 +626,74 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         }
     }
 
+    /// Keeps a synced peer as `FullySynced` until it reports an advanced status
+    /// `NetworkConfig::advanced_status_grace` times in a row, so that a synced peer appearing
+    /// ahead for a single status does not trigger range sync.
+    fn apply_advanced_status_grace(
+        &mut self,
+        peer_id: PeerId,
+        sync_type: PeerSyncType,
+    ) -> PeerSyncType {
+        if !matches!(sync_type, PeerSyncType::Advanced)
+            || !self.network_globals().peers.read().is_synced(&peer_id)
+        {
+            self.advanced_statuses.remove(&peer_id);
+            return sync_type;
+        }
+
+        let grace = self.network_globals().config.advanced_status_grace;
+        let advanced_statuses = self.advanced_statuses.entry(peer_id).or_default();
+        *advanced_statuses += 1;
+        if *advanced_statuses < grace {
+            debug!(
+                self.log,
+                "Synced peer reported an advanced status";
+                "peer_id" => %peer_id,
+                "advanced_statuses" => *advanced_statuses,
+                "grace" => grace,
+            );
+            PeerSyncType::FullySynced
+        } else {
+            self.advanced_statuses.remove(&peer_id);
+            sync_type
+        }
+    }
+
+    /// Tracks the fork digest of the latest status of a peer. Peers on a different fork digest
+    /// than ours are rejected before reaching sync.
+    ///
+    /// A known peer that moved to our fork digest from a prior fork, e.g. at a scheduled fork
+    /// boundary, is kept as is. Any other change of fork digest removes the peer from range sync
+    /// so that its usefulness is re-evaluated from its latest status.
+    fn check_peer_fork_digest(&mut self, peer_id: PeerId, fork_digest: [u8; 4]) {
+        let Some(previous_fork_digest) = self.peer_fork_digests.insert(peer_id, fork_digest) else {
+            return;
+        };
+        if previous_fork_digest == fork_digest {
+            return;
+        }
+
+        let fork_context = self.network.fork_context();
+        let is_fork_transition = match (
+            fork_context.from_context_bytes(previous_fork_digest),
+            fork_context.from_context_bytes(fork_digest),
+        ) {
+            (Some(previous_fork), Some(fork)) => previous_fork < fork,
+            _ => false,
+        };
+        if !is_fork_transition {
+            debug!(
+                self.log,
+                "Peer changed fork digest unexpectedly";
+                "peer_id" => %peer_id,
+                "previous_fork_digest" => hex::encode(previous_fork_digest),
+                "fork_digest" => hex::encode(fork_digest),
+            );
+            metrics::inc_counter(&metrics::SYNC_PEER_FORK_DIGEST_MISMATCH);
+            self.range_sync.peer_disconnect(&mut self.network, &peer_id);
+        }
+    }
+
     /// Trigger range sync for a set of peers that claim to have imported a head unknown to us.
     fn add_peers_force_range_sync(
         &mut self,
@@ -456,6 +710,7 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         };
 
         let head_slot = head_slot.unwrap_or_else(|| {
+            metrics::inc_counter(&metrics::SYNC_FORCE_RANGE_SYNC_ASSUMED_HEAD_SLOT);
             debug!(self.log,
                 "On add peers force range sync assuming local head_slot";
                 "local_head_slot" => local.head_slot,
@@ -516,6 +771,9 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         }
 
         // Remove peer from all data structures
+        self.peer_fork_digests.remove(peer_id);
+        self.advanced_statuses.remove(peer_id);
+        self.unknown_root_lookups_per_peer.remove(peer_id);
         self.range_sync.peer_disconnect(&mut self.network, peer_id);
         let _ = self
             .backfill_sync
@@ -526,6 +784,31 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         self.update_sync_state();
     }
 
+    /// Logs the columns this node needs which no connected peer can serve, once the status of
+    /// the first peers has been received. These columns stall sync until peers are found, adding
+    /// boot nodes may help.
+    fn report_columns_without_custody_peers(&mut self) {
+        if self.reported_columns_without_custody_peers
+            || self.peer_fork_digests.is_empty()
+            || !self.chain.spec.is_peer_das_scheduled()
+        {
+            return;
+        }
+        self.reported_columns_without_custody_peers = true;
+
+        let columns = self.network.columns_without_custody_peers();
+        if columns.is_empty() {
+            debug!(self.log, "All sampling columns have custody peers");
+        } else {
+            warn!(
+                self.log,
+                "No connected peers custody some sampling columns";
+                "columns" => ?columns,
+                "info" => "sync may stall until peers are found, consider adding boot nodes",
+            );
+        }
+    }
+
     /// Prune stale requests that are waiting for peers
     fn prune_requests(&mut self) {
         // continue_custody_by_root_requests attempts to make progress on all requests. If some
@@ -534,6 +817,15 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         for (id, result) in self.network.continue_custody_by_root_requests() {
             self.on_custody_by_root_result(id, result);
         }
+        self.continue_deferred_custody_requests();
+        self.continue_deferred_processor_sends();
+
+        // Range sync batches waiting for custody peers must eventually fail if no peers are found.
+        self.range_sync
+            .send_batches_awaiting_custody_peers(&mut self.network);
+
+        // components_by_range requests that never complete would otherwise be kept forever.
+        self.prune_leaked_range_requests(LEAKED_RANGE_REQUEST_MAX_AGE);
     }
 
     /// Updates the syncing state of a peer.
@@ -559,6 +851,11 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         if let Some(was_updated) = update_sync_status {
             let is_connected = self.network_globals().peers.read().is_connected(peer_id);
             if was_updated {
+                self.network
+                    .record_decision(SyncDecision::PeerSyncStatusChanged {
+                        peer_id: *peer_id,
+                        sync_status: rpr,
+                    });
                 debug!(
                     self.log,
                     "Peer transitioned sync state";
@@ -596,7 +893,12 @@ impl<T: BeaconChainTypes> SyncManager<T> {
     /// - If there is no range sync and no required backfill and we have synced up to the currently
     ///   known peers, we consider ourselves synced.
     fn update_sync_state(&mut self) {
-        let new_state: SyncState = match self.range_sync.state() {
+        let range_sync_state = self.range_sync.state();
+        if let Ok(state) = range_sync_state {
+            update_range_sync_slot_metrics(state);
+        }
+
+        let new_state: SyncState = match range_sync_state {
             Err(e) => {
                 crit!(self.log, "Error getting range sync state"; "error" => %e);
                 return;
@@ -682,6 +984,7 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         let new_state = self.network_globals().sync_state.read().clone();
         if !new_state.eq(&old_state) {
             info!(self.log, "Sync state updated"; "old_state" => %old_state, "new_state" => %new_state);
+            metrics::register_sync_state_transition(&old_state, &new_state);
             // If we have become synced - Subscribe to all the core subnet topics
             // We don't need to subscribe if the old state is a state that would have already
             // invoked this call.
@@ -691,7 +994,68 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                     SyncState::Synced { .. } | SyncState::BackFillSyncing { .. }
                 )
             {
+                self.core_topics_subscription_pending = true;
+            } else if !new_state.is_synced() {
+                self.core_topics_subscription_pending = false;
+            }
+        }
+        self.maybe_subscribe_core_topics();
+
+        self.update_sync_stall_reason();
+    }
+
+    /// Subscribes to the core topics if we became synced and enough synced peers are connected,
+    /// to avoid attesting based on the view of too few peers.
+    fn maybe_subscribe_core_topics(&mut self) {
+        if self.core_topics_subscription_pending {
+            let min_synced_peers = self
+                .network_globals()
+                .config
+                .min_synced_peers_for_core_topics;
+            let synced_peers = self.network_globals().peers.read().synced_peers().count();
+            if synced_peers >= min_synced_peers {
+                self.core_topics_subscription_pending = false;
                 self.network.subscribe_core_topics();
+            } else {
+                debug!(self.log, "Waiting for synced peers to subscribe to core topics";
+                    "synced_peers" => synced_peers,
+                    "min_synced_peers" => min_synced_peers,
+                );
+            }
+        }
+        metrics::set_gauge(
+            &metrics::SYNC_CORE_TOPICS_SUBSCRIPTION_PENDING,
+            self.core_topics_subscription_pending as i64,
+        );
+    }
+
+    /// Determines why sync is not making progress, if at all, from the current sync state, peers
+    /// and execution engine state, and publishes it to the network globals.
+    fn update_sync_stall_reason(&mut self) {
+        let sync_state = self.network_globals().sync_state();
+        let new_reason = if !sync_state.is_synced() && !self.network.is_execution_engine_online() {
+            Some(SyncStallReason::ExecutionEngineOffline)
+        } else if sync_state.is_stalled() {
+            Some(SyncStallReason::NoPeers)
+        } else if matches!(sync_state, SyncState::Synced)
+            && matches!(
+                self.network_globals().backfill_state(),
+                BackFillState::Paused | BackFillState::Failed
+            )
+            && cfg!(not(feature = "disable-backfill"))
+        {
+            Some(SyncStallReason::AwaitingBackfill)
+        } else {
+            None
+        };
+
+        let old_reason = self.network_globals().set_sync_stall_reason(new_reason);
+        if old_reason != new_reason {
+            match new_reason {
+                Some(reason) => {
+                    info!(self.log, "Sync is not progressing"; "reason" => %reason, "sync_state" => %sync_state)
+                }
+                None => debug!(self.log, "Sync is progressing again"; "sync_state" => %sync_state),
             }
         }
     }
@@ -733,18 +1097,40 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                 }
                 _ = prune_requests.tick() => {
                     self.prune_requests();
+                    self.report_columns_without_custody_peers();
                 }
                 _ = register_metrics_interval.tick() => {
                     self.network.register_metrics();
+                    self.backfill_sync.register_metrics();
+                    self.register_range_sync_eta_metric();
                 }
             }
         }
     }
 
+    /// Observes the local head while range syncing and exports the estimated time to reach the
+    /// target slot of the chain being synced.
+    fn register_range_sync_eta_metric(&mut self) {
+        let estimate = match self.range_sync.state() {
+            Ok(Some((_, _, target_slot))) => {
+                let head_slot = self.chain.canonical_head.cached_head().head_slot();
+                self.range_sync_progress.observe(head_slot, Instant::now());
+                self.range_sync_progress
+                    .estimated_seconds_to_slot(target_slot)
+                    .map_or(-1, |seconds| seconds as i64)
+            }
+            Ok(None) | Err(_) => {
+                self.range_sync_progress.clear();
+                0
+            }
+        };
+        metrics::set_gauge(&metrics::SYNC_RANGE_ESTIMATED_SECONDS_TO_SYNC, estimate);
+    }
+
     pub(crate) fn handle_message(&mut self, sync_message: SyncMessage<T::EthSpec>) {
         match sync_message {
-            SyncMessage::AddPeer(peer_id, info) => {
-                self.add_peer(peer_id, info);
+            SyncMessage::AddPeer(peer_id, info, fork_digest) => {
+                self.add_peer(peer_id, info, fork_digest);
             }
             SyncMessage::AddPeersForceRangeSync {
                 peers,
@@ -827,7 +1213,9 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                 );
             }
             SyncMessage::UnknownBlockHashFromAttestation(peer_id, block_root) => {
-                if !self.notified_unknown_roots.contains(&(peer_id, block_root)) {
+                if !self.notified_unknown_roots.contains(&(peer_id, block_root))
+                    && self.consume_unknown_root_budget(peer_id)
+                {
                     self.notified_unknown_roots.insert((peer_id, block_root));
                     debug!(self.log, "Received unknown block hash message"; "block_root" => ?block_root, "peer" => ?peer_id);
                     self.handle_unknown_block_root(peer_id, block_root);
@@ -842,6 +1230,42 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                     self.on_sampling_result(requester, result)
                 }
             }
+            SyncMessage::RequestPeersForColumn(column_index) => {
+                self.network.request_peers_for_column(column_index);
+            }
+            SyncMessage::GetColumnsWithoutCustodyPeers(sender) => {
+                // The requester may have given up waiting, ignore the error.
+                let _ = sender.send(self.network.columns_without_custody_peers());
+            }
+            SyncMessage::DumpDecisionLog { reset, sender } => {
+                // The requester may have given up waiting, ignore the error.
+                let _ = sender.send(self.network.dump_decision_log(reset));
+            }
+            SyncMessage::IsLookupActive(block_root, sender) => {
+                // The requester may have given up waiting, ignore the error.
+                let _ = sender.send(self.block_lookups.lookup_status(block_root));
+            }
+            SyncMessage::GetEngineStateView(sender) => {
+                // The requester may have given up waiting, ignore the error.
+                let _ = sender.send(self.network.execution_engine_state());
+            }
+            SyncMessage::DenylistParentRoots { roots, sender } => {
+                let denylist = self.block_lookups.extend_parent_lookup_denylist(roots);
+                // The requester may have given up waiting, ignore the error.
+                let _ = sender.send(denylist);
+            }
+            SyncMessage::SetSamplingConfig(sampling_config) => {
+                self.sampling.set_sampling_config(sampling_config);
+            }
+            SyncMessage::SetLookupPeerAgreement(min_peer_agreement) => {
+                info!(
+                    self.log,
+                    "Updated lookup peer agreement";
+                    "min_peer_agreement" => min_peer_agreement.get(),
+                );
+                self.block_lookups
+                    .set_min_peer_agreement(min_peer_agreement);
+            }
             SyncMessage::Disconnect(peer_id) => {
                 debug!(self.log, "Received disconnected message"; "peer_id" => %peer_id);
                 self.peer_disconnect(&peer_id);
@@ -854,9 +1278,14 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             SyncMessage::BlockComponentProcessed {
                 process_type,
                 result,
-            } => self
-                .block_lookups
-                .on_processing_result(process_type, result, &mut self.network),
+                stage_timings,
+            } => {
+                if let Some(stage_timings) = stage_timings {
+                    stage_timings.observe();
+                }
+                self.block_lookups
+                    .on_processing_result(process_type, result, &mut self.network)
+            }
             SyncMessage::GossipBlockProcessResult {
                 block_root,
                 imported,
@@ -925,6 +1354,35 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         }
     }
 
+    /// Counts an unknown root lookup triggered by `peer_id` in the current slot. Returns `false` if
+    /// the peer has exceeded `MAX_UNKNOWN_ROOT_LOOKUPS_PER_PEER_PER_SLOT`, in which case the root
+    /// must be ignored. The peer is penalized once per slot in which it exceeds the budget.
+    fn consume_unknown_root_budget(&mut self, peer_id: PeerId) -> bool {
+        let current_slot = self.chain.slot().unwrap_or_else(|_| Slot::new(0));
+        let (slot, count) = self
+            .unknown_root_lookups_per_peer
+            .entry(peer_id)
+            .or_insert((current_slot, 0));
+        if *slot != current_slot {
+            *slot = current_slot;
+            *count = 0;
+        }
+        *count += 1;
+
+        if *count <= MAX_UNKNOWN_ROOT_LOOKUPS_PER_PEER_PER_SLOT {
+            return true;
+        }
+        if *count == MAX_UNKNOWN_ROOT_LOOKUPS_PER_PEER_PER_SLOT + 1 {
+            debug!(self.log, "Peer exceeded unknown root lookup budget"; "peer" => %peer_id, "slot" => current_slot);
+            self.network.report_peer(
+                peer_id,
+                PeerAction::HighToleranceError,
+                "too_many_unknown_roots",
+            );
+        }
+        false
+    }
+
     fn handle_unknown_block_root(&mut self, peer_id: PeerId, block_root: Hash256) {
         match self.should_search_for_block(None, &peer_id) {
             Ok(_) => {
@@ -971,6 +1429,7 @@ impl<T: BeaconChainTypes> SyncManager<T> {
 
     fn handle_new_execution_engine_state(&mut self, engine_state: EngineState) {
         self.network.update_execution_engine_state(engine_state);
+        self.update_sync_stall_reason();
 
         match engine_state {
             EngineState::Online => {
@@ -978,7 +1437,8 @@ impl<T: BeaconChainTypes> SyncManager<T> {
 
                 // - Block lookups:
                 //   We start searching for blocks again. This is done by updating the stored ee online
-                //   state. No further action required.
+                //   state. Lookups retained while offline are sent for processing.
+                self.block_lookups.continue_all_lookups(&mut self.network);
 
                 // - Parent lookups:
                 //   We start searching for parents again. This is done by updating the stored ee
@@ -997,10 +1457,18 @@ impl<T: BeaconChainTypes> SyncManager<T> {
 
                 // - Block lookups:
                 //   Disabled while in this state. We drop current requests and don't search for new
-                //   blocks.
-                let dropped_single_blocks_requests =
+                //   blocks. Lookups with pending custody columns are retained to not download them
+                //   again.
+                let (dropped_single_blocks_requests, dropped_parent_requests) =
                     self.block_lookups.drop_single_block_requests();
 
+                // - Custody:
+                //   Custody requests are issued on behalf of block lookups, drop those of the
+                //   dropped lookups too.
+                let dropped_custody_requests = self
+                    .network
+                    .drop_custody_by_root_requests(&self.block_lookups.active_lookup_ids());
+
                 // - Range:
                 //   We still send found peers to range so that it can keep track of potential chains
                 //   with respect to our current peers. Range will stop processing batches in the
@@ -1008,10 +1476,26 @@ impl<T: BeaconChainTypes> SyncManager<T> {
 
                 // - Backfill: Not affected by ee states, nothing to do.
 
-                // Some logs.
-                if dropped_single_blocks_requests > 0 {
+                // Some logs and metrics.
+                for (component, dropped) in [
+                    ("single_block", dropped_single_blocks_requests),
+                    ("parent", dropped_parent_requests),
+                    ("custody", dropped_custody_requests),
+                ] {
+                    metrics::inc_counter_vec_by(
+                        &metrics::SYNC_REQUESTS_DROPPED_EXECUTION_ENGINE_OFFLINE,
+                        &[component],
+                        dropped as u64,
+                    );
+                }
+                if dropped_single_blocks_requests > 0
+                    || dropped_parent_requests > 0
+                    || dropped_custody_requests > 0
+                {
                     debug!(self.log, "Execution engine not online. Dropping active requests.";
                         "dropped_single_blocks_requests" => dropped_single_blocks_requests,
+                        "dropped_parent_requests" => dropped_parent_requests,
+                        "dropped_custody_requests" => dropped_custody_requests,
                     );
                 }
             }
@@ -1220,6 +1704,23 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                 response.map(|(columns, peer_group)| (columns, peer_group, seen_timestamp)),
                 &mut self.network,
             );
+        self.continue_deferred_custody_requests();
+    }
+
+    /// Continues the lookups whose custody requests were deferred because too many were active,
+    /// now that some may have finished.
+    fn continue_deferred_custody_requests(&mut self) {
+        if self.network.take_deferred_custody_requests() {
+            self.block_lookups.continue_all_lookups(&mut self.network);
+        }
+    }
+
+    /// Continues the lookups that failed to send a downloaded component to the beacon processor,
+    /// to send it again.
+    fn continue_deferred_processor_sends(&mut self) {
+        if self.network.take_deferred_processor_sends() {
+            self.block_lookups.continue_all_lookups(&mut self.network);
+        }
     }
 
     fn on_sampling_result(&mut self, requester: SamplingRequester, result: SamplingResult) {
@@ -1292,28 +1793,59 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                         }
                     }
                 }
-                Err(_) => match range_request_id.requester {
-                    RangeRequestId::RangeSync { chain_id, batch_id } => {
-                        self.range_sync.inject_error(
-                            &mut self.network,
-                            peer_id,
-                            batch_id,
-                            chain_id,
-                            range_request_id.id,
-                        );
-                        self.update_sync_state();
-                    }
-                    RangeRequestId::BackfillSync { batch_id } => match self
-                        .backfill_sync
-                        .inject_error(&mut self.network, batch_id, &peer_id, range_request_id.id)
-                    {
-                        Ok(_) => {}
-                        Err(_) => self.update_sync_state(),
-                    },
-                },
+                Err(_) => self.on_range_components_error(range_request_id, peer_id),
+            }
+        }
+    }
+
+    /// Notifies range or backfill sync that a components_by_range request has failed.
+    fn on_range_components_error(
+        &mut self,
+        range_request_id: ComponentsByRangeRequestId,
+        peer_id: PeerId,
+    ) {
+        match range_request_id.requester {
+            RangeRequestId::RangeSync { chain_id, batch_id } => {
+                self.range_sync.inject_error(
+                    &mut self.network,
+                    peer_id,
+                    batch_id,
+                    chain_id,
+                    range_request_id.id,
+                );
+                self.update_sync_state();
             }
+            RangeRequestId::BackfillSync { batch_id } => match self.backfill_sync.inject_error(
+                &mut self.network,
+                batch_id,
+                &peer_id,
+                range_request_id.id,
+            ) {
+                Ok(_) => {}
+                Err(_) => self.update_sync_state(),
+            },
         }
     }
+
+    /// Removes the components_by_range requests older than `max_age` and fails their batches.
+    fn prune_leaked_range_requests(&mut self, max_age: Duration) {
+        for (range_request_id, peer_id) in self
+            .network
+            .prune_leaked_components_by_range_requests(max_age)
+        {
+            self.on_range_components_error(range_request_id, peer_id);
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn retry_deferred_processor_sends(&mut self) {
+        self.continue_deferred_processor_sends();
+    }
+
+    #[cfg(test)]
+    pub(crate) fn prune_leaked_range_requests_older_than(&mut self, max_age: Duration) {
+        self.prune_leaked_range_requests(max_age);
+    }
 }
 
 impl From<Result<AvailabilityProcessingStatus, BlockError>> for BlockProcessingResult {
@@ -4,6 +4,7 @@
 mod backfill_sync;
 mod block_lookups;
 mod block_sidecar_coupling;
+mod decision_log;
 pub mod manager;
 mod network_context;
 mod peer_sampling;
@@ -12,6 +13,8 @@ mod range_sync;
 #[cfg(test)]
 mod tests;
 
+pub use block_lookups::LookupStatus;
+pub use decision_log::{SyncDecision, SyncDecisionEntry};
 pub use lighthouse_network::service::api_types::SamplingId;
 pub use manager::{BatchProcessResult, SyncMessage};
 pub use range_sync::{BatchOperationOutcome, ChainId};
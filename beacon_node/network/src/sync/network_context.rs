@@ -4,6 +4,7 @@
 use self::custody::{ActiveCustodyRequest, Error as CustodyRequestError};
 pub use self::requests::{BlocksByRootSingleRequest, DataColumnsByRootSingleBlockRequest};
 use super::block_sidecar_coupling::RangeBlockComponentsRequest;
+use super::decision_log::{SyncDecision, SyncDecisionEntry, SyncDecisionLog};
 use super::manager::BlockProcessType;
 use super::range_sync::ByRangeRequestType;
 use super::SyncMessage;
@@ -42,8 +43,8 @@ use std::time::Duration;
 use tokio::sync::mpsc;
 use types::blob_sidecar::FixedBlobSidecarList;
 use types::{
-    BlobSidecar, ColumnIndex, DataColumnSidecar, DataColumnSidecarList, EthSpec, ForkContext,
-    Hash256, SignedBeaconBlock, Slot,
+    BlobSidecar, ColumnIndex, DataColumnSidecar, DataColumnSidecarList, DataColumnSubnetId,
+    EthSpec, ForkContext, Hash256, SignedBeaconBlock, Slot,
 };
 
 pub mod custody;
@@ -183,10 +184,17 @@ pub struct SyncNetworkContext<T: BeaconChainTypes> {
 
     /// Mapping of active custody column requests for a block root
     custody_by_root_requests: FnvHashMap<CustodyRequester, ActiveCustodyRequest<T>>,
-
-    /// BlocksByRange requests paired with other ByRange requests for data components
+    /// Whether a custody request has been deferred because `custody_by_root_requests` was at
+    /// capacity since the last call to `take_deferred_custody_requests`.
+    deferred_custody_requests: bool,
+    /// Whether a lookup failed to send a downloaded component to the beacon processor and retained
+    /// it to send again, since the last call to `take_deferred_processor_sends`.
+    deferred_processor_sends: bool,
+
+    /// BlocksByRange requests paired with other ByRange requests for data components, with the
+    /// peer serving the blocks
     components_by_range_requests:
-        FnvHashMap<ComponentsByRangeRequestId, RangeBlockComponentsRequest<T::EthSpec>>,
+        FnvHashMap<ComponentsByRangeRequestId, (PeerId, RangeBlockComponentsRequest<T::EthSpec>)>,
 
     /// Whether the ee is online. If it's not, we don't allow access to the
     /// `beacon_processor_send`.
@@ -199,6 +207,12 @@ pub struct SyncNetworkContext<T: BeaconChainTypes> {
 
     fork_context: Arc<ForkContext>,
 
+    /// Recent significant decisions taken by sync, to debug stalls after the fact.
+    decision_log: SyncDecisionLog,
+
+    /// Peers known to reliably serve a column, selected ahead of other custodial peers.
+    preferred_custody_peers: HashMap<ColumnIndex, PeerId>,
+
     /// Logger for the `SyncNetworkContext`.
     pub log: slog::Logger,
 }
@@ -218,25 +232,52 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         fork_context: Arc<ForkContext>,
         log: slog::Logger,
     ) -> Self {
+        let decision_log = SyncDecisionLog::new(
+            network_beacon_processor
+                .network_globals
+                .config
+                .sync_decision_log_capacity,
+        );
         SyncNetworkContext {
             network_send,
             execution_engine_state: EngineState::Online, // always assume `Online` at the start
             request_id: 1,
-            blocks_by_root_requests: ActiveRequests::new("blocks_by_root"),
-            blobs_by_root_requests: ActiveRequests::new("blobs_by_root"),
-            data_columns_by_root_requests: ActiveRequests::new("data_columns_by_root"),
-            blocks_by_range_requests: ActiveRequests::new("blocks_by_range"),
-            blobs_by_range_requests: ActiveRequests::new("blobs_by_range"),
-            data_columns_by_range_requests: ActiveRequests::new("data_columns_by_range"),
+            blocks_by_root_requests: ActiveRequests::new("blocks_by_root", log.clone()),
+            blobs_by_root_requests: ActiveRequests::new("blobs_by_root", log.clone()),
+            data_columns_by_root_requests: ActiveRequests::new("data_columns_by_root", log.clone()),
+            blocks_by_range_requests: ActiveRequests::new("blocks_by_range", log.clone()),
+            blobs_by_range_requests: ActiveRequests::new("blobs_by_range", log.clone()),
+            data_columns_by_range_requests: ActiveRequests::new(
+                "data_columns_by_range",
+                log.clone(),
+            ),
             custody_by_root_requests: <_>::default(),
+            deferred_custody_requests: false,
+            deferred_processor_sends: false,
             components_by_range_requests: FnvHashMap::default(),
             network_beacon_processor,
             chain,
             fork_context,
+            decision_log,
+            preferred_custody_peers: HashMap::new(),
             log,
         }
     }
 
+    /// Records a significant sync decision in the bounded decision log.
+    pub fn record_decision(&mut self, decision: SyncDecision) {
+        self.decision_log.record(decision);
+    }
+
+    /// Returns the recent sync decisions, oldest first, clearing the log if `reset` is `true`.
+    pub fn dump_decision_log(&mut self, reset: bool) -> Vec<SyncDecisionEntry> {
+        self.decision_log.dump(reset)
+    }
+
+    pub fn fork_context(&self) -> &ForkContext {
+        &self.fork_context
+    }
+
     pub fn send_sync_message(&mut self, sync_message: SyncMessage<T::EthSpec>) {
         self.network_beacon_processor
             .send_sync_message(sync_message);
@@ -258,15 +299,21 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             data_columns_by_range_requests,
             // custody_by_root_requests is a meta request of data_columns_by_root_requests
             custody_by_root_requests: _,
+            deferred_custody_requests: _,
+            deferred_processor_sends: _,
             // components_by_range_requests is a meta request of various _by_range requests
             components_by_range_requests: _,
             execution_engine_state: _,
             network_beacon_processor: _,
             chain: _,
             fork_context: _,
+            decision_log: _,
+            preferred_custody_peers,
             log: _,
         } = self;
 
+        preferred_custody_peers.retain(|_, preferred_peer| preferred_peer != peer_id);
+
         let blocks_by_root_ids = blocks_by_root_requests
             .active_requests_of_peer(peer_id)
             .into_iter()
@@ -306,10 +353,57 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             .custody_peers_for_column(column_index)
     }
 
+    /// Prefer `peer_id` when selecting a peer to request `column_index` from. The preference is
+    /// cleared when the peer disconnects.
+    pub fn set_preferred_custody_peer(&mut self, column_index: ColumnIndex, peer_id: PeerId) {
+        self.preferred_custody_peers.insert(column_index, peer_id);
+    }
+
+    pub fn preferred_custody_peer(&self, column_index: ColumnIndex) -> Option<PeerId> {
+        self.preferred_custody_peers.get(&column_index).copied()
+    }
+
+    /// Returns the preferred peer for `column_index` if it still custodies the column, otherwise a
+    /// random custodial peer.
     pub fn get_random_custodial_peer(&self, column_index: ColumnIndex) -> Option<PeerId> {
-        self.get_custodial_peers(column_index)
+        let custodial_peers = self.get_custodial_peers(column_index);
+        if let Some(preferred_peer) = self.preferred_custody_peers.get(&column_index) {
+            if custodial_peers.contains(preferred_peer) {
+                return Some(*preferred_peer);
+            }
+        }
+        custodial_peers.into_iter().choose(&mut thread_rng())
+    }
+
+    /// Returns the count of connected peers that can serve each of the columns this node needs,
+    /// ordered by column index. A column with no serving peers will stall sync.
+    pub fn custody_peer_coverage(&self) -> Vec<(ColumnIndex, usize)> {
+        let mut column_indexes = self
+            .network_globals()
+            .sampling_columns
+            .iter()
+            .copied()
+            .collect::<Vec<_>>();
+        column_indexes.sort_unstable();
+        column_indexes
             .into_iter()
-            .choose(&mut thread_rng())
+            .map(|column_index| (column_index, self.get_custodial_peers(column_index).len()))
+            .collect()
+    }
+
+    /// Returns the columns this node needs which no connected peer can serve, ordered by column
+    /// index.
+    pub fn columns_without_custody_peers(&self) -> Vec<ColumnIndex> {
+        self.custody_peer_coverage()
+            .into_iter()
+            .filter(|(_, peer_count)| *peer_count == 0)
+            .map(|(column_index, _)| column_index)
+            .collect()
+    }
+
+    /// Returns `true` if this node custodies every column, i.e. it is a supernode.
+    pub fn is_supernode(&self) -> bool {
+        self.network_globals().sampling_columns.len() as u64 >= self.chain.spec.number_of_columns
     }
 
     pub fn network_globals(&self) -> &NetworkGlobals<T::EthSpec> {
@@ -409,7 +503,8 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             expects_columns,
             data_column_requests.map(|items| items.len()),
         );
-        self.components_by_range_requests.insert(id, info);
+        self.components_by_range_requests
+            .insert(id, (peer_id, info));
 
         Ok(id.id)
     }
@@ -426,11 +521,12 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             // avoid retrying from failed peers, however `BatchState` currently only tracks the peer
             // serving the blocks.
             let Some(custody_peer) = self.get_random_custodial_peer(*column_index) else {
-                // TODO(das): this will be pretty bad UX. To improve we should:
-                // - Attempt to fetch custody requests first, before requesting blocks
-                // - Handle the no peers case gracefully, maybe add some timeout and give a few
-                //   minutes / seconds to the peer manager to locate peers on this subnet before
-                //   abandoing progress on the chain completely.
+                // Range sync holds the batch for `custody_peers_grace_period` to give the peer
+                // manager some time to locate peers on this subnet before abandoning progress.
+                // Request discovery on this subnet in the meantime.
+                // TODO(das): Attempt to fetch custody requests first, before requesting blocks
+                self.network_beacon_processor
+                    .send_sync_message(SyncMessage::RequestPeersForColumn(*column_index));
                 return Err(RpcRequestSendError::NoCustodyPeers);
             };
 
@@ -461,7 +557,7 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         };
 
         if let Err(e) = {
-            let request = entry.get_mut();
+            let (_, request) = entry.get_mut();
             match range_block_component {
                 RangeBlockComponent::Block(resp) => resp.map(|(blocks, _)| {
                     request.add_blocks(blocks);
@@ -478,18 +574,55 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             return Some(Err(e));
         }
 
-        if entry.get_mut().is_finished() {
+        if entry.get().1.is_finished() {
             // If the request is finished, dequeue everything
-            let request = entry.remove();
-            let blocks = request
-                .into_responses(&self.chain.spec)
-                .map_err(RpcResponseError::BlockComponentCouplingError);
+            let (_, request) = entry.remove();
+            let blocks = request.into_responses(&self.chain.spec).map_err(|e| {
+                metrics::inc_counter(&metrics::SYNC_BLOCK_COMPONENT_COUPLING_ERRORS);
+                debug!(
+                    self.log,
+                    "Failed to couple block components";
+                    "id" => ?id,
+                    "error" => %e,
+                );
+                RpcResponseError::BlockComponentCouplingError(e)
+            });
             Some(blocks)
         } else {
             None
         }
     }
 
+    /// Removes the components_by_range requests active for longer than `max_age`. Their individual
+    /// requests are subject to RPC timeouts, so such requests never complete and would otherwise
+    /// be kept forever. Returns the removed requests with the peer serving their blocks, so the
+    /// requester can be notified of the failure.
+    pub fn prune_leaked_components_by_range_requests(
+        &mut self,
+        max_age: Duration,
+    ) -> Vec<(ComponentsByRangeRequestId, PeerId)> {
+        let mut leaked = vec![];
+        self.components_by_range_requests
+            .retain(|id, (peer_id, request)| {
+                if request.age() < max_age {
+                    return true;
+                }
+                warn!(self.log, "Removing leaked components_by_range request";
+                    "id" => ?id,
+                    "peer_id" => %peer_id,
+                    "age_secs" => request.age().as_secs(),
+                    "outstanding_components" => ?request.outstanding_components(),
+                );
+                leaked.push((*id, *peer_id));
+                false
+            });
+        metrics::inc_counter_by(
+            &metrics::SYNC_LEAKED_COMPONENTS_BY_RANGE_REQUESTS,
+            leaked.len() as u64,
+        );
+        leaked
+    }
+
     /// Request block of `block_root` if necessary by checking:
     /// - If the da_checker has a pending block from gossip or a previous request
     ///
@@ -613,8 +746,13 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             .filter(|index| !imported_blob_indexes.contains(index))
             .collect::<Vec<_>>();
 
+        let some_blobs_cached = indices.len() < expected_blobs;
+
         if indices.is_empty() {
             // No blobs required, do not issue any request
+            if some_blobs_cached {
+                metrics::inc_counter(&metrics::SYNC_LOOKUP_BLOBS_CACHE_HITS);
+            }
             return Ok(LookupRequestResult::NoRequestNeeded("no indices to fetch"));
         }
 
@@ -650,11 +788,16 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             peer_id,
             // true = enforce max_requests are returned for blobs_by_root. We only issue requests for
             // blocks after we know the block has data, and only request peers after they claim to
-            // have imported the block+blobs.
+            // have imported the block+blobs. A short response is penalized with `BlobCountMismatch`
+            // in `on_single_blob_response`.
             true,
             BlobsByRootRequestItems::new(request),
         );
 
+        if some_blobs_cached {
+            metrics::inc_counter(&metrics::SYNC_LOOKUP_BLOBS_CACHE_HITS);
+        }
+
         Ok(LookupRequestResult::RequestSent(req_id))
     }
 
@@ -714,19 +857,32 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             .unwrap_or_default();
 
         // Include only the blob indexes not yet imported (received through gossip)
-        let custody_indexes_to_fetch = self
-            .network_globals()
-            .sampling_columns
-            .clone()
-            .into_iter()
+        let sampling_columns = &self.network_globals().sampling_columns;
+        let custody_indexes_to_fetch = sampling_columns
+            .iter()
+            .copied()
             .filter(|index| !custody_indexes_imported.contains(index))
             .collect::<Vec<_>>();
 
+        let some_columns_cached = custody_indexes_to_fetch.len() < sampling_columns.len();
+
         if custody_indexes_to_fetch.is_empty() {
             // No indexes required, do not issue any request
+            metrics::inc_counter(&metrics::SYNC_LOOKUP_COLUMNS_CACHE_HITS);
             return Ok(LookupRequestResult::NoRequestNeeded("no indices to fetch"));
         }
 
+        let max_custody_requests = self
+            .network_globals()
+            .config
+            .max_concurrent_custody_requests;
+        if self.custody_by_root_requests.len() >= max_custody_requests {
+            // Lookup sync event safety: the lookup is continued once an active custody request
+            // finishes, see `take_deferred_custody_requests`.
+            self.deferred_custody_requests = true;
+            return Ok(LookupRequestResult::Pending("too many custody requests"));
+        }
+
         let req_id = self.next_id();
         let id = SingleLookupReqId { lookup_id, req_id };
 
@@ -754,12 +910,33 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
                 // created cannot return data immediately, it must send some request to the network
                 // first. And there must exist some request, `custody_indexes_to_fetch` is not empty.
                 self.custody_by_root_requests.insert(requester, request);
+                if some_columns_cached {
+                    metrics::inc_counter(&metrics::SYNC_LOOKUP_COLUMNS_CACHE_HITS);
+                }
                 Ok(LookupRequestResult::RequestSent(req_id))
             }
             Err(e) => Err(RpcRequestSendError::CustodyRequestError(e)),
         }
     }
 
+    /// Returns `true` if a custody request has been deferred since the last call, clearing the
+    /// flag. Lookups should then be continued once capacity is freed for custody requests.
+    pub fn take_deferred_custody_requests(&mut self) -> bool {
+        std::mem::take(&mut self.deferred_custody_requests)
+    }
+
+    /// Records that a lookup retained a downloaded component it failed to send to the beacon
+    /// processor, to be sent again once `take_deferred_processor_sends` returns `true`.
+    pub fn defer_processor_send(&mut self) {
+        self.deferred_processor_sends = true;
+    }
+
+    /// Returns `true` if a lookup deferred sending a component to the beacon processor since the
+    /// last call, clearing the flag.
+    pub fn take_deferred_processor_sends(&mut self) -> bool {
+        std::mem::take(&mut self.deferred_processor_sends)
+    }
+
     fn send_blocks_by_range_request(
         &mut self,
         peer_id: PeerId,
@@ -828,14 +1005,13 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             })
             .map_err(|_| RpcRequestSendError::NetworkSendError)?;
 
-        let max_blobs_per_block = self.chain.spec.max_blobs_per_block(request_epoch);
         self.blobs_by_range_requests.insert(
             id,
             peer_id,
             // false = do not enforce max_requests are returned for *_by_range methods. We don't
             // know if there are missed blocks.
             false,
-            BlobsByRangeRequestItems::new(request, max_blobs_per_block),
+            BlobsByRangeRequestItems::new(request, self.chain.spec.clone()),
         );
         Ok(id)
     }
@@ -883,10 +1059,38 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         self.execution_engine_state == EngineState::Online
     }
 
+    /// Returns sync's view on the execution engine state, which may lag behind the actual state of
+    /// the execution engine.
+    pub fn execution_engine_state(&self) -> EngineState {
+        self.execution_engine_state
+    }
+
     pub fn update_execution_engine_state(&mut self, engine_state: EngineState) {
         debug!(self.log, "Sync's view on execution engine state updated";
             "past_state" => ?self.execution_engine_state, "new_state" => ?engine_state);
         self.execution_engine_state = engine_state;
+        self.record_decision(SyncDecision::ExecutionEngineState(engine_state));
+    }
+
+    /// Drops all active custody requests, except those issued by one of `retained_lookups`, and
+    /// returns how many requests were dropped. Responses to their inflight `data_columns_by_root`
+    /// requests will be ignored.
+    pub fn drop_custody_by_root_requests(&mut self, retained_lookups: &HashSet<Id>) -> usize {
+        let requests_before = self.custody_by_root_requests.len();
+        self.custody_by_root_requests
+            .retain(|requester, _| retained_lookups.contains(&requester.0.lookup_id));
+        requests_before - self.custody_by_root_requests.len()
+    }
+
+    /// Requests the network service to discover and dial peers on the subnet of `column_index`.
+    pub fn request_peers_for_column(&self, column_index: ColumnIndex) {
+        let subnet_id = DataColumnSubnetId::from_column_index(column_index, &self.chain.spec);
+        debug!(self.log, "Requesting peers for column"; "column_index" => column_index, "subnet_id" => %subnet_id);
+        self.network_send
+            .send(NetworkMessage::DiscoverDataColumnPeers(subnet_id))
+            .unwrap_or_else(|_| {
+                warn!(self.log, "Could not request peers: channel failed");
+            });
     }
 
     /// Terminates the connection with the peer and bans them.
@@ -1036,9 +1240,24 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         peer_id: PeerId,
         rpc_event: RpcEvent<Arc<BlobSidecar<T::EthSpec>>>,
     ) -> Option<RpcResponseResult<FixedBlobSidecarList<T::EthSpec>>> {
+        // Read the expected count before handling the event, which may resolve the request.
+        let expected_blobs = self
+            .blobs_by_root_requests
+            .active_items(&id)
+            .map(|items| items.expected_blobs());
         let response = self.blobs_by_root_requests.on_response(id, rpc_event);
         let response = response.map(|res| {
-            res.and_then(|(blobs, seen_timestamp)| {
+            res.map_err(|e| match (e, expected_blobs) {
+                // A peer returning fewer blobs than the block committed to is penalized distinctly.
+                (
+                    RpcResponseError::VerifyError(LookupVerifyError::NotEnoughResponsesReturned {
+                        actual,
+                    }),
+                    Some(expected),
+                ) => LookupVerifyError::BlobCountMismatch { expected, actual }.into(),
+                (e, _) => e,
+            })
+            .and_then(|(blobs, seen_timestamp)| {
                 if let Some(max_len) = blobs
                     .first()
                     .map(|blob| self.chain.spec.max_blobs_per_block(blob.epoch()) as usize)
@@ -1285,6 +1504,16 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         ] {
             metrics::set_gauge_vec(&metrics::SYNC_ACTIVE_NETWORK_REQUESTS, &[id], count as i64);
         }
+
+        metrics::set_gauge(&metrics::SYNC_IS_SUPERNODE, self.is_supernode() as i64);
+
+        for (column_index, peer_count) in self.custody_peer_coverage() {
+            metrics::set_gauge_vec(
+                &metrics::SYNC_CUSTODY_COLUMN_PEERS,
+                &[&column_index.to_string()],
+                peer_count as i64,
+            );
+        }
     }
 }
 
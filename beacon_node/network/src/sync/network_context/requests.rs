@@ -3,6 +3,7 @@ use std::{collections::hash_map::Entry, hash::Hash};
 use beacon_chain::validator_monitor::timestamp_now;
 use fnv::FnvHashMap;
 use lighthouse_network::PeerId;
+use slog::{debug, Logger};
 use strum::IntoStaticStr;
 use types::{Hash256, Slot};
 
@@ -35,6 +36,7 @@ pub enum LookupVerifyError {
     UnrequestedSlot(Slot),
     InvalidInclusionProof,
     DuplicatedData(Slot, u64),
+    BlobCountMismatch { expected: usize, actual: usize },
     InternalError(String),
 }
 
@@ -42,6 +44,7 @@ pub enum LookupVerifyError {
 pub struct ActiveRequests<K: Eq + Hash, T: ActiveRequestItems> {
     requests: FnvHashMap<K, ActiveRequest<T>>,
     name: &'static str,
+    log: Logger,
 }
 
 /// Stateful container for a single active ReqResp request
@@ -59,10 +62,11 @@ enum State<T> {
 }
 
 impl<K: Eq + Hash, T: ActiveRequestItems> ActiveRequests<K, T> {
-    pub fn new(name: &'static str) -> Self {
+    pub fn new(name: &'static str, log: Logger) -> Self {
         Self {
             requests: <_>::default(),
             name,
+            log,
         }
     }
 
@@ -125,7 +129,19 @@ impl<K: Eq + Hash, T: ActiveRequestItems> ActiveRequests<K, T> {
                     // `rpc/handler.rs`. Handling this case adds complexity for no gain. Even if an
                     // attacker could abuse this, there's no gain in sending garbage chunks that
                     // will be ignored anyway.
-                    State::CompletedEarly => None,
+                    State::CompletedEarly => {
+                        metrics::inc_counter_vec(
+                            &metrics::SYNC_RESPONSE_COUNT_VIOLATIONS,
+                            &[self.name, "too_many"],
+                        );
+                        debug!(
+                            self.log,
+                            "Peer sent more responses than requested";
+                            "type" => self.name,
+                            "peer_id" => %request.peer_id,
+                        );
+                        None
+                    }
                     // Ignore items after errors. We may want to penalize repeated invalid chunks
                     // for the same response. But that's an optimization to ban peers sending
                     // invalid data faster that we choose to not adopt for now.
@@ -140,6 +156,16 @@ impl<K: Eq + Hash, T: ActiveRequestItems> ActiveRequests<K, T> {
                     // Received a stream termination in a valid sequence, consume items
                     State::Active(mut items) => {
                         if request.expect_max_responses {
+                            metrics::inc_counter_vec(
+                                &metrics::SYNC_RESPONSE_COUNT_VIOLATIONS,
+                                &[self.name, "not_enough"],
+                            );
+                            debug!(
+                                self.log,
+                                "Peer sent fewer responses than requested";
+                                "type" => self.name,
+                                "peer_id" => %request.peer_id,
+                            );
                             Some(Err(LookupVerifyError::NotEnoughResponsesReturned {
                                 actual: items.consume().len(),
                             }
@@ -171,6 +197,14 @@ impl<K: Eq + Hash, T: ActiveRequestItems> ActiveRequests<K, T> {
         }
     }
 
+    /// Returns the accumulator of a request that has not resolved yet.
+    pub fn active_items(&self, id: &K) -> Option<&T> {
+        match &self.requests.get(id)?.state {
+            State::Active(items) => Some(items),
+            State::CompletedEarly | State::Errored => None,
+        }
+    }
+
     pub fn active_requests_of_peer(&self, peer_id: &PeerId) -> Vec<&K> {
         self.requests
             .iter()
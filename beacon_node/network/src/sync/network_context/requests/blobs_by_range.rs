@@ -1,22 +1,24 @@
 use super::{ActiveRequestItems, LookupVerifyError};
 use lighthouse_network::rpc::methods::BlobsByRangeRequest;
 use std::sync::Arc;
-use types::{BlobSidecar, EthSpec};
+use types::{BlobSidecar, ChainSpec, EthSpec};
 
 /// Accumulates results of a blobs_by_range request. Only returns items after receiving the
 /// stream termination.
 pub struct BlobsByRangeRequestItems<E: EthSpec> {
     request: BlobsByRangeRequest,
     items: Vec<Arc<BlobSidecar<E>>>,
-    max_blobs_per_block: u64,
+    /// Used to compute the maximum blobs per block at the epoch of each blob, as a request may
+    /// span a fork which changes the maximum.
+    spec: Arc<ChainSpec>,
 }
 
 impl<E: EthSpec> BlobsByRangeRequestItems<E> {
-    pub fn new(request: BlobsByRangeRequest, max_blobs_per_block: u64) -> Self {
+    pub fn new(request: BlobsByRangeRequest, spec: Arc<ChainSpec>) -> Self {
         Self {
             request,
             items: vec![],
-            max_blobs_per_block,
+            spec,
         }
     }
 }
@@ -30,7 +32,7 @@ impl<E: EthSpec> ActiveRequestItems for BlobsByRangeRequestItems<E> {
         {
             return Err(LookupVerifyError::UnrequestedSlot(blob.slot()));
         }
-        if blob.index >= self.max_blobs_per_block {
+        if blob.index >= self.spec.max_blobs_per_block(blob.epoch()) {
             return Err(LookupVerifyError::UnrequestedIndex(blob.index));
         }
         if !blob.verify_blob_sidecar_inclusion_proof() {
@@ -54,3 +56,52 @@ impl<E: EthSpec> ActiveRequestItems for BlobsByRangeRequestItems<E> {
         std::mem::take(&mut self.items)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::test_utils::{generate_rand_block_and_blobs, NumBlobs};
+    use rand::SeedableRng;
+    use types::{test_utils::XorShiftRng, Epoch, ForkName, MinimalEthSpec as E, Slot};
+
+    #[test]
+    fn max_blobs_per_block_follows_the_fork_of_each_blob() {
+        let mut spec = ForkName::Deneb.make_genesis_spec(E::default_spec());
+        spec.electra_fork_epoch = Some(Epoch::new(1));
+        let spec = Arc::new(spec);
+        let deneb_max_blobs = spec.max_blobs_per_block(Epoch::new(0));
+        assert!(spec.max_blobs_per_block(Epoch::new(1)) > deneb_max_blobs);
+
+        // A blob with an index only valid after the fork raising the maximum.
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let (_, blobs) = generate_rand_block_and_blobs::<E>(
+            ForkName::Electra,
+            NumBlobs::Number(deneb_max_blobs as usize + 1),
+            &mut rng,
+            &spec,
+        );
+        let blob = blobs.last().unwrap().clone();
+        assert_eq!(blob.index, deneb_max_blobs);
+        let blob_at_slot = |slot: Slot| {
+            let mut blob = blob.clone();
+            // The slot is not covered by the inclusion proof.
+            blob.signed_block_header.message.slot = slot;
+            Arc::new(blob)
+        };
+
+        let slots_per_epoch = E::slots_per_epoch();
+        let mut items = BlobsByRangeRequestItems::<E>::new(
+            BlobsByRangeRequest {
+                start_slot: 0,
+                count: 2 * slots_per_epoch,
+            },
+            spec,
+        );
+
+        assert!(items.add(blob_at_slot(Slot::new(slots_per_epoch))).is_ok());
+        assert!(matches!(
+            items.add(blob_at_slot(Slot::new(slots_per_epoch - 1))),
+            Err(LookupVerifyError::UnrequestedIndex(index)) if index == deneb_max_blobs
+        ));
+    }
+}
@@ -37,6 +37,11 @@ impl<E: EthSpec> BlobsByRootRequestItems<E> {
             items: vec![],
         }
     }
+
+    /// The number of blobs committed by the block that were requested, i.e. not already imported.
+    pub fn expected_blobs(&self) -> usize {
+        self.request.indices.len()
+    }
 }
 
 impl<E: EthSpec> ActiveRequestItems for BlobsByRootRequestItems<E> {
@@ -38,6 +38,13 @@ impl<T: BeaconChainTypes> Sampling<T> {
         }
     }
 
+    /// Replace the sampling config used for future requests. Active requests keep sampling with
+    /// the config they were created with.
+    pub fn set_sampling_config(&mut self, sampling_config: SamplingConfig) {
+        debug!(self.log, "Updated sampling config"; "config" => ?sampling_config);
+        self.sampling_config = sampling_config;
+    }
+
     #[cfg(test)]
     pub fn active_sampling_requests(&self) -> Vec<Hash256> {
         self.requests.values().map(|r| r.block_root).collect()
@@ -105,6 +105,8 @@ pub struct BatchInfo<E: EthSpec, B: BatchConfig = RangeSyncBatchConfig> {
     non_faulty_processing_attempts: u8,
     /// The number of download retries this batch has undergone due to a failed request.
     failed_download_attempts: Vec<PeerId>,
+    /// The number of failed downloads after which the batch is considered failed.
+    max_download_attempts: u8,
     /// State of the batch.
     state: BatchState<E>,
     /// Whether this batch contains all blocks or all blocks and blobs.
@@ -165,6 +167,7 @@ impl<E: EthSpec, B: BatchConfig> BatchInfo<E, B> {
             end_slot,
             failed_processing_attempts: Vec::new(),
             failed_download_attempts: Vec::new(),
+            max_download_attempts: B::max_batch_download_attempts(),
             non_faulty_processing_attempts: 0,
             state: BatchState::AwaitingDownload,
             batch_type,
@@ -172,6 +175,25 @@ impl<E: EthSpec, B: BatchConfig> BatchInfo<E, B> {
         }
     }
 
+    /// Overrides the maximum download attempts of `BatchConfig::max_batch_download_attempts`.
+    pub fn with_max_download_attempts(mut self, max_download_attempts: u8) -> Self {
+        self.max_download_attempts = max_download_attempts;
+        self
+    }
+
+    /// Returns the number of failed download and processing attempts of this batch from `peer_id`.
+    pub fn failed_attempts_from_peer(&self, peer_id: &PeerId) -> usize {
+        self.failed_download_attempts
+            .iter()
+            .filter(|peer| *peer == peer_id)
+            .count()
+            + self
+                .failed_processing_attempts
+                .iter()
+                .filter(|attempt| attempt.peer_id == *peer_id)
+                .count()
+    }
+
     /// Gives a list of peers from which this batch has had a failed download or processing
     /// attempt.
     pub fn failed_peers(&self) -> HashSet<PeerId> {
@@ -308,14 +330,13 @@ impl<E: EthSpec, B: BatchConfig> BatchInfo<E, B> {
                 if mark_failed {
                     self.failed_download_attempts.push(peer);
                 }
-                self.state = if self.failed_download_attempts.len()
-                    >= B::max_batch_download_attempts() as usize
-                {
-                    BatchState::Failed
-                } else {
-                    // drop the blocks
-                    BatchState::AwaitingDownload
-                };
+                self.state =
+                    if self.failed_download_attempts.len() >= self.max_download_attempts as usize {
+                        BatchState::Failed
+                    } else {
+                        // drop the blocks
+                        BatchState::AwaitingDownload
+                    };
                 Ok(self.outcome())
             }
             BatchState::Poisoned => unreachable!("Poisoned batch"),
@@ -3,7 +3,7 @@ use super::RangeSyncType;
 use crate::metrics;
 use crate::metrics::PEERS_PER_COLUMN_SUBNET;
 use crate::network_beacon_processor::ChainSegmentProcessId;
-use crate::sync::network_context::RangeRequestId;
+use crate::sync::network_context::{RangeRequestId, RpcRequestSendError};
 use crate::sync::{network_context::SyncNetworkContext, BatchOperationOutcome, BatchProcessResult};
 use beacon_chain::block_verification_types::RpcBlock;
 use beacon_chain::BeaconChainTypes;
@@ -15,6 +15,7 @@ use rand::seq::SliceRandom;
 use rand::Rng;
 use slog::{crit, debug, o, warn};
 use std::collections::{btree_map::Entry, BTreeMap, HashSet};
+use std::time::Instant;
 use strum::IntoStaticStr;
 use types::{Epoch, EthSpec, Hash256, Slot};
 
@@ -113,6 +114,10 @@ pub struct SyncingChain<T: BeaconChainTypes> {
     /// The current processing batch, if any.
     current_processing_batch: Option<BatchId>,
 
+    /// Batches that could not be requested because no peer serves some of the sampling columns,
+    /// and the time since they have been waiting for such peers.
+    awaiting_custody_peers: FnvHashMap<BatchId, Instant>,
+
     /// The chain's log.
     log: slog::Logger,
 }
@@ -153,6 +158,7 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             attempted_optimistic_starts: HashSet::default(),
             state: ChainSyncingState::Stopped,
             current_processing_batch: None,
+            awaiting_custody_peers: FnvHashMap::default(),
             log: log.new(o!("chain" => id)),
         }
     }
@@ -204,7 +210,7 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             for id in batch_ids {
                 if let Some(batch) = self.batches.get_mut(&id) {
                     if let BatchOperationOutcome::Failed { blacklist } =
-                        batch.download_failed(true)?
+                        batch_download_failed(batch)?
                     {
                         return Err(RemoveChain::ChainFailed {
                             blacklist,
@@ -378,6 +384,12 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
                         // attempting to process other batches.
                         return Ok(KeepChain);
                     }
+                    BatchState::AwaitingDownload
+                        if self.awaiting_custody_peers.contains_key(&epoch) =>
+                    {
+                        // The optimistic batch is waiting for custody peers to be requested.
+                        return Ok(KeepChain);
+                    }
                     BatchState::Poisoned => unreachable!("Poisoned batch"),
                     BatchState::Processing(_)
                     | BatchState::AwaitingDownload
@@ -415,6 +427,13 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
                 BatchState::Downloading(..) => {
                     // Batch is not ready, nothing to process
                 }
+                BatchState::AwaitingDownload
+                    if self
+                        .awaiting_custody_peers
+                        .contains_key(&self.processing_target) =>
+                {
+                    // Batch is waiting for custody peers to be requested, nothing to process
+                }
                 BatchState::Poisoned => unreachable!("Poisoned batch"),
                 BatchState::Failed | BatchState::AwaitingDownload | BatchState::Processing(_) => {
                     // these are all inconsistent states:
@@ -895,7 +914,7 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             if let Some(active_requests) = self.peers.get_mut(peer_id) {
                 active_requests.remove(&batch_id);
             }
-            if let BatchOperationOutcome::Failed { blacklist } = batch.download_failed(true)? {
+            if let BatchOperationOutcome::Failed { blacklist } = batch_download_failed(batch)? {
                 return Err(RemoveChain::ChainFailed {
                     blacklist,
                     failing_batch: batch_id,
@@ -927,20 +946,19 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
         };
 
         // Find a peer to request the batch
-        let failed_peers = batch.failed_peers();
-
         let new_peer = self
             .peers
             .iter()
             .map(|(peer, requests)| {
                 (
-                    failed_peers.contains(peer),
+                    batch.failed_attempts_from_peer(peer),
                     requests.len(),
                     rand::thread_rng().gen::<u32>(),
                     *peer,
                 )
             })
-            // Sort peers prioritizing unrelated peers with less active requests.
+            // Sort peers prioritizing peers with less failed attempts for this batch, so that
+            // retries rotate through all the peers of the chain, then with less active requests.
             .min()
             .map(|(_, _, _, peer)| peer);
 
@@ -972,6 +990,7 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
                 },
             ) {
                 Ok(request_id) => {
+                    self.awaiting_custody_peers.remove(&batch_id);
                     // inform the batch about the new request
                     batch.start_downloading_from_peer(peer, request_id)?;
                     if self
@@ -999,6 +1018,23 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
                         });
                 }
                 Err(e) => {
+                    if e == RpcRequestSendError::NoCustodyPeers {
+                        // Give the peer manager some time to find peers on the missing subnets.
+                        // The batch is requested again once new peers join the chain, or on the
+                        // next call to `send_batches_awaiting_custody_peers`.
+                        let waiting_since = *self
+                            .awaiting_custody_peers
+                            .entry(batch_id)
+                            .or_insert_with(Instant::now);
+                        let grace_period =
+                            network.network_globals().config.custody_peers_grace_period;
+                        if waiting_since.elapsed() < grace_period {
+                            debug!(self.log, "Waiting for custody peers to request batch";
+                                "batch_id" => batch_id, &batch);
+                            return Ok(KeepChain);
+                        }
+                    }
+
                     // NOTE: under normal conditions this shouldn't happen but we handle it anyway
                     warn!(self.log, "Could not send batch request";
                         "batch_id" => batch_id, "error" => ?e, &batch);
@@ -1007,7 +1043,7 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
                     self.peers
                         .get_mut(&peer)
                         .map(|request| request.remove(&batch_id));
-                    match batch.download_failed(true)? {
+                    match batch_download_failed(batch)? {
                         BatchOperationOutcome::Failed { blacklist } => {
                             return Err(RemoveChain::ChainFailed {
                                 blacklist,
@@ -1045,6 +1081,36 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
         self.process_completed_batches(network)
     }
 
+    /// Attempts to request the batches that are waiting for peers on some of the sampling column
+    /// subnets. Batches that have waited longer than the grace period are failed.
+    pub fn send_batches_awaiting_custody_peers(
+        &mut self,
+        network: &mut SyncNetworkContext<T>,
+    ) -> ProcessingResult {
+        if !matches!(self.state, ChainSyncingState::Syncing) {
+            return Ok(KeepChain);
+        }
+
+        // Forget batches that have been removed since, e.g. when the chain advanced.
+        let batches = &self.batches;
+        self.awaiting_custody_peers.retain(|batch_id, _| {
+            batches
+                .get(batch_id)
+                .is_some_and(|batch| matches!(batch.state(), BatchState::AwaitingDownload))
+        });
+
+        let batch_ids = self
+            .awaiting_custody_peers
+            .keys()
+            .copied()
+            .collect::<Vec<_>>();
+        for batch_id in batch_ids {
+            self.retry_batch_download(network, batch_id)?;
+        }
+
+        Ok(KeepChain)
+    }
+
     /// Attempts to request the next required batches from the peer pool if the chain is syncing. It will exhaust the peer
     /// pool and left over batches until the batch buffer is reached or all peers are exhausted.
     fn request_batches(&mut self, network: &mut SyncNetworkContext<T>) -> ProcessingResult {
@@ -1052,6 +1118,9 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             return Ok(KeepChain);
         }
 
+        // first re-attempt the batches waiting for custody peers, new peers may serve them
+        self.send_batches_awaiting_custody_peers(network)?;
+
         // find the next pending batch and request it from the peer
 
         // randomize the peers for load balancing
@@ -1083,7 +1152,8 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             if let Entry::Vacant(entry) = self.batches.entry(epoch) {
                 if let Some(peer) = idle_peers.pop() {
                     let batch_type = network.batch_type(epoch);
-                    let optimistic_batch = BatchInfo::new(&epoch, EPOCHS_PER_BATCH, batch_type);
+                    let optimistic_batch = BatchInfo::new(&epoch, EPOCHS_PER_BATCH, batch_type)
+                        .with_max_download_attempts(max_batch_download_attempts(network));
                     entry.insert(optimistic_batch);
                     self.send_batch(network, epoch, peer)?;
                 }
@@ -1190,7 +1260,10 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             }
             Entry::Vacant(entry) => {
                 let batch_type = network.batch_type(batch_id);
-                entry.insert(BatchInfo::new(&batch_id, EPOCHS_PER_BATCH, batch_type));
+                entry.insert(
+                    BatchInfo::new(&batch_id, EPOCHS_PER_BATCH, batch_type)
+                        .with_max_download_attempts(max_batch_download_attempts(network)),
+                );
                 self.to_be_downloaded += EPOCHS_PER_BATCH;
                 Some(batch_id)
             }
@@ -1238,6 +1311,26 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
     }
 }
 
+/// The configured maximum download attempts of a range sync batch.
+fn max_batch_download_attempts<T: BeaconChainTypes>(network: &SyncNetworkContext<T>) -> u8 {
+    network
+        .network_globals()
+        .config
+        .range_sync_max_batch_download_attempts
+}
+
+/// Registers a failed download of `batch`, counting the batches that exhausted their download
+/// attempts.
+fn batch_download_failed<E: EthSpec>(
+    batch: &mut BatchInfo<E>,
+) -> Result<BatchOperationOutcome, WrongBatchState> {
+    let outcome = batch.download_failed(true)?;
+    if matches!(outcome, BatchOperationOutcome::Failed { .. }) {
+        metrics::inc_counter(&metrics::SYNC_RANGE_BATCH_DOWNLOAD_ATTEMPTS_EXHAUSTED);
+    }
+    Ok(outcome)
+}
+
 impl<T: BeaconChainTypes> slog::KV for &mut SyncingChain<T> {
     fn serialize(
         &self,
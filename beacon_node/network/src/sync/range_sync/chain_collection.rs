@@ -6,6 +6,7 @@
 use super::chain::{ChainId, ProcessingResult, RemoveChain, SyncingChain};
 use super::sync_type::RangeSyncType;
 use crate::metrics;
+use crate::sync::decision_log::SyncDecision;
 use crate::sync::network_context::SyncNetworkContext;
 use beacon_chain::{BeaconChain, BeaconChainTypes};
 use fnv::FnvHashMap;
@@ -504,6 +505,13 @@ impl<T: BeaconChainTypes> ChainCollection<T> {
                     &self.log,
                 );
                 debug!(self.log, "New chain added to sync"; "peer_id" => peer_rpr, "sync_type" => ?sync_type, &new_chain);
+                network.record_decision(SyncDecision::ChainAdded {
+                    id,
+                    sync_type: sync_type.as_str(),
+                    start_epoch,
+                    target_head_slot,
+                    target_head_root,
+                });
                 collection.insert(id, new_chain);
                 metrics::inc_counter_vec(&metrics::SYNCING_CHAINS_ADDED, &[sync_type.as_str()]);
                 self.update_metrics();
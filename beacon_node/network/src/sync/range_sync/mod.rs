@@ -4,6 +4,7 @@
 mod batch;
 mod chain;
 mod chain_collection;
+mod progress;
 mod range;
 mod sync_type;
 
@@ -14,5 +15,6 @@ pub use batch::{
 pub use chain::{BatchId, ChainId, EPOCHS_PER_BATCH};
 #[cfg(test)]
 pub use chain_collection::SyncChainStatus;
+pub use progress::RangeSyncProgress;
 pub use range::RangeSync;
 pub use sync_type::RangeSyncType;
@@ -0,0 +1,108 @@
+//! Tracks the progress of range sync to estimate the time remaining until it reaches its target.
+
+use std::collections::VecDeque;
+use std::time::Instant;
+use types::Slot;
+
+/// The number of observations of the local head slot used to compute the rolling sync rate. The
+/// sync manager observes the head once per metrics interval.
+const PROGRESS_OBSERVATIONS: usize = 12;
+
+/// A rolling window of observations of the local head slot while range syncing.
+#[derive(Default)]
+pub struct RangeSyncProgress {
+    observations: VecDeque<(Slot, Instant)>,
+}
+
+impl RangeSyncProgress {
+    /// Observe that the local head was at `slot` at the given `instant`.
+    pub fn observe(&mut self, slot: Slot, instant: Instant) {
+        if self.observations.len() == PROGRESS_OBSERVATIONS {
+            self.observations.pop_front();
+        }
+        self.observations.push_back((slot, instant));
+    }
+
+    /// Clears all past observations, e.g. once range sync is no longer running.
+    pub fn clear(&mut self) {
+        self.observations.clear();
+    }
+
+    /// Returns the rate at which the local head advanced between the oldest and the latest
+    /// observations, or `None` if less than two observations span a non-zero duration.
+    pub fn slots_per_second(&self) -> Option<f64> {
+        let (first_slot, first_instant) = self.observations.front()?;
+        let (last_slot, last_instant) = self.observations.back()?;
+        let seconds = last_instant.duration_since(*first_instant).as_secs_f64();
+        if seconds > 0.0 {
+            Some(last_slot.saturating_sub(*first_slot).as_u64() as f64 / seconds)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the estimated number of seconds until the local head reaches `target_slot` at the
+    /// current rate.
+    ///
+    /// Returns `None` if the rate is unknown or zero, as no estimate can be made.
+    pub fn estimated_seconds_to_slot(&self, target_slot: Slot) -> Option<u64> {
+        let (latest_slot, _) = self.observations.back()?;
+        let slots_per_second = self.slots_per_second()?;
+        if slots_per_second > 0.0 {
+            let distance = target_slot.saturating_sub(*latest_slot).as_u64() as f64;
+            Some((distance / slots_per_second).ceil() as u64)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn estimates_time_to_target_slot() {
+        let mut progress = RangeSyncProgress::default();
+        let start = Instant::now();
+        progress.observe(Slot::new(100), start);
+        progress.observe(Slot::new(150), start + Duration::from_secs(5));
+        progress.observe(Slot::new(200), start + Duration::from_secs(10));
+
+        assert_eq!(progress.slots_per_second(), Some(10.0));
+        assert_eq!(
+            progress.estimated_seconds_to_slot(Slot::new(1_200)),
+            Some(100)
+        );
+        assert_eq!(progress.estimated_seconds_to_slot(Slot::new(150)), Some(0));
+    }
+
+    #[test]
+    fn no_estimate_without_progress() {
+        let mut progress = RangeSyncProgress::default();
+        let start = Instant::now();
+        assert_eq!(progress.estimated_seconds_to_slot(Slot::new(1_000)), None);
+
+        progress.observe(Slot::new(100), start);
+        assert_eq!(progress.estimated_seconds_to_slot(Slot::new(1_000)), None);
+
+        progress.observe(Slot::new(100), start + Duration::from_secs(5));
+        assert_eq!(progress.slots_per_second(), Some(0.0));
+        assert_eq!(progress.estimated_seconds_to_slot(Slot::new(1_000)), None);
+    }
+
+    #[test]
+    fn rate_only_considers_recent_observations() {
+        let mut progress = RangeSyncProgress::default();
+        let start = Instant::now();
+        for i in 0..PROGRESS_OBSERVATIONS as u64 {
+            progress.observe(Slot::new(i * 100), start + Duration::from_secs(i));
+        }
+        let stalled_at = Slot::new((PROGRESS_OBSERVATIONS as u64 - 1) * 100);
+        for i in 0..PROGRESS_OBSERVATIONS as u64 {
+            progress.observe(stalled_at, start + Duration::from_secs(100 + i));
+        }
+        assert_eq!(progress.slots_per_second(), Some(0.0));
+    }
+}
@@ -44,6 +44,7 @@ use super::chain_collection::{ChainCollection, SyncChainStatus};
 use super::sync_type::RangeSyncType;
 use crate::metrics;
 use crate::status::ToStatusMessage;
+use crate::sync::decision_log::SyncDecision;
 use crate::sync::network_context::SyncNetworkContext;
 use crate::sync::BatchProcessResult;
 use beacon_chain::block_verification_types::RpcBlock;
@@ -335,6 +336,12 @@ where
             debug!(self.log, "Chain removed"; "sync_type" => ?sync_type, &chain, "reason" => ?remove_reason, "op" => op);
         }
 
+        network.record_decision(SyncDecision::ChainRemoved {
+            id: chain.get_id(),
+            sync_type: sync_type.as_str(),
+            reason: format!("{remove_reason:?}"),
+        });
+
         if let RemoveChain::ChainFailed { blacklist, .. } = remove_reason {
             if RangeSyncType::Finalized == sync_type && blacklist {
                 warn!(self.log, "Chain failed! Syncing to its head won't be retried for at least the next {} seconds", FAILED_CHAINS_EXPIRY_SECONDS; &chain);
@@ -377,4 +384,21 @@ where
             );
         }
     }
+
+    /// Re-attempts the batches that are waiting for peers on some of the sampling column subnets,
+    /// failing those that have exceeded the grace period.
+    pub fn send_batches_awaiting_custody_peers(&mut self, network: &mut SyncNetworkContext<T>) {
+        for (removed_chain, sync_type, remove_reason) in self
+            .chains
+            .call_all(|chain| chain.send_batches_awaiting_custody_peers(network))
+        {
+            self.on_chain_removed(
+                removed_chain,
+                sync_type,
+                remove_reason,
+                network,
+                "awaiting custody peers",
+            );
+        }
+    }
 }
@@ -1,13 +1,16 @@
 use crate::network_beacon_processor::NetworkBeaconProcessor;
-use crate::sync::block_lookups::{
-    BlockLookupSummary, PARENT_DEPTH_TOLERANCE, SINGLE_BLOCK_LOOKUP_MAX_ATTEMPTS,
-};
+use crate::sync::block_lookups::{BlockLookupSummary, SINGLE_BLOCK_LOOKUP_MAX_ATTEMPTS};
 use crate::sync::{
-    manager::{BlockProcessType, BlockProcessingResult, SyncManager},
+    manager::{
+        BlockProcessType, BlockProcessingResult, SyncManager,
+        MAX_UNKNOWN_ROOT_LOOKUPS_PER_PEER_PER_SLOT,
+    },
     peer_sampling::SamplingConfig,
-    SamplingId, SyncMessage,
+    LookupStatus, SamplingId, SyncMessage,
 };
 use crate::NetworkMessage;
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -23,11 +26,12 @@ use beacon_chain::{
         BeaconChainHarness, EphemeralHarnessType, LoggerType, NumBlobs,
     },
     validator_monitor::timestamp_now,
-    AvailabilityPendingExecutedBlock, AvailabilityProcessingStatus, BlockError,
+    AvailabilityPendingExecutedBlock, AvailabilityProcessingStatus, BlockError, EngineState,
     PayloadVerificationOutcome, PayloadVerificationStatus,
 };
-use beacon_processor::WorkEvent;
+use beacon_processor::{Work, WorkEvent};
 use lighthouse_network::{
+    config::DEFAULT_MAX_PARENT_LOOKUP_DEPTH,
     rpc::{RPCError, RequestType, RpcErrorResponse},
     service::api_types::{
         AppRequestId, DataColumnsByRootRequestId, DataColumnsByRootRequester, Id,
@@ -56,6 +60,14 @@ type DCByRootId = (SyncRequestId, Vec<ColumnIndex>);
 
 impl TestRig {
     pub fn test_setup() -> Self {
+        // Tests expect a peer reporting an advanced status to trigger range sync right away
+        Self::test_setup_with_network_config(NetworkConfig {
+            advanced_status_grace: 1,
+            ..NetworkConfig::default()
+        })
+    }
+
+    pub fn test_setup_with_network_config(network_config: NetworkConfig) -> Self {
         let logger_type = if cfg!(feature = "test_logger") {
             LoggerType::Test
         } else if cfg!(feature = "ci_logger") {
@@ -93,7 +105,7 @@ impl TestRig {
         let (sync_tx, sync_rx) = mpsc::unbounded_channel::<SyncMessage<E>>();
         // TODO(das): make the generation of the ENR use the deterministic rng to have consistent
         // column assignments
-        let network_config = Arc::new(NetworkConfig::default());
+        let network_config = Arc::new(network_config);
         let globals = Arc::new(NetworkGlobals::new_test_globals(
             Vec::new(),
             &log,
@@ -121,6 +133,7 @@ impl TestRig {
         TestRig {
             beacon_processor_rx,
             beacon_processor_rx_queue: vec![],
+            beacon_processor_tx: beacon_processor.beacon_processor_send.clone(),
             network_rx,
             network_rx_queue: vec![],
             sync_rx,
@@ -195,7 +208,7 @@ impl TestRig {
     }
 
     /// Drain all sync messages in the sync_rx attached to the beacon processor
-    fn drain_sync_rx(&mut self) {
+    pub fn drain_sync_rx(&mut self) {
         while let Ok(sync_message) = self.sync_rx.try_recv() {
             self.send_sync_message(sync_message);
         }
@@ -308,6 +321,13 @@ impl TestRig {
         }
     }
 
+    fn assert_not_lookup(&self, block_root: Hash256) {
+        let lookups = self.sync_manager.active_single_lookups();
+        if lookups.iter().any(|l| l.1 == block_root) {
+            panic!("Expected no lookup for {block_root}: {lookups:?}")
+        }
+    }
+
     fn assert_lookup_peers(&self, block_root: Hash256, mut expected_peers: Vec<PeerId>) {
         let mut lookup = self
             .sync_manager
@@ -358,6 +378,30 @@ impl TestRig {
         );
     }
 
+    fn lookup_status(&mut self, block_root: Hash256) -> Option<LookupStatus> {
+        let (tx, mut rx) = tokio::sync::oneshot::channel();
+        self.send_sync_message(SyncMessage::IsLookupActive(block_root, tx));
+        rx.try_recv().expect("should respond")
+    }
+
+    fn denylist_parent_roots(&mut self, roots: Vec<Hash256>) -> Vec<Hash256> {
+        let (sender, mut rx) = tokio::sync::oneshot::channel();
+        self.send_sync_message(SyncMessage::DenylistParentRoots { roots, sender });
+        rx.try_recv().expect("should respond")
+    }
+
+    fn engine_state_view(&mut self) -> EngineState {
+        let (tx, mut rx) = tokio::sync::oneshot::channel();
+        self.send_sync_message(SyncMessage::GetEngineStateView(tx));
+        rx.try_recv().expect("should respond")
+    }
+
+    fn columns_without_custody_peers(&mut self) -> Vec<ColumnIndex> {
+        let (tx, mut rx) = tokio::sync::oneshot::channel();
+        self.send_sync_message(SyncMessage::GetColumnsWithoutCustodyPeers(tx));
+        rx.try_recv().expect("should respond")
+    }
+
     #[track_caller]
     fn expect_no_active_lookups(&self) {
         self.expect_no_active_single_lookups();
@@ -441,6 +485,7 @@ impl TestRig {
         self.send_sync_message(SyncMessage::BlockComponentProcessed {
             process_type: BlockProcessType::SingleBlock { id },
             result,
+            stage_timings: None,
         })
     }
 
@@ -456,6 +501,7 @@ impl TestRig {
         self.send_sync_message(SyncMessage::BlockComponentProcessed {
             process_type: BlockProcessType::SingleBlob { id },
             result,
+            stage_timings: None,
         })
     }
 
@@ -719,12 +765,13 @@ impl TestRig {
         })
     }
 
-    fn complete_valid_custody_request(
+    /// Completes the `data_columns_by_root` requests of a custody request with valid columns and
+    /// returns the id of the lookup that issued them.
+    fn complete_custody_column_downloads(
         &mut self,
         ids: DCByRootIds,
-        data_columns: Vec<Arc<DataColumnSidecar<E>>>,
-        missing_components: bool,
-    ) {
+        data_columns: &[Arc<DataColumnSidecar<E>>],
+    ) -> Id {
         let lookup_id = if let SyncRequestId::DataColumnsByRoot(DataColumnsByRootRequestId {
             requester: DataColumnsByRootRequester::Custody(id),
             ..
@@ -735,8 +782,6 @@ impl TestRig {
             panic!("not a custody requester")
         };
 
-        let first_column = data_columns.first().cloned().unwrap();
-
         for id in ids {
             self.log(&format!("return valid data column for {id:?}"));
             let indices = &id.1;
@@ -747,6 +792,18 @@ impl TestRig {
             self.complete_data_columns_by_root_request(id, &columns_to_send);
         }
 
+        lookup_id
+    }
+
+    fn complete_valid_custody_request(
+        &mut self,
+        ids: DCByRootIds,
+        data_columns: Vec<Arc<DataColumnSidecar<E>>>,
+        missing_components: bool,
+    ) {
+        let lookup_id = self.complete_custody_column_downloads(ids, &data_columns);
+        let first_column = data_columns.first().cloned().unwrap();
+
         // Expect work event
         // TODO(das): worth it to append sender id to the work event for stricter assertion?
         self.expect_rpc_custody_column_work_event();
@@ -764,6 +821,7 @@ impl TestRig {
                     first_column.block_root(),
                 ))
             },
+            stage_timings: None,
         });
     }
 
@@ -820,6 +878,24 @@ impl TestRig {
         }
     }
 
+    /// Fills the beacon processor channel with no-op work, so that sending to it fails.
+    fn fill_beacon_processor_channel(&mut self) {
+        while self
+            .beacon_processor_tx
+            .try_send(WorkEvent {
+                drop_during_sync: false,
+                work: Work::GossipVoluntaryExit(Box::new(|| {})),
+            })
+            .is_ok()
+        {}
+    }
+
+    /// Discards all the work in the beacon processor channel.
+    fn clear_beacon_processor_channel(&mut self) {
+        self.drain_processor_rx();
+        self.beacon_processor_rx_queue.clear();
+    }
+
     fn drain_processor_rx(&mut self) {
         while let Ok(event) = self.beacon_processor_rx.try_recv() {
             self.beacon_processor_rx_queue.push(event);
@@ -1367,6 +1443,134 @@ fn test_single_block_lookup_happy_path() {
     rig.expect_no_active_lookups();
 }
 
+#[test]
+fn block_sent_for_processing_again_after_processor_channel_full() {
+    let mut rig = TestRig::test_setup();
+    let block = rig.rand_block();
+    let peer_id = rig.new_connected_peer();
+    let block_root = block.canonical_root();
+    rig.trigger_unknown_block_from_attestation(block_root, peer_id);
+    let id = rig.expect_block_lookup_request(block_root);
+
+    // The downloaded block can't be sent for processing while the channel is full, it's retained
+    rig.fill_beacon_processor_channel();
+    rig.single_lookup_block_response(id, peer_id, Some(block.into()));
+    rig.clear_beacon_processor_channel();
+    rig.expect_empty_network();
+    rig.assert_single_lookups_count(1);
+
+    // Once the channel has capacity the block is sent again without downloading it again
+    rig.sync_manager.retry_deferred_processor_sends();
+    rig.expect_block_process(ResponseType::Block);
+    rig.expect_empty_network();
+}
+
+#[test]
+fn lookup_requires_configured_peer_agreement() {
+    let mut rig = TestRig::test_setup();
+    rig.send_sync_message(SyncMessage::SetLookupPeerAgreement(
+        NonZeroUsize::new(2).unwrap(),
+    ));
+    let block = Arc::new(rig.rand_block());
+    let block_root = block.canonical_root();
+    let peer_1 = rig.new_connected_peer();
+    rig.trigger_unknown_block_from_attestation(block_root, peer_1);
+    let id = rig.expect_block_lookup_request(block_root);
+
+    // A single peer returning the block is not enough, the lookup waits for another peer
+    rig.single_lookup_block_response(id, peer_1, Some(block.clone()));
+    rig.expect_empty_network();
+    rig.expect_empty_beacon_processor();
+    rig.assert_single_lookups_count(1);
+
+    // The block is requested from a new peer, and sent for processing once it agrees
+    let peer_2 = rig.new_connected_peer();
+    rig.trigger_unknown_block_from_attestation(block_root, peer_2);
+    let id = rig.expect_block_lookup_request(block_root);
+    rig.single_lookup_block_response(id, peer_2, Some(block));
+    rig.expect_block_process(ResponseType::Block);
+}
+
+#[test]
+fn lookup_peer_agreement_with_malicious_peer_answering_first() {
+    let mut rig = TestRig::test_setup();
+    rig.send_sync_message(SyncMessage::SetLookupPeerAgreement(
+        NonZeroUsize::new(2).unwrap(),
+    ));
+    let block = Arc::new(rig.rand_block());
+    let block_root = block.canonical_root();
+    // Same block root as the honest block, but a different signature
+    let (message, _) = block.as_ref().clone().deconstruct();
+    let forged_block = Arc::new(SignedBeaconBlock::from_block(
+        message,
+        types::Signature::empty(),
+    ));
+
+    let malicious_peer = rig.new_connected_peer();
+    rig.trigger_unknown_block_from_attestation(block_root, malicious_peer);
+    let id = rig.expect_block_lookup_request(block_root);
+    rig.single_lookup_block_response(id, malicious_peer, Some(forged_block));
+    rig.expect_empty_network();
+
+    // A disagreeing peer does not discard the forged block yet, nobody is known to be honest
+    let peer_1 = rig.new_connected_peer();
+    rig.trigger_unknown_block_from_attestation(block_root, peer_1);
+    let id = rig.expect_block_lookup_request(block_root);
+    rig.single_lookup_block_response(id, peer_1, Some(block.clone()));
+    rig.expect_empty_network();
+    rig.expect_empty_beacon_processor();
+    rig.assert_single_lookups_count(1);
+
+    // Once a second peer agrees with the honest block it is processed, and the malicious peer
+    // is penalized
+    let peer_2 = rig.new_connected_peer();
+    rig.trigger_unknown_block_from_attestation(block_root, peer_2);
+    let id = rig.expect_block_lookup_request(block_root);
+    rig.single_lookup_block_response(id, peer_2, Some(block));
+    rig.expect_single_penalty(malicious_peer, "lookup_peer_disagreement");
+    rig.expect_block_process(ResponseType::Block);
+}
+
+#[test]
+fn lookup_status_reports_active_lookups() {
+    let mut rig = TestRig::test_setup();
+    let (_, block, parent_root, block_root) = rig.rand_block_and_parent();
+    let peer_id = rig.new_connected_peer();
+    rig.trigger_unknown_parent_block(peer_id, block.into());
+    rig.expect_block_parent_request(parent_root);
+
+    let parent = rig
+        .lookup_status(parent_root)
+        .expect("parent lookup is active");
+    assert_eq!(parent.block_root, parent_root);
+    assert_eq!(parent.awaiting_parent, None);
+    assert_eq!(parent.child_lookups, 1);
+    assert_eq!(parent.peer_count, 1);
+    assert!(parent.block_request_state.starts_with("Downloading"));
+
+    let child = rig
+        .lookup_status(block_root)
+        .expect("child lookup is active");
+    assert_eq!(child.awaiting_parent, Some(parent_root));
+    assert_eq!(child.child_lookups, 0);
+
+    assert!(rig.lookup_status(Hash256::repeat_byte(0xff)).is_none());
+}
+
+#[test]
+fn engine_state_view_follows_execution_engine_updates() {
+    let mut rig = TestRig::test_setup();
+    assert_eq!(rig.engine_state_view(), EngineState::Online);
+
+    rig.sync_manager
+        .update_execution_engine_state(EngineState::Offline);
+    assert_eq!(rig.engine_state_view(), EngineState::Offline);
+
+    rig.sync_manager
+        .update_execution_engine_state(EngineState::Online);
+    assert_eq!(rig.engine_state_view(), EngineState::Online);
+}
+
 // Tests that if a peer does not respond with a block, we downscore and retry the block only
 #[test]
 fn test_single_block_lookup_empty_response() {
@@ -1684,7 +1888,7 @@ fn test_parent_lookup_too_many_processing_attempts_must_blacklist() {
 #[test]
 fn test_parent_lookup_too_deep_grow_ancestor() {
     let mut rig = TestRig::test_setup();
-    let mut blocks = rig.rand_blockchain(PARENT_DEPTH_TOLERANCE);
+    let mut blocks = rig.rand_blockchain(DEFAULT_MAX_PARENT_LOOKUP_DEPTH);
 
     let peer_id = rig.new_connected_peer();
     let trigger_block = blocks.pop().unwrap();
@@ -1715,7 +1919,7 @@ fn test_parent_lookup_too_deep_grow_ancestor() {
         (
             RangeSyncType::Head,
             Slot::new(0),
-            Slot::new(PARENT_DEPTH_TOLERANCE as u64 - 1)
+            Slot::new(DEFAULT_MAX_PARENT_LOOKUP_DEPTH as u64 - 1)
         )
     );
     // Should not penalize peer, but network is not clear because of the blocks_by_range requests
@@ -1723,10 +1927,160 @@ fn test_parent_lookup_too_deep_grow_ancestor() {
     rig.assert_failed_chain(chain_hash);
 }
 
+#[test]
+fn test_parent_lookup_exceeding_configured_depth_triggers_range_sync() {
+    let max_parent_lookup_depth = 4;
+    let mut rig = TestRig::test_setup_with_network_config(NetworkConfig {
+        max_parent_lookup_depth,
+        ..NetworkConfig::default()
+    });
+    let mut blocks = rig.rand_blockchain(max_parent_lookup_depth);
+
+    let peer_id = rig.new_connected_peer();
+    let trigger_block = blocks.pop().unwrap();
+    let chain_hash = trigger_block.canonical_root();
+    rig.trigger_unknown_parent_block(peer_id, trigger_block);
+
+    for block in blocks.into_iter().rev() {
+        let id = rig.expect_block_parent_request(block.canonical_root());
+        rig.parent_lookup_block_response(id, peer_id, Some(block.clone()));
+        rig.parent_lookup_block_response(id, peer_id, None);
+        rig.expect_block_process(ResponseType::Block);
+        rig.parent_block_processed(
+            chain_hash,
+            BlockProcessingResult::Err(BlockError::ParentUnknown {
+                parent_root: block.parent_root(),
+            }),
+        )
+    }
+
+    // The chain is well below the default depth, but exceeds the configured one
+    rig.drain_sync_rx();
+    assert_eq!(
+        rig.active_range_sync_chain(),
+        (
+            RangeSyncType::Head,
+            Slot::new(0),
+            Slot::new(max_parent_lookup_depth as u64 - 1)
+        )
+    );
+    rig.expect_no_penalty_for(peer_id);
+    rig.assert_failed_chain(chain_hash);
+}
+
+#[test]
+fn test_unknown_block_from_attestation_rate_limited_per_peer() {
+    let mut rig = TestRig::test_setup();
+    let peer_id = rig.new_connected_peer();
+    let other_peer_id = rig.new_connected_peer();
+
+    for i in 0..MAX_UNKNOWN_ROOT_LOOKUPS_PER_PEER_PER_SLOT * 4 {
+        rig.trigger_unknown_block_from_attestation(Hash256::repeat_byte(i as u8 + 1), peer_id);
+    }
+    rig.assert_single_lookups_count(MAX_UNKNOWN_ROOT_LOOKUPS_PER_PEER_PER_SLOT);
+    rig.expect_single_penalty(peer_id, "too_many_unknown_roots");
+
+    // Other peers have their own budget.
+    rig.trigger_unknown_block_from_attestation(Hash256::repeat_byte(0xff), other_peer_id);
+    rig.assert_single_lookups_count(MAX_UNKNOWN_ROOT_LOOKUPS_PER_PEER_PER_SLOT + 1);
+    rig.expect_no_penalty_for(other_peer_id);
+}
+
+#[test]
+fn test_denylisted_parent_root_creates_no_lookup() {
+    let parent_root = Hash256::repeat_byte(0xaa);
+    let mut rig = TestRig::test_setup_with_network_config(NetworkConfig {
+        parent_lookup_denylist: vec![parent_root],
+        ..NetworkConfig::default()
+    });
+    let mut block = rig.rand_block();
+    *block.message_mut().parent_root_mut() = parent_root;
+    let peer_id = rig.new_connected_peer();
+
+    rig.trigger_unknown_parent_block(peer_id, block.into());
+    rig.expect_empty_network();
+    rig.expect_no_active_lookups();
+}
+
+#[test]
+fn test_parent_root_denylisted_at_runtime_creates_no_lookup() {
+    let mut rig = TestRig::test_setup();
+    let (_, block, parent_root, _) = rig.rand_block_and_parent();
+    let (_, other_block, other_parent_root, _) = rig.rand_block_and_parent();
+    let peer_id = rig.new_connected_peer();
+
+    assert_eq!(
+        rig.denylist_parent_roots(vec![parent_root]),
+        vec![parent_root]
+    );
+    rig.trigger_unknown_parent_block(peer_id, block.into());
+    rig.expect_empty_network();
+    rig.expect_no_active_lookups();
+
+    // Roots which are not denylisted are still looked up.
+    rig.trigger_unknown_parent_block(peer_id, other_block.into());
+    rig.expect_block_parent_request(other_parent_root);
+}
+
+#[test]
+fn test_denylisted_parent_root_of_processed_block_creates_no_lookup() {
+    let mut rig = TestRig::test_setup();
+    let block = Arc::new(rig.rand_block());
+    let block_root = block.canonical_root();
+    let parent_root = block.parent_root();
+    let peer_id = rig.new_connected_peer();
+    rig.denylist_parent_roots(vec![parent_root]);
+
+    rig.trigger_unknown_block_from_attestation(block_root, peer_id);
+    let id = rig.expect_block_lookup_request(block_root);
+    rig.single_lookup_block_response(id, peer_id, Some(block.clone()));
+    rig.expect_block_process(ResponseType::Block);
+
+    // The block turns out to have a denylisted unknown parent, which must not be looked up.
+    rig.single_block_component_processed(
+        id.lookup_id,
+        BlockProcessingResult::Err(BlockError::ParentUnknown { parent_root }),
+    );
+    rig.expect_empty_network();
+    rig.expect_no_active_lookups();
+}
+
+#[test]
+fn test_parent_lookups_exceeding_configured_max_are_ignored() {
+    let mut rig = TestRig::test_setup_with_network_config(NetworkConfig {
+        max_concurrent_parent_lookups: 1,
+        ..NetworkConfig::default()
+    });
+    let (_, block, parent_root, block_root) = rig.rand_block_and_parent();
+    let (_, other_block, other_parent_root, other_block_root) = rig.rand_block_and_parent();
+    let mut sibling_block = rig.rand_block();
+    *sibling_block.message_mut().parent_root_mut() = parent_root;
+    let sibling_block_root = sibling_block.canonical_root();
+    let peer_1 = rig.new_connected_peer();
+    let peer_2 = rig.new_connected_peer();
+    let peer_3 = rig.new_connected_peer();
+
+    rig.trigger_unknown_parent_block(peer_1, block.into());
+    rig.expect_block_parent_request(parent_root);
+    rig.assert_parent_lookups_count(1);
+
+    // A trigger for a new chain exceeds the max, no lookups are created
+    rig.trigger_unknown_parent_block(peer_2, other_block.into());
+    rig.assert_parent_lookups_count(1);
+    rig.assert_not_lookup(other_block_root);
+    rig.assert_not_lookup(other_parent_root);
+
+    // A trigger for the existing chain still attaches its peer to it
+    rig.trigger_unknown_parent_block(peer_3, sibling_block.into());
+    rig.assert_lookup_is_active(block_root);
+    rig.assert_lookup_is_active(sibling_block_root);
+    rig.assert_lookup_peers(parent_root, vec![peer_1, peer_3]);
+}
+
 #[test]
 fn test_parent_lookup_too_deep_grow_tip() {
     let mut rig = TestRig::test_setup();
-    let blocks = rig.rand_blockchain(PARENT_DEPTH_TOLERANCE - 1);
+    let blocks = rig.rand_blockchain(DEFAULT_MAX_PARENT_LOOKUP_DEPTH - 1);
     let peer_id = rig.new_connected_peer();
     let tip = blocks.last().unwrap().clone();
 
@@ -1753,7 +2107,7 @@ fn test_parent_lookup_too_deep_grow_tip() {
         (
             RangeSyncType::Head,
             Slot::new(0),
-            Slot::new(PARENT_DEPTH_TOLERANCE as u64 - 2)
+            Slot::new(DEFAULT_MAX_PARENT_LOOKUP_DEPTH as u64 - 2)
         )
     );
     // Should not penalize peer, but network is not clear because of the blocks_by_range requests
@@ -2019,6 +2373,25 @@ fn blobs_in_da_checker_skip_download() {
     r.expect_no_active_lookups();
 }
 
+#[test]
+fn lookups_dropped_when_execution_engine_offline() {
+    let mut rig = TestRig::test_setup();
+    let (_, block, parent_root, _) = rig.rand_block_and_parent();
+    let peer_id = rig.new_connected_peer();
+
+    // Trigger the request
+    rig.trigger_unknown_parent_block(peer_id, block.into());
+    rig.expect_block_parent_request(parent_root);
+    rig.assert_parent_lookups_count(1);
+
+    rig.sync_manager
+        .update_execution_engine_state(EngineState::Offline);
+
+    // Both the child lookup and its parent lookup are dropped.
+    rig.assert_parent_lookups_count(0);
+    rig.expect_no_active_lookups();
+}
+
 #[test]
 fn sampling_happy_path() {
     let Some(mut r) = TestRig::test_setup_after_fulu() else {
@@ -2105,6 +2478,43 @@ fn sampling_batch_requests() {
     r.expect_clean_finished_sampling();
 }
 
+#[test]
+fn sampling_uses_updated_config_for_new_requests() {
+    let Some(mut r) = TestRig::test_setup_after_fulu() else {
+        return;
+    };
+    let _supernode = r.new_connected_supernode_peer();
+    let (block_1, _) = r.rand_block_and_data_columns();
+    let block_root_1 = block_1.canonical_root();
+    r.trigger_sample_block(block_root_1, block_1.slot());
+
+    // The first request is created with the initial config.
+    let (_, column_indexes_1) = r
+        .expect_only_data_columns_by_root_requests(block_root_1, 1)
+        .pop()
+        .unwrap();
+    assert_eq!(column_indexes_1.len(), SAMPLING_REQUIRED_SUCCESSES);
+
+    let updated_required_successes = SAMPLING_REQUIRED_SUCCESSES + 1;
+    r.send_sync_message(SyncMessage::SetSamplingConfig(SamplingConfig::Custom {
+        required_successes: vec![updated_required_successes],
+    }));
+
+    // A new request samples with the updated config.
+    let (block_2, _) = r.rand_block_and_data_columns();
+    let block_root_2 = block_2.canonical_root();
+    r.trigger_sample_block(block_root_2, block_2.slot());
+    let (_, column_indexes_2) = r
+        .expect_only_data_columns_by_root_requests(block_root_2, 1)
+        .pop()
+        .unwrap();
+    assert_eq!(column_indexes_2.len(), updated_required_successes);
+
+    // The in-flight request keeps its original config.
+    r.assert_sampling_request_ongoing(block_root_1, &column_indexes_1);
+    r.expect_active_sampling(&block_root_1);
+}
+
 #[test]
 fn sampling_batch_requests_not_enough_responses_returned() {
     let Some(mut r) = TestRig::test_setup_after_fulu() else {
@@ -2150,6 +2560,85 @@ fn sampling_batch_requests_not_enough_responses_returned() {
     r.expect_active_sampling(&block_root);
 }
 
+#[test]
+fn is_supernode() {
+    let Some(r) = TestRig::test_setup_after_fulu() else {
+        return;
+    };
+    assert!(!r.sync_manager.is_supernode());
+
+    let r = TestRig::test_setup_with_network_config(NetworkConfig {
+        subscribe_all_data_column_subnets: true,
+        ..NetworkConfig::default()
+    });
+    assert_eq!(
+        r.network_globals.sampling_columns.len() as u64,
+        r.spec.number_of_columns
+    );
+    assert!(r.sync_manager.is_supernode());
+}
+
+#[test]
+fn custody_peer_coverage() {
+    let Some(mut r) = TestRig::test_setup_after_fulu() else {
+        return;
+    };
+    let coverage = r.sync_manager.custody_peer_coverage();
+    assert_eq!(coverage.len(), r.network_globals.sampling_columns.len());
+    assert!(coverage.iter().all(|(_, peers)| *peers == 0));
+
+    // A supernode can serve every column.
+    r.new_connected_supernode_peer();
+    let coverage = r.sync_manager.custody_peer_coverage();
+    assert!(coverage.iter().all(|(_, peers)| *peers == 1));
+}
+
+#[test]
+fn columns_without_custody_peers() {
+    let Some(mut r) = TestRig::test_setup_after_fulu() else {
+        return;
+    };
+    let mut sampling_columns = r
+        .network_globals
+        .sampling_columns
+        .iter()
+        .copied()
+        .collect::<Vec<_>>();
+    sampling_columns.sort_unstable();
+    assert_eq!(r.columns_without_custody_peers(), sampling_columns);
+
+    // A supernode can serve every column.
+    r.new_connected_supernode_peer();
+    assert!(r.columns_without_custody_peers().is_empty());
+}
+
+#[test]
+fn preferred_custody_peer_is_selected_until_disconnected() {
+    let Some(mut r) = TestRig::test_setup_after_fulu() else {
+        return;
+    };
+    let column_index = 0;
+    let preferred_peer = r.new_connected_supernode_peer();
+    let other_peer = r.new_connected_supernode_peer();
+    r.sync_manager
+        .set_preferred_custody_peer(column_index, preferred_peer);
+
+    for _ in 0..32 {
+        assert_eq!(
+            r.sync_manager.get_random_custodial_peer(column_index),
+            Some(preferred_peer)
+        );
+    }
+
+    r.peer_disconnected(preferred_peer);
+    assert_eq!(r.sync_manager.preferred_custody_peer(column_index), None);
+    // The chance of never selecting the other custodial peer at random is negligible.
+    let selected = (0..64)
+        .filter_map(|_| r.sync_manager.get_random_custodial_peer(column_index))
+        .collect::<HashSet<_>>();
+    assert!(selected.contains(&other_peer));
+}
+
 #[test]
 fn custody_lookup_happy_path() {
     let Some(mut r) = TestRig::test_setup_after_fulu() else {
@@ -2172,6 +2661,114 @@ fn custody_lookup_happy_path() {
     r.expect_no_active_lookups();
 }
 
+#[test]
+fn custody_lookup_supernode_with_half_columns_cached() {
+    let mut r = TestRig::test_setup_with_network_config(NetworkConfig {
+        subscribe_all_data_column_subnets: true,
+        ..NetworkConfig::default()
+    });
+    if !r.after_fulu() {
+        return;
+    }
+    r.new_connected_peers_for_peerdas();
+    let (block, data_columns) = r.rand_block_and_data_columns();
+    let block_root = block.canonical_root();
+    let half_columns = data_columns.len() / 2;
+
+    // Half the columns are cached, but reconstruction of the rest has not been triggered, so the
+    // remaining columns must still be fetched.
+    r.harness
+        .chain
+        .data_availability_checker
+        .put_rpc_custody_columns(block_root, data_columns[..half_columns].to_vec())
+        .unwrap();
+
+    let peer_id = r.new_connected_peer();
+    r.trigger_unknown_block_from_attestation(block_root, peer_id);
+    let id = r.expect_block_lookup_request(block_root);
+    r.complete_valid_block_request(id, block.into(), true);
+    let custody_ids =
+        r.expect_only_data_columns_by_root_requests(block_root, data_columns.len() - half_columns);
+    r.complete_valid_custody_request(custody_ids, data_columns, false);
+    r.expect_no_active_lookups();
+}
+
+#[test]
+fn custody_lookups_exceeding_configured_max_are_deferred() {
+    let mut r = TestRig::test_setup_with_network_config(NetworkConfig {
+        max_concurrent_custody_requests: 1,
+        ..NetworkConfig::default()
+    });
+    if !r.after_fulu() {
+        return;
+    }
+    let spec = E::default_spec();
+    let sample_column_count = (spec.samples_per_slot * spec.data_columns_per_group()) as usize;
+    r.new_connected_peers_for_peerdas();
+    let (block_1, data_columns_1) = r.rand_block_and_data_columns();
+    let (block_2, _) = r.rand_block_and_data_columns();
+    let block_root_1 = block_1.canonical_root();
+    let block_root_2 = block_2.canonical_root();
+    let peer_id = r.new_connected_peer();
+
+    r.trigger_unknown_block_from_attestation(block_root_1, peer_id);
+    let id = r.expect_block_lookup_request(block_root_1);
+    r.complete_valid_block_request(id, block_1.into(), true);
+    let custody_ids =
+        r.expect_only_data_columns_by_root_requests(block_root_1, sample_column_count);
+
+    // The second custody request exceeds the max and is deferred
+    r.trigger_unknown_block_from_attestation(block_root_2, peer_id);
+    let id = r.expect_block_lookup_request(block_root_2);
+    r.complete_valid_block_request(id, block_2.into(), true);
+    r.expect_empty_network();
+    r.assert_single_lookups_count(2);
+
+    // Once the first custody request completes, the deferred one is sent
+    r.complete_custody_column_downloads(custody_ids, &data_columns_1);
+    r.expect_data_columns_by_root_requests(block_root_2, sample_column_count);
+}
+
+#[test]
+fn custody_columns_downloaded_while_execution_engine_offline_are_processed_once_online() {
+    let Some(mut r) = TestRig::test_setup_after_fulu() else {
+        return;
+    };
+    let spec = E::default_spec();
+    r.new_connected_peers_for_peerdas();
+    let (block, data_columns) = r.rand_block_and_data_columns();
+    let block_root = block.canonical_root();
+    let peer_id = r.new_connected_peer();
+    r.trigger_unknown_block_from_attestation(block_root, peer_id);
+    let id = r.expect_block_lookup_request(block_root);
+    r.complete_valid_block_request(id, block.into(), true);
+    let sample_column_count = spec.samples_per_slot * spec.data_columns_per_group();
+    let custody_ids =
+        r.expect_only_data_columns_by_root_requests(block_root, sample_column_count as usize);
+
+    // The lookup downloading custody columns is retained while the execution engine is offline
+    r.sync_manager
+        .update_execution_engine_state(EngineState::Offline);
+    r.assert_single_lookups_count(1);
+
+    // Columns downloaded while offline are not sent for processing
+    let lookup_id = r.complete_custody_column_downloads(custody_ids, &data_columns);
+    r.expect_empty_beacon_processor();
+    r.assert_single_lookups_count(1);
+
+    // Once online, the retained columns are processed without downloading them again
+    r.sync_manager
+        .update_execution_engine_state(EngineState::Online);
+    r.expect_rpc_custody_column_work_event();
+    r.expect_no_requests_for(block_root);
+    r.send_sync_message(SyncMessage::BlockComponentProcessed {
+        process_type: BlockProcessType::SingleCustodyColumn(lookup_id),
+        result: BlockProcessingResult::Ok(AvailabilityProcessingStatus::Imported(block_root)),
+        stage_timings: None,
+    });
+    r.expect_no_active_lookups();
+}
+
 // TODO(das): Test retries of DataColumnByRoot:
 // - Expect request for column_index
 // - Respond with bad data
@@ -2628,7 +3225,7 @@ mod deneb_only {
             .parent_block_response()
             .expect_parent_blobs_request()
             .empty_parent_blobs_response()
-            .expect_penalty("NotEnoughResponsesReturned")
+            .expect_penalty("BlobCountMismatch")
             .log("Re-request parent blobs, succeed and import parent")
             .expect_parent_blobs_request()
             .parent_blob_response()
@@ -2728,7 +3325,7 @@ mod deneb_only {
             .block_response_and_expect_blob_request()
             .missing_components_from_block_request()
             .empty_blobs_response()
-            .expect_penalty("NotEnoughResponsesReturned")
+            .expect_penalty("BlobCountMismatch")
             .expect_blobs_request()
             .expect_no_block_request();
     }
@@ -2769,14 +3366,24 @@ mod deneb_only {
         let Some(tester) = DenebTester::new(RequestTrigger::AttestationUnknownBlock) else {
             return;
         };
+        // Counters are shared with other tests, only assert that it moved.
+        let not_enough_responses = || {
+            crate::metrics::get_int_counter(
+                &crate::metrics::SYNC_RESPONSE_COUNT_VIOLATIONS,
+                &["blobs_by_root", "not_enough"],
+            )
+            .map_or(0, |counter| counter.get())
+        };
+        let before = not_enough_responses();
         tester
             .block_response_triggering_process()
             .missing_components_from_block_request()
             .invalidate_blobs_too_few()
             .blobs_response()
-            .expect_penalty("NotEnoughResponsesReturned")
+            .expect_penalty("BlobCountMismatch")
             .expect_blobs_request()
             .expect_no_block_request();
+        assert!(not_enough_responses() > before);
     }
 
     // Test peer returning block that has unknown parent, and a new lookup is created
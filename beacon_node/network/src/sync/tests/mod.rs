@@ -5,7 +5,7 @@ use crate::NetworkMessage;
 use beacon_chain::builder::Witness;
 use beacon_chain::eth1_chain::CachingEth1Backend;
 use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
-use beacon_processor::WorkEvent;
+use beacon_processor::{BeaconProcessorSend, WorkEvent};
 use lighthouse_network::NetworkGlobals;
 use slog::Logger;
 use slot_clock::ManualSlotClock;
@@ -48,6 +48,8 @@ struct TestRig {
     /// Receiver for `BeaconProcessor` events (e.g. block processing results).
     beacon_processor_rx: mpsc::Receiver<WorkEvent<E>>,
     beacon_processor_rx_queue: Vec<WorkEvent<E>>,
+    /// Sender of the `BeaconProcessor` channel, to simulate a full channel.
+    beacon_processor_tx: BeaconProcessorSend<E>,
     /// Receiver for `NetworkMessage` (e.g. outgoing RPC requests from sync)
     network_rx: mpsc::UnboundedReceiver<NetworkMessage<E>>,
     /// Stores all `NetworkMessage`s received from `network_recv`. (e.g. outgoing RPC requests)
@@ -1,8 +1,9 @@
 use super::*;
+use crate::metrics;
 use crate::status::ToStatusMessage;
 use crate::sync::manager::SLOT_IMPORT_TOLERANCE;
 use crate::sync::range_sync::RangeSyncType;
-use crate::sync::SyncMessage;
+use crate::sync::{SyncDecision, SyncMessage};
 use beacon_chain::data_column_verification::CustodyDataColumn;
 use beacon_chain::test_utils::{AttestationStrategy, BlockStrategy};
 use beacon_chain::{block_verification_types::RpcBlock, EngineState, NotifyExecutionLayer};
@@ -10,16 +11,19 @@ use lighthouse_network::rpc::methods::{
     BlobsByRangeRequest, DataColumnsByRangeRequest, OldBlocksByRangeRequest,
     OldBlocksByRangeRequestV2,
 };
-use lighthouse_network::rpc::{RequestType, StatusMessage};
+use lighthouse_network::rpc::{RPCError, RequestType, StatusMessage};
 use lighthouse_network::service::api_types::{
     AppRequestId, BlobsByRangeRequestId, BlocksByRangeRequestId, DataColumnsByRangeRequestId,
     SyncRequestId,
 };
-use lighthouse_network::{PeerId, SyncInfo};
+use lighthouse_network::types::{SyncStallReason, SyncState};
+use lighthouse_network::{NetworkConfig, PeerId, SyncInfo};
+use std::collections::HashSet;
 use std::time::Duration;
+use store::AnchorInfo;
 use types::{
-    BlobSidecarList, BlockImportSource, EthSpec, Hash256, MinimalEthSpec as E, SignedBeaconBlock,
-    SignedBeaconBlockHash, Slot,
+    BlobSidecarList, BlockImportSource, Epoch, EthSpec, Hash256, MinimalEthSpec as E,
+    SignedBeaconBlock, SignedBeaconBlockHash, Slot,
 };
 
 const D: Duration = Duration::new(0, 0);
@@ -114,13 +118,51 @@ impl TestRig {
         }
     }
 
+    fn local_fork_digest(&self) -> [u8; 4] {
+        self.harness.chain.status_message().fork_digest
+    }
+
+    /// The fork digest of the fork prior to the current one, if any.
+    fn previous_fork_digest(&self) -> Option<[u8; 4]> {
+        let chain = &self.harness.chain;
+        let previous_fork = chain
+            .spec
+            .fork_name_at_slot::<E>(chain.slot().unwrap())
+            .previous_fork()?;
+        Some(ChainSpec::compute_fork_digest(
+            chain.spec.fork_version_for_name(previous_fork),
+            chain.genesis_validators_root,
+        ))
+    }
+
+    /// Produce a head peer with an advanced head, whose status has the given fork digest.
+    fn add_head_peer_with_fork_digest(&mut self, fork_digest: [u8; 4]) -> PeerId {
+        let local_info = self.local_info();
+        let peer_id = self.new_connected_supernode_peer();
+        self.send_sync_message(SyncMessage::AddPeer(
+            peer_id,
+            SyncInfo {
+                head_root: Hash256::random(),
+                head_slot: local_info.head_slot + 1 + Slot::new(SLOT_IMPORT_TOLERANCE as u64),
+                ..local_info
+            },
+            fork_digest,
+        ));
+        peer_id
+    }
+
     fn add_peer(&mut self, remote_info: SyncInfo) -> PeerId {
         // Create valid peer known to network globals
         // TODO(fulu): Using supernode peers to ensure we have peer across all column
         // subnets for syncing. Should add tests connecting to full node peers.
         let peer_id = self.new_connected_supernode_peer();
         // Send peer to sync
-        self.send_sync_message(SyncMessage::AddPeer(peer_id, remote_info.clone()));
+        let fork_digest = self.local_fork_digest();
+        self.send_sync_message(SyncMessage::AddPeer(
+            peer_id,
+            remote_info.clone(),
+            fork_digest,
+        ));
         peer_id
     }
 
@@ -442,3 +484,311 @@ fn pause_and_resume_on_ee_offline() {
     // The head chain and finalized chain (2) should be in the processing queue
     rig.expect_chain_segments(2);
 }
+
+#[test]
+fn stall_reason_reports_ee_offline() {
+    let mut rig = TestRig::test_setup();
+
+    rig.add_head_peer();
+    assert_eq!(rig.network_globals.sync_stall_reason(), None);
+
+    rig.update_execution_engine_state(EngineState::Offline);
+    assert_eq!(
+        rig.network_globals.sync_stall_reason(),
+        Some(SyncStallReason::ExecutionEngineOffline)
+    );
+
+    rig.update_execution_engine_state(EngineState::Online);
+    assert_eq!(rig.network_globals.sync_stall_reason(), None);
+}
+
+#[test]
+fn core_topics_subscription_awaits_min_synced_peers() {
+    let mut rig = TestRig::test_setup_with_network_config(NetworkConfig {
+        min_synced_peers_for_core_topics: 2,
+        ..NetworkConfig::default()
+    });
+    rig.network_globals.set_sync_state(SyncState::Stalled);
+    let is_subscribe_core_topics =
+        |ev: &NetworkMessage<E>| matches!(ev, NetworkMessage::SubscribeCoreTopics).then_some(());
+
+    // A single synced peer makes us synced, but is not enough to subscribe
+    let local_info = rig.local_info();
+    rig.add_peer(local_info.clone());
+    assert!(rig.network_globals.sync_state().is_synced());
+    rig.pop_received_network_event(is_subscribe_core_topics)
+        .expect_err("should not subscribe to core topics with a single synced peer");
+
+    rig.add_peer(local_info);
+    rig.pop_received_network_event(is_subscribe_core_topics)
+        .expect("should subscribe to core topics with enough synced peers");
+}
+
+#[test]
+fn sync_state_transition_is_counted() {
+    let mut rig = TestRig::test_setup();
+    rig.network_globals.set_sync_state(SyncState::Stalled);
+    // Counters are shared with other tests, only assert that it moved.
+    let transitions_from_stalled_to_synced = || {
+        metrics::get_int_counter(&metrics::SYNC_STATE_TRANSITIONS, &["Stalled", "Synced"])
+            .map_or(0, |counter| counter.get())
+    };
+    let before = transitions_from_stalled_to_synced();
+
+    // Adding a synced peer transitions out of the stalled state.
+    let local_info = rig.local_info();
+    rig.add_peer(local_info);
+    assert!(rig.network_globals.sync_state().is_synced());
+    assert!(transitions_from_stalled_to_synced() > before);
+}
+
+#[test]
+fn synced_peer_must_stay_advanced_to_trigger_range_sync() {
+    // The default grace requires two consecutive advanced statuses
+    let mut rig = TestRig::test_setup_with_network_config(NetworkConfig::default());
+    let local_info = rig.local_info();
+    let peer_id = rig.add_peer(local_info.clone());
+    let advanced_info = SyncInfo {
+        head_root: Hash256::random(),
+        head_slot: local_info.head_slot + 1 + Slot::new(SLOT_IMPORT_TOLERANCE as u64),
+        ..local_info
+    };
+    let fork_digest = rig.local_fork_digest();
+
+    // A single advanced status of a synced peer is considered jitter
+    rig.send_sync_message(SyncMessage::AddPeer(
+        peer_id,
+        advanced_info.clone(),
+        fork_digest,
+    ));
+    assert!(rig.sync_manager.range_sync_state().unwrap().is_none());
+
+    // The peer is still advanced on its next status
+    rig.send_sync_message(SyncMessage::AddPeer(peer_id, advanced_info, fork_digest));
+    rig.assert_state(RangeSyncType::Head);
+}
+
+#[test]
+fn batch_download_retries_rotate_through_peers() {
+    let mut rig = TestRig::test_setup();
+    // Post-PeerDAS the data columns of a batch are requested from custody peers
+    if rig.after_fulu() {
+        return;
+    }
+
+    let finalized_root = Hash256::random();
+    let peers = (0..3)
+        .map(|_| rig.add_finalized_peer_with_root(finalized_root))
+        .collect::<HashSet<_>>();
+
+    // Fail the download of the first batch once per peer, every retry goes to a new peer
+    let mut requested_peers = HashSet::new();
+    for _ in 0..peers.len() {
+        let ((id, peer_id), _) = rig.find_blocks_by_range_request(filter().epoch(0));
+        assert!(
+            requested_peers.insert(peer_id),
+            "batch retried from peer {peer_id} that already failed it"
+        );
+        rig.send_sync_message(SyncMessage::RpcError {
+            peer_id,
+            request_id: SyncRequestId::BlocksByRange(id),
+            error: RPCError::UnsupportedProtocol,
+        });
+    }
+    assert_eq!(requested_peers, peers);
+}
+
+#[test]
+fn batch_awaits_custody_peers_within_grace_period() {
+    let mut rig = TestRig::test_setup();
+    if !rig.after_fulu() {
+        return;
+    }
+
+    // A supernode that is not part of the chain serves the columns of the first request.
+    let supernode = rig.new_connected_supernode_peer();
+    // The only peer of the chain custodies few columns.
+    let local_info = rig.local_info();
+    let remote_info = SyncInfo {
+        head_root: Hash256::random(),
+        head_slot: local_info.head_slot + 1 + Slot::new(SLOT_IMPORT_TOLERANCE as u64),
+        ..local_info
+    };
+    let chain_peer = rig.new_connected_peer();
+    let fork_digest = rig.local_fork_digest();
+    rig.send_sync_message(SyncMessage::AddPeer(
+        chain_peer,
+        remote_info.clone(),
+        fork_digest,
+    ));
+    rig.assert_state(RangeSyncType::Head);
+    let _ = rig.find_blocks_by_range_request(filter().epoch(0));
+
+    // The supernode disconnects and no remaining peer serves all sampling columns. The batch is
+    // held instead of failing the chain.
+    rig.network_globals
+        .peers
+        .write()
+        .__disconnect_peer_testing_only(&supernode);
+    rig.peer_disconnected(supernode);
+    rig.assert_state(RangeSyncType::Head);
+    rig.expect_empty_network();
+
+    // Sync requests the network service to discover peers on the uncovered subnets.
+    rig.drain_sync_rx();
+    rig.pop_received_network_event(|ev| match ev {
+        NetworkMessage::DiscoverDataColumnPeers(_) => Some(()),
+        _ => None,
+    })
+    .expect("should request peers for the uncovered column");
+
+    // A supernode joins the chain within the grace period, and the batch is requested.
+    let new_supernode = rig.new_connected_supernode_peer();
+    rig.send_sync_message(SyncMessage::AddPeer(
+        new_supernode,
+        remote_info,
+        fork_digest,
+    ));
+    let _ = rig.find_blocks_by_range_request(filter().epoch(0));
+}
+
+#[test]
+fn leaked_range_request_is_removed_and_batch_retried() {
+    let mut rig = TestRig::test_setup();
+
+    let _peer = rig.add_head_peer();
+    rig.assert_state(RangeSyncType::Head);
+    // The request never completes.
+    let _ = rig.find_blocks_by_range_request(filter().epoch(0));
+
+    rig.sync_manager
+        .prune_leaked_range_requests_older_than(Duration::ZERO);
+
+    // The batch is failed and requested again.
+    let _ = rig.find_blocks_by_range_request(filter().epoch(0));
+    rig.assert_state(RangeSyncType::Head);
+}
+
+#[test]
+fn peer_with_unexpected_fork_digest_change_is_re_evaluated() {
+    let mut rig = TestRig::test_setup();
+    let mut unknown_fork_digest = rig.local_fork_digest();
+    unknown_fork_digest[0] ^= 0xff;
+
+    // Get a peer with an advanced head
+    let head_peer = rig.add_head_peer_with_fork_digest(unknown_fork_digest);
+    rig.assert_state(RangeSyncType::Head);
+    let _ = rig.find_blocks_by_range_request(filter().peer(head_peer));
+
+    // The peer re-statuses on our fork digest, synced to our head. It is removed from range sync
+    // and the head chain had a single peer.
+    // Counters are shared with other tests, only assert that it moved.
+    let mismatches = || {
+        metrics::SYNC_PEER_FORK_DIGEST_MISMATCH
+            .as_ref()
+            .map_or(0, |counter| counter.get())
+    };
+    let before = mismatches();
+    let fork_digest = rig.local_fork_digest();
+    let local_info = rig.local_info();
+    rig.send_sync_message(SyncMessage::AddPeer(head_peer, local_info, fork_digest));
+    assert!(rig.sync_manager.range_sync_state().unwrap().is_none());
+    assert!(mismatches() > before);
+}
+
+#[test]
+fn peer_changing_fork_digest_at_fork_transition_is_kept() {
+    let mut rig = TestRig::test_setup();
+    let Some(previous_fork_digest) = rig.previous_fork_digest() else {
+        return;
+    };
+
+    // Get a peer with an advanced head
+    let head_peer = rig.add_head_peer_with_fork_digest(previous_fork_digest);
+    rig.assert_state(RangeSyncType::Head);
+    let _ = rig.find_blocks_by_range_request(filter().peer(head_peer));
+
+    // The peer re-statuses on our fork digest after the fork, it keeps serving the head chain.
+    let fork_digest = rig.local_fork_digest();
+    let local_info = rig.local_info();
+    rig.send_sync_message(SyncMessage::AddPeer(head_peer, local_info, fork_digest));
+    rig.assert_state(RangeSyncType::Head);
+}
+
+#[test]
+fn decision_log_records_range_sync() {
+    let mut rig = TestRig::test_setup();
+
+    let head_peer = rig.add_head_peer();
+    rig.assert_state(RangeSyncType::Head);
+
+    let (tx, mut rx) = tokio::sync::oneshot::channel();
+    rig.send_sync_message(SyncMessage::DumpDecisionLog {
+        reset: false,
+        sender: tx,
+    });
+    let decisions = rx
+        .try_recv()
+        .expect("should respond")
+        .into_iter()
+        .map(|entry| entry.decision)
+        .collect::<Vec<_>>();
+
+    assert!(matches!(
+        decisions.as_slice(),
+        [
+            SyncDecision::PeerSyncStatusChanged { peer_id, sync_status: "Advanced" },
+            SyncDecision::ChainAdded { sync_type: "Head", .. },
+        ] if *peer_id == head_peer
+    ));
+}
+
+#[test]
+#[cfg(not(feature = "disable-backfill"))]
+fn backfill_respects_max_concurrent_batches() {
+    let mut rig = TestRig::test_setup_with_network_config(NetworkConfig {
+        max_concurrent_backfill_batches: 2,
+        ..NetworkConfig::default()
+    });
+    let local_info = rig.local_info();
+    for _ in 0..4 {
+        rig.add_peer(local_info.clone());
+    }
+
+    // Pretend the node started from a checkpoint, leaving blocks to backfill.
+    let store = rig.harness.chain.store.clone();
+    let anchor = store.get_anchor_info();
+    store
+        .compare_and_set_anchor_info_with_write(
+            anchor.clone(),
+            AnchorInfo {
+                oldest_block_slot: Epoch::new(10).start_slot(E::slots_per_epoch()),
+                oldest_block_parent: Hash256::random(),
+                ..anchor
+            },
+        )
+        .unwrap();
+    rig.sync_manager.restart_backfill_sync();
+
+    // Forward sync is idle, so a new synced peer resumes backfill. Only two of the five idle peers
+    // are assigned a batch.
+    rig.add_peer(local_info);
+    assert!(matches!(
+        rig.network_globals.sync_state(),
+        SyncState::BackFillSyncing { .. }
+    ));
+    let mut requested_batches = 0;
+    while rig
+        .pop_received_network_event(|ev| match ev {
+            NetworkMessage::SendRequest {
+                request: RequestType::BlocksByRange(_),
+                ..
+            } => Some(()),
+            _ => None,
+        })
+        .is_ok()
+    {
+        requested_batches += 1;
+    }
+    assert_eq!(requested_batches, 2);
+}
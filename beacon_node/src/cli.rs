@@ -629,6 +629,30 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("metrics-disable-cors")
+                .long("metrics-disable-cors")
+                .requires("metrics")
+                .conflicts_with("metrics-allow-origin")
+                .help("Do not set CORS headers on responses from the Prometheus metrics HTTP \
+                    server. Use this when a reverse proxy in front of the server handles CORS.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("metrics-delta-mode")
+                .long("metrics-delta-mode")
+                .requires("metrics")
+                .help("Only serve the metric samples which have changed since the previous scrape \
+                    of the Prometheus metrics HTTP server. This output is non-standard and is \
+                    not suitable for multiple concurrent scrapers. \
+                    NOTE: this is an experimental flag and may change any time without notice!")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+                .hide(true)
+        )
         .arg(
             Arg::new("shuffling-cache-size")
             .long("shuffling-cache-size")
@@ -647,6 +671,65 @@ pub fn cli_app() -> Command {
                 .hide(true)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("custody-peers-grace-period")
+                .long("custody-peers-grace-period")
+                .value_name("SECONDS")
+                .help("How long range sync waits for peers serving all the sampling columns \
+                before failing a batch request.")
+                .action(ArgAction::Set)
+                .hide(true)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("max-parent-lookup-depth")
+                .long("max-parent-lookup-depth")
+                .value_name("N")
+                .help("The maximum length of a chain of parent block lookups. Longer chains are \
+                synced with range sync instead.")
+                .action(ArgAction::Set)
+                .hide(true)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("parent-lookup-denylist")
+                .long("parent-lookup-denylist")
+                .value_name("ROOTS")
+                .help("A comma-separated list of block roots which never trigger a lookup when \
+                received as the unknown parent of a block.")
+                .action(ArgAction::Set)
+                .hide(true)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("min-synced-peers-for-core-topics")
+                .long("min-synced-peers-for-core-topics")
+                .value_name("N")
+                .help("The minimum number of synced peers required to subscribe to the core gossip \
+                topics once synced.")
+                .action(ArgAction::Set)
+                .hide(true)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("max-concurrent-custody-requests")
+                .long("max-concurrent-custody-requests")
+                .value_name("N")
+                .help("The maximum number of custody column requests for lookups active at once.")
+                .action(ArgAction::Set)
+                .hide(true)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("max-concurrent-backfill-batches")
+                .long("max-concurrent-backfill-batches")
+                .value_name("N")
+                .help("The maximum number of backfill batches downloaded at once. Lower values \
+                reduce the bandwidth used by backfill sync.")
+                .action(ArgAction::Set)
+                .hide(true)
+                .display_order(0)
+        )
         /*
          * Monitoring metrics
          */
@@ -776,6 +859,27 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("deposit-verification-chunk-size")
+                .long("deposit-verification-chunk-size")
+                .value_name("N")
+                .help("The number of deposits whose merkle proofs are verified by each parallel \
+                       task during block processing. Larger values bound the number of tasks \
+                       spawned for blocks with many deposits. Must be greater than zero")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("persist-observed-sidecars")
+                .long("persist-observed-sidecars")
+                .help("Persist the caches of blob and data column sidecars observed on gossip on \
+                       shutdown and reload them on startup. This prevents duplicate sidecars \
+                       from being briefly accepted after a restart during periods of \
+                       non-finality.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
         .arg(
             Arg::new("block-cache-size")
                 .long("block-cache-size")
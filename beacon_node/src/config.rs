@@ -229,6 +229,14 @@ pub fn get_config<E: EthSpec>(
         client_config.http_metrics.allow_origin = Some(allow_origin.to_string());
     }
 
+    if cli_args.get_flag("metrics-disable-cors") {
+        client_config.http_metrics.cors_enabled = false;
+    }
+
+    if cli_args.get_flag("metrics-delta-mode") {
+        client_config.http_metrics.delta_mode = true;
+    }
+
     /*
      * Explorer metrics
      */
@@ -446,6 +454,14 @@ pub fn get_config<E: EthSpec>(
         client_config.chain.epochs_per_migration = epochs_per_migration;
     }
 
+    if let Some(chunk_size) =
+        clap_utils::parse_optional(cli_args, "deposit-verification-chunk-size")?
+    {
+        client_config.chain.deposit_verification_chunk_size = chunk_size;
+    }
+
+    client_config.chain.persist_observed_sidecars = cli_args.get_flag("persist-observed-sidecars");
+
     if let Some(prune_blobs) = clap_utils::parse_optional(cli_args, "prune-blobs")? {
         client_config.store.prune_blobs = prune_blobs;
     }
@@ -1470,6 +1486,46 @@ pub fn set_network_config(
             })?;
     }
 
+    if let Some(seconds) = clap_utils::parse_optional(cli_args, "custody-peers-grace-period")? {
+        config.custody_peers_grace_period = Duration::from_secs(seconds);
+    }
+
+    if let Some(depth) = clap_utils::parse_optional(cli_args, "max-parent-lookup-depth")? {
+        config.max_parent_lookup_depth = depth;
+    }
+
+    if let Some(roots) = cli_args.get_one::<String>("parent-lookup-denylist") {
+        config.parent_lookup_denylist = roots
+            .split(',')
+            .map(Hash256::from_str)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Invalid --parent-lookup-denylist value: {:?}", e))?;
+    }
+
+    if let Some(min_synced_peers) =
+        clap_utils::parse_optional(cli_args, "min-synced-peers-for-core-topics")?
+    {
+        config.min_synced_peers_for_core_topics = min_synced_peers;
+    }
+
+    if let Some(max_requests) =
+        clap_utils::parse_optional(cli_args, "max-concurrent-custody-requests")?
+    {
+        if max_requests == 0 {
+            return Err("--max-concurrent-custody-requests must be a non-zero value".to_string());
+        }
+        config.max_concurrent_custody_requests = max_requests;
+    }
+
+    if let Some(max_batches) =
+        clap_utils::parse_optional(cli_args, "max-concurrent-backfill-batches")?
+    {
+        if max_batches == 0 {
+            return Err("--max-concurrent-backfill-batches must be a non-zero value".to_string());
+        }
+        config.max_concurrent_backfill_batches = max_batches;
+    }
+
     Ok(())
 }
 
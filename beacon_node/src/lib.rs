@@ -1,5 +1,6 @@
 mod cli;
 mod config;
+mod metrics;
 
 pub use beacon_chain;
 use beacon_chain::{
@@ -103,7 +104,7 @@ impl<E: EthSpec> ProductionBeaconNode<E> {
             )?;
 
         let builder = if let Some(mut slasher_config) = client_config.slasher.clone() {
-            match slasher_config.override_backend() {
+            let outcome = match slasher_config.override_backend() {
                 DatabaseBackendOverride::Success(old_backend) => {
                     info!(
                         log,
@@ -112,6 +113,7 @@ impl<E: EthSpec> ProductionBeaconNode<E> {
                         "configured_backend" => %old_backend,
                         "override_backend" => %slasher_config.backend,
                     );
+                    "success"
                 }
                 DatabaseBackendOverride::Failure(path) => {
                     warn!(
@@ -120,9 +122,12 @@ impl<E: EthSpec> ProductionBeaconNode<E> {
                         "advice" => "delete old MDBX database or enable MDBX backend",
                         "path" => path.display()
                     );
+                    "failure"
                 }
-                _ => {}
-            }
+                DatabaseBackendOverride::Noop => "none",
+            };
+            metrics::inc_counter_vec(&metrics::SLASHER_BACKEND_OVERRIDE, &[outcome]);
+
             let slasher = Arc::new(
                 Slasher::open(
                     slasher_config,
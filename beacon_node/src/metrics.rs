@@ -0,0 +1,13 @@
+pub use metrics::*;
+use std::sync::LazyLock;
+
+/*
+ * Slasher
+ */
+pub static SLASHER_BACKEND_OVERRIDE: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "beacon_node_slasher_backend_override_total",
+        "Count of slasher database backend overrides at startup by outcome",
+        &["outcome"],
+    )
+});
@@ -1,4 +1,7 @@
 use ssz_derive::{Decode, Encode};
+use state_processing::per_block_processing::process_operations::{
+    ConsolidationRequestOutcome, DepositOutcome,
+};
 use state_processing::ConsensusContext;
 use std::collections::HashMap;
 use types::{EthSpec, Hash256, IndexedAttestation, Slot};
@@ -22,6 +25,17 @@ pub struct OnDiskConsensusContext<E: EthSpec> {
     /// They are not part of the on-disk format.
     #[ssz(skip_serializing, skip_deserializing)]
     indexed_attestations: HashMap<Hash256, IndexedAttestation<E>>,
+    /// Outcomes of the deposits applied during block processing.
+    ///
+    /// Like the indexed attestations, they are only kept in memory.
+    #[ssz(skip_serializing, skip_deserializing)]
+    deposit_outcomes: Vec<DepositOutcome>,
+    /// Outcomes of the consolidation requests applied during block processing.
+    #[ssz(skip_serializing, skip_deserializing)]
+    consolidation_request_outcomes: Vec<ConsolidationRequestOutcome>,
+    /// Number of signatures of each operation type included for verification with the block.
+    #[ssz(skip_serializing, skip_deserializing)]
+    operation_signature_counts: Vec<(&'static str, u64)>,
 }
 
 impl<E: EthSpec> OnDiskConsensusContext<E> {
@@ -35,12 +49,18 @@ impl<E: EthSpec> OnDiskConsensusContext<E> {
             proposer_index,
             current_block_root,
             indexed_attestations,
+            deposit_outcomes,
+            consolidation_request_outcomes,
+            operation_signature_counts,
         } = ctxt;
         OnDiskConsensusContext {
             slot,
             proposer_index,
             current_block_root,
             indexed_attestations,
+            deposit_outcomes,
+            consolidation_request_outcomes,
+            operation_signature_counts,
         }
     }
 
@@ -50,6 +70,9 @@ impl<E: EthSpec> OnDiskConsensusContext<E> {
             proposer_index,
             current_block_root,
             indexed_attestations,
+            deposit_outcomes,
+            consolidation_request_outcomes,
+            operation_signature_counts,
         } = self;
 
         let mut ctxt = ConsensusContext::new(slot);
@@ -60,6 +83,9 @@ impl<E: EthSpec> OnDiskConsensusContext<E> {
         if let Some(block_root) = current_block_root {
             ctxt = ctxt.set_current_block_root(block_root);
         }
+        ctxt.deposit_outcomes = deposit_outcomes;
+        ctxt.consolidation_request_outcomes = consolidation_request_outcomes;
+        ctxt.operation_signature_counts = operation_signature_counts;
         ctxt.set_indexed_attestations(indexed_attestations)
     }
 }
@@ -303,6 +303,9 @@ pub enum DBColumn {
     OpPool,
     #[strum(serialize = "etc")]
     Eth1Cache,
+    /// For persisting the observed gossip sidecar caches across restarts.
+    #[strum(serialize = "obs")]
+    ObservedSidecars,
     #[strum(serialize = "frk")]
     ForkChoice,
     #[strum(serialize = "pkc")]
@@ -396,6 +399,7 @@ impl DBColumn {
             | Self::BeaconChain
             | Self::OpPool
             | Self::Eth1Cache
+            | Self::ObservedSidecars
             | Self::ForkChoice
             | Self::PubkeyCache
             | Self::BeaconRestorePoint
@@ -4,8 +4,9 @@ use beacon_chain::StateSkipConfig;
 use node_test_rig::{
     environment::{Environment, EnvironmentBuilder},
     eth2::types::StateId,
-    testing_client_config, LocalBeaconNode,
+    testing_client_config, ClientGenesis, LocalBeaconNode,
 };
+use std::sync::Arc;
 use types::{EthSpec, MinimalEthSpec, Slot};
 
 fn env_builder() -> EnvironmentBuilder<MinimalEthSpec> {
@@ -60,3 +61,53 @@ fn http_server_genesis_state() {
 
     env.fire_signal();
 }
+
+#[test]
+fn start_from_genesis_state() {
+    let mut env = env_builder()
+        .test_logger()
+        .expect("should build env logger")
+        .multi_threaded_tokio_runtime()
+        .expect("should start tokio runtime")
+        .build()
+        .expect("environment should build");
+
+    let node = build_node(&mut env);
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+    let mut genesis_state = chain
+        .state_at_slot(Slot::new(0), StateSkipConfig::WithStateRoots)
+        .expect("should find genesis state");
+    genesis_state.drop_all_caches().unwrap();
+
+    // Start a second node from the genesis state of the first, with a different interop genesis in
+    // its config which must be overridden.
+    let mut client_config = testing_client_config();
+    client_config.genesis = ClientGenesis::Interop {
+        validator_count: 4,
+        genesis_time: 0,
+    };
+    let context = env.core_context();
+    let other_node = env
+        .runtime()
+        .block_on(LocalBeaconNode::production_with_genesis_state(
+            context,
+            client_config,
+            Some(Arc::new(genesis_state)),
+        ))
+        .expect("should start node from genesis state");
+    let other_chain = other_node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    assert_eq!(other_chain.genesis_block_root, chain.genesis_block_root);
+    assert_eq!(
+        other_chain.genesis_validators_root,
+        chain.genesis_validators_root
+    );
+
+    env.fire_signal();
+}
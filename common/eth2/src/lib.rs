@@ -20,7 +20,7 @@ use derivative::Derivative;
 use either::Either;
 use futures::Stream;
 use futures_util::StreamExt;
-use lighthouse_network::PeerId;
+use lighthouse_network::{Eth2Enr, PeerId};
 use pretty_reqwest_error::PrettyReqwestError;
 pub use reqwest;
 use reqwest::{
@@ -35,7 +35,8 @@ use ssz::Encode;
 use std::fmt;
 use std::future::Future;
 use std::path::PathBuf;
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use store::fork_versioned_response::ExecutionOptimisticFinalizedForkVersionedResponse;
 
 pub const V1: EndpointVersion = EndpointVersion(1);
@@ -85,6 +86,10 @@ pub enum Error {
     NoServerPubkey,
     /// The client has been configured without an API token, but requires one for this request.
     NoToken,
+    /// The server does not know the requested validator.
+    UnknownValidator(ValidatorId),
+    /// The server returned an ENR without a valid `eth2` field.
+    InvalidEnr(String),
 }
 
 impl From<reqwest::Error> for Error {
@@ -118,6 +123,8 @@ impl Error {
             Error::InvalidHeaders(_) => None,
             Error::TokenReadError(..) => None,
             Error::NoServerPubkey | Error::NoToken => None,
+            Error::UnknownValidator(_) => None,
+            Error::InvalidEnr(_) => None,
         }
     }
 }
@@ -165,15 +172,21 @@ impl Timeouts {
     }
 }
 
+/// A callback invoked after each request to a beacon node with the (redacted) URL of the beacon
+/// node, the HTTP method and the round-trip duration of the request.
+pub type RequestObserver = Arc<dyn Fn(&str, &str, Duration) + Send + Sync>;
+
 /// A wrapper around `reqwest::Client` which provides convenience methods for interfacing with a
 /// Lighthouse Beacon Node HTTP server (`http_api`).
-#[derive(Clone, Debug, Derivative)]
-#[derivative(PartialEq)]
+#[derive(Clone, Derivative)]
+#[derivative(Debug, PartialEq)]
 pub struct BeaconNodeHttpClient {
     #[derivative(PartialEq = "ignore")]
     client: reqwest::Client,
     server: SensitiveUrl,
     timeouts: Timeouts,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    request_observer: Option<RequestObserver>,
 }
 
 impl Eq for BeaconNodeHttpClient {}
@@ -196,6 +209,7 @@ impl BeaconNodeHttpClient {
             client: reqwest::Client::new(),
             server,
             timeouts,
+            request_observer: None,
         }
     }
 
@@ -208,9 +222,16 @@ impl BeaconNodeHttpClient {
             client,
             server,
             timeouts,
+            request_observer: None,
         }
     }
 
+    /// Report the round-trip duration of every request sent by this client to `observer`.
+    pub fn with_request_observer(mut self, observer: RequestObserver) -> Self {
+        self.request_observer = Some(observer);
+        self
+    }
+
     /// Return the path with the standard `/eth/vX` prefix applied.
     fn eth_path(&self, version: EndpointVersion) -> Result<Url, Error> {
         let mut path = self.server.full.clone();
@@ -223,6 +244,16 @@ impl BeaconNodeHttpClient {
         Ok(path)
     }
 
+    /// Send the request in `builder`, reporting its duration to the request observer, if any.
+    async fn send(&self, method: &str, builder: RequestBuilder) -> Result<Response, Error> {
+        let start = Instant::now();
+        let result = builder.send().await;
+        if let Some(observer) = &self.request_observer {
+            observer(self.server.as_ref(), method, start.elapsed());
+        }
+        Ok(result?)
+    }
+
     /// Perform a HTTP GET request.
     async fn get<T: DeserializeOwned, U: IntoUrl>(&self, url: U) -> Result<T, Error> {
         let response = self.get_response(url, |b| b).await?;
@@ -235,7 +266,7 @@ impl BeaconNodeHttpClient {
         url: U,
         builder: impl FnOnce(RequestBuilder) -> RequestBuilder,
     ) -> Result<Response, Error> {
-        let response = builder(self.client.get(url)).send().await?;
+        let response = self.send("GET", builder(self.client.get(url))).await?;
         ok_or_error(response).await
     }
 
@@ -404,7 +435,7 @@ impl BeaconNodeHttpClient {
             builder = builder.timeout(timeout);
         }
 
-        let response = builder.json(body).send().await?;
+        let response = self.send("POST", builder.json(body)).await?;
         ok_or_error(response).await
     }
 
@@ -420,10 +451,13 @@ impl BeaconNodeHttpClient {
         if let Some(timeout) = timeout {
             builder = builder.timeout(timeout);
         }
-        let response = builder
-            .header(CONSENSUS_VERSION_HEADER, fork.to_string())
-            .json(body)
-            .send()
+        let response = self
+            .send(
+                "POST",
+                builder
+                    .header(CONSENSUS_VERSION_HEADER, fork.to_string())
+                    .json(body),
+            )
             .await?;
         ok_or_error(response).await
     }
@@ -441,7 +475,9 @@ impl BeaconNodeHttpClient {
             "Content-Type",
             HeaderValue::from_static("application/octet-stream"),
         );
-        let response = builder.headers(headers).json(body).send().await?;
+        let response = self
+            .send("POST", builder.headers(headers).json(body))
+            .await?;
         ok_or_error(response).await
     }
 
@@ -466,7 +502,9 @@ impl BeaconNodeHttpClient {
             "Content-Type",
             HeaderValue::from_static("application/octet-stream"),
         );
-        let response = builder.headers(headers).body(body).send().await?;
+        let response = self
+            .send("POST", builder.headers(headers).body(body))
+            .await?;
         ok_or_error(response).await
     }
 
@@ -1788,6 +1826,16 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// Fetches the ENR of the node from `GET node/identity` and returns the `EnrForkId` of its
+    /// `eth2` field, which identifies the network and fork the node is on.
+    pub async fn get_enr_fork_id(&self) -> Result<EnrForkId, Error> {
+        let identity = self.get_node_identity().await?.data;
+        identity
+            .enr
+            .eth2()
+            .map_err(|e| Error::InvalidEnr(e.to_string()))
+    }
+
     /// `GET node/syncing`
     pub async fn get_node_syncing(&self) -> Result<GenericResponse<SyncingData>, Error> {
         let mut path = self.eth_path(V1)?;
@@ -1809,7 +1857,7 @@ impl BeaconNodeHttpClient {
             .push("node")
             .push("health");
 
-        let status = self.client.get(path).send().await?.status();
+        let status = self.send("GET", self.client.get(path)).await?.status();
         if status == StatusCode::OK || status == StatusCode::PARTIAL_CONTENT {
             Ok(status)
         } else {
@@ -5,18 +5,22 @@ pub mod attestation_rewards;
 mod block_packing_efficiency;
 mod block_rewards;
 mod standard_block_rewards;
+mod sync_committee_performance;
 mod sync_committee_rewards;
 
 use crate::{
     types::{
-        DepositTreeSnapshot, Epoch, EthSpec, FinalizedExecutionBlock, GenericResponse, ValidatorId,
+        DepositTreeSnapshot, Epoch, EthSpec, FinalizedExecutionBlock, GenericResponse,
+        PendingConsolidation, PendingDeposit, PendingPartialWithdrawal, StateId, ValidatorId,
     },
     BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, Slot,
 };
+use lighthouse_network::PeerId;
 use proto_array::core::ProtoArray;
 use serde::{Deserialize, Serialize};
 use ssz::four_byte_option_impl;
 use ssz_derive::{Decode, Encode};
+use std::collections::HashMap;
 use store::{AnchorInfo, BlobInfo, Split, StoreConfig};
 
 pub use attestation_performance::{
@@ -26,9 +30,17 @@ pub use attestation_rewards::StandardAttestationRewards;
 pub use block_packing_efficiency::{
     BlockPackingEfficiency, BlockPackingEfficiencyQuery, ProposerInfo, UniqueAttestation,
 };
-pub use block_rewards::{AttestationRewards, BlockReward, BlockRewardMeta, BlockRewardsQuery};
-pub use lighthouse_network::{types::SyncState, PeerInfo};
+pub use block_rewards::{
+    AttestationRewardBreakdown, AttestationRewards, BlockReward, BlockRewardMeta, BlockRewardsQuery,
+};
+pub use lighthouse_network::{
+    types::{SyncStallReason, SyncState},
+    PeerInfo,
+};
 pub use standard_block_rewards::StandardBlockReward;
+pub use sync_committee_performance::{
+    SyncCommitteePerformance, SyncCommitteePerformanceQuery, SyncCommitteePerformanceStatistics,
+};
 pub use sync_committee_rewards::SyncCommitteeReward;
 
 // Define "legacy" implementations of `Option<T>` which use four bytes for encoding the union
@@ -88,6 +100,35 @@ pub struct ValidatorInclusionData {
     pub is_previous_epoch_head_attester: bool,
 }
 
+/// The request body of `POST lighthouse/ui/validator_metrics`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorMetricsRequestData {
+    pub indices: Vec<u64>,
+}
+
+/// The performance of a validator as observed by the validator monitor of the beacon node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorMetrics {
+    pub attestation_hits: u64,
+    pub attestation_misses: u64,
+    pub attestation_hit_percentage: f64,
+    pub attestation_head_hits: u64,
+    pub attestation_head_misses: u64,
+    pub attestation_head_hit_percentage: f64,
+    pub attestation_target_hits: u64,
+    pub attestation_target_misses: u64,
+    pub attestation_target_hit_percentage: f64,
+    /// The inclusion distance of the latest attestation included on chain, or 0 if none has
+    /// been observed yet.
+    pub latest_attestation_inclusion_distance: u64,
+}
+
+/// The response of `POST lighthouse/ui/validator_metrics`, keyed by validator index.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorMetricsResponse {
+    pub validators: HashMap<String, ValidatorMetrics>,
+}
+
 /// Reports on the health of the Lighthouse instance.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Health {
@@ -187,6 +228,30 @@ pub struct Eth1SyncStatusData {
     pub lighthouse_is_cached_and_ready: bool,
 }
 
+impl Eth1SyncStatusData {
+    /// Returns `true` if the eth1 data is more than `threshold_secs` out of date.
+    ///
+    /// The data is out of date if either the latest cached block is more than `threshold_secs`
+    /// behind the voting target, or the eth1 node's head block is more than `threshold_secs`
+    /// older than `now` (seconds since the UNIX epoch). Unknown blocks are always considered
+    /// stale.
+    pub fn is_eth1_stale(&self, now: u64, threshold_secs: u64) -> bool {
+        let cache_lag = self
+            .latest_cached_block_timestamp
+            .map(|timestamp| self.voting_target_timestamp.saturating_sub(timestamp));
+        let head_age = self
+            .head_block_timestamp
+            .map(|timestamp| now.saturating_sub(timestamp));
+
+        match (cache_lag, head_age) {
+            (Some(cache_lag), Some(head_age)) => {
+                cache_lag > threshold_secs || head_age > threshold_secs
+            }
+            _ => true,
+        }
+    }
+}
+
 /// A fully parsed eth1 deposit contract log.
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct DepositLog {
@@ -241,6 +306,163 @@ pub struct DatabaseInfo {
     pub split: Split,
     pub anchor: AnchorInfo,
     pub blob_info: BlobInfo,
+    /// The database backend of the slasher, if it is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slasher_backend: Option<String>,
+}
+
+/// Request body for `POST lighthouse/database/reconstruct_range`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateReconstructionRequest {
+    pub start_slot: Slot,
+    pub end_slot: Slot,
+}
+
+/// A reconstruction of historic states started by `POST lighthouse/database/reconstruct_range`.
+///
+/// Progress can be polled via `GET lighthouse/database/info`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateReconstructionJob {
+    pub start_slot: Slot,
+    pub end_slot: Slot,
+}
+
+/// The indices of the data sidecars observed on gossip for a block proposal.
+///
+/// This reflects the gossip observations of the node since the last finalized slot, not the
+/// sidecars stored in its database.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObservedSidecars {
+    pub slot: Slot,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub proposer_index: u64,
+    #[serde(with = "serde_utils::quoted_u64_vec")]
+    pub indices: Vec<u64>,
+}
+
+/// The length and total balance of one of the pending queues of an Electra state.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PendingQueue {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub length: u64,
+    /// The sum of the amounts in the queue, in Gwei.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub total_gwei: u64,
+    /// The estimated number of epochs to process the whole queue at the churn of the state, or
+    /// `None` if the queue can't currently make progress.
+    pub estimated_epochs: Option<u64>,
+}
+
+/// The pending queues of an Electra state, as returned by `lighthouse/states/{state_id}/pending_queues`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PendingQueues {
+    pub pending_deposits: PendingQueue,
+    pub pending_partial_withdrawals: PendingQueue,
+    pub pending_consolidations: PendingQueue,
+}
+
+/// The activation queue of a state, as returned by `lighthouse/states/{state_id}/activation_queue`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActivationQueue {
+    /// The number of validators eligible for activation which have not been activated yet.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub length: u64,
+    /// The place of the requested validator in the queue, or `None` if no validator was requested
+    /// or it isn't in the queue.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validator: Option<ActivationQueuePosition>,
+}
+
+/// The place of a validator in the activation queue.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ActivationQueuePosition {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    /// The number of validators ahead of this one in the queue.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub position: u64,
+    /// The estimated activation epoch at the activation churn of the state, assuming the chain
+    /// keeps finalizing, or `None` if the queue can't currently make progress.
+    pub estimated_activation_epoch: Option<Epoch>,
+}
+
+/// Query parameters for the `lighthouse/states/{state_id}/activation_queue` endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActivationQueueQuery {
+    pub validator_id: Option<ValidatorId>,
+}
+
+/// The response of `lighthouse/syncing`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncingResponse {
+    pub data: SyncState,
+    /// Why sync is not making progress, if it isn't.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stall_reason: Option<SyncStallReason>,
+}
+
+/// The sync state of the node alongside its peer counts, as returned by `lighthouse/sync/health`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncHealth {
+    pub sync_state: SyncState,
+    pub connected_peers: usize,
+    /// Connected peers that are synced with us or advanced, i.e. useful to sync from.
+    pub synced_peers: usize,
+    /// Connected peers whose chain is ahead of ours.
+    pub advanced_peers: usize,
+}
+
+/// A significant decision recently taken by sync, as returned by `lighthouse/sync/decisions`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncDecision {
+    /// Milliseconds since the UNIX epoch.
+    pub timestamp_ms: u64,
+    pub decision: String,
+}
+
+/// Where the custody group count of a peer was learned from.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CustodyGroupCountSource {
+    Metadata,
+    Enr,
+}
+
+/// The data columns a node attributes to a peer, as returned by
+/// `lighthouse/peers/{peer_id}/custody`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerCustody {
+    pub peer_id: String,
+    /// The custody group count advertised by the peer, `None` if it has not advertised one.
+    pub custody_group_count: Option<u64>,
+    pub custody_group_count_source: Option<CustodyGroupCountSource>,
+    /// The columns the node considers the peer custodies, in ascending order. Only these columns
+    /// are requested from the peer.
+    pub custody_columns: Vec<u64>,
+}
+
+/// An active sync lookup, as returned by `lighthouse/sync/lookups/{block_root}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncLookup {
+    pub id: u32,
+    pub block_root: Hash256,
+    pub awaiting_parent: Option<Hash256>,
+    /// Number of lookups awaiting this lookup as their parent.
+    pub child_lookups: usize,
+    pub peer_count: usize,
+    pub block_request_state: String,
+    pub component_requests_state: String,
+}
+
+/// Sync's view on the execution engine, as returned by `lighthouse/sync/engine_state`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncEngineState {
+    /// Whether sync considers the execution engine online. Lookups and range sync are paused while
+    /// it is offline.
+    pub sync_engine_online: bool,
+    /// Whether the execution engine is actually online, `None` if no execution engine is
+    /// configured.
+    pub execution_engine_online: Option<bool>,
 }
 
 impl BeaconNodeHttpClient {
@@ -257,7 +479,7 @@ impl BeaconNodeHttpClient {
     }
 
     /// `GET lighthouse/syncing`
-    pub async fn get_lighthouse_syncing(&self) -> Result<GenericResponse<SyncState>, Error> {
+    pub async fn get_lighthouse_syncing(&self) -> Result<SyncingResponse, Error> {
         let mut path = self.server.full.clone();
 
         path.path_segments_mut()
@@ -268,6 +490,19 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/sync/health`
+    pub async fn get_lighthouse_sync_health(&self) -> Result<GenericResponse<SyncHealth>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("sync")
+            .push("health");
+
+        self.get(path).await
+    }
+
     /*
      * Note:
      *
@@ -323,6 +558,42 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// Returns `true` if the validator has been slashed, according to
+    /// `GET lighthouse/validator_inclusion/{epoch}/{validator_id}`.
+    ///
+    /// Returns `Error::UnknownValidator` if the validator is not known to the server.
+    pub async fn is_validator_slashed(
+        &self,
+        epoch: Epoch,
+        validator_id: ValidatorId,
+    ) -> Result<bool, Error> {
+        self.get_lighthouse_validator_inclusion(epoch, validator_id.clone())
+            .await?
+            .data
+            .map(|inclusion_data| inclusion_data.is_slashed)
+            .ok_or(Error::UnknownValidator(validator_id))
+    }
+
+    /// `POST lighthouse/ui/validator_metrics`
+    ///
+    /// Only validators monitored by the validator monitor of the server are included in the
+    /// response.
+    pub async fn post_lighthouse_ui_validator_metrics(
+        &self,
+        indices: Vec<u64>,
+    ) -> Result<GenericResponse<ValidatorMetricsResponse>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("ui")
+            .push("validator_metrics");
+
+        self.post_with_response(path, &ValidatorMetricsRequestData { indices })
+            .await
+    }
+
     /// `GET lighthouse/eth1/syncing`
     pub async fn get_lighthouse_eth1_syncing(
         &self,
@@ -406,6 +677,257 @@ impl BeaconNodeHttpClient {
         self.post_with_response(path, &()).await
     }
 
+    /// `POST lighthouse/database/reconstruct_range`
+    pub async fn post_lighthouse_database_reconstruct_range(
+        &self,
+        start_slot: Slot,
+        end_slot: Slot,
+    ) -> Result<GenericResponse<StateReconstructionJob>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("reconstruct_range");
+
+        self.post_with_response(
+            path,
+            &StateReconstructionRequest {
+                start_slot,
+                end_slot,
+            },
+        )
+        .await
+    }
+
+    /// `GET lighthouse/observed/blob_sidecars`
+    pub async fn get_lighthouse_observed_blob_sidecars(
+        &self,
+    ) -> Result<GenericResponse<Vec<ObservedSidecars>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("observed")
+            .push("blob_sidecars");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/observed/data_columns`
+    pub async fn get_lighthouse_observed_data_columns(
+        &self,
+    ) -> Result<GenericResponse<Vec<ObservedSidecars>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("observed")
+            .push("data_columns");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/sync/decisions`
+    pub async fn get_lighthouse_sync_decisions(
+        &self,
+    ) -> Result<GenericResponse<Vec<SyncDecision>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("sync")
+            .push("decisions");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/sync/decisions/reset`
+    ///
+    /// Returns the recent sync decisions and clears them, such that subsequent calls only return
+    /// decisions taken afterwards.
+    pub async fn post_lighthouse_sync_decisions_reset(
+        &self,
+    ) -> Result<GenericResponse<Vec<SyncDecision>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("sync")
+            .push("decisions")
+            .push("reset");
+
+        self.post_with_response(path, &()).await
+    }
+
+    /// `POST lighthouse/sync/parent_lookup_denylist`
+    ///
+    /// Adds `roots` to the parent lookup denylist of sync, returning the full denylist.
+    pub async fn post_lighthouse_sync_parent_lookup_denylist(
+        &self,
+        roots: &[Hash256],
+    ) -> Result<GenericResponse<Vec<Hash256>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("sync")
+            .push("parent_lookup_denylist");
+
+        self.post_with_response(path, &roots).await
+    }
+
+    /// `GET lighthouse/peers/{peer_id}/custody`
+    pub async fn get_lighthouse_peer_custody(
+        &self,
+        peer_id: PeerId,
+    ) -> Result<GenericResponse<PeerCustody>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push(&peer_id.to_string())
+            .push("custody");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/sync/lookups/{block_root}`
+    ///
+    /// Returns `None` if sync is not looking up `block_root`.
+    pub async fn get_lighthouse_sync_lookup(
+        &self,
+        block_root: Hash256,
+    ) -> Result<GenericResponse<Option<SyncLookup>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("sync")
+            .push("lookups")
+            .push(&format!("{block_root:?}"));
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/sync/engine_state`
+    pub async fn get_lighthouse_sync_engine_state(
+        &self,
+    ) -> Result<GenericResponse<SyncEngineState>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("sync")
+            .push("engine_state");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/states/{state_id}/pending_queues`
+    pub async fn get_lighthouse_pending_queues(
+        &self,
+        state_id: StateId,
+    ) -> Result<GenericResponse<PendingQueues>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("states")
+            .push(&state_id.to_string())
+            .push("pending_queues");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/states/{state_id}/activation_queue?validator_id`
+    ///
+    /// Returns the length of the activation queue and, if `validator_id` is provided, the place of
+    /// that validator in it.
+    pub async fn get_lighthouse_activation_queue(
+        &self,
+        state_id: StateId,
+        validator_id: Option<ValidatorId>,
+    ) -> Result<GenericResponse<ActivationQueue>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("states")
+            .push(&state_id.to_string())
+            .push("activation_queue");
+
+        if let Some(validator_id) = validator_id {
+            path.query_pairs_mut()
+                .append_pair("validator_id", &validator_id.to_string());
+        }
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/states/{state_id}/pending_deposits`
+    pub async fn get_lighthouse_pending_deposits(
+        &self,
+        state_id: StateId,
+    ) -> Result<GenericResponse<Vec<PendingDeposit>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("states")
+            .push(&state_id.to_string())
+            .push("pending_deposits");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/states/{state_id}/pending_partial_withdrawals`
+    pub async fn get_lighthouse_pending_partial_withdrawals(
+        &self,
+        state_id: StateId,
+    ) -> Result<GenericResponse<Vec<PendingPartialWithdrawal>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("states")
+            .push(&state_id.to_string())
+            .push("pending_partial_withdrawals");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/states/{state_id}/pending_consolidations`
+    pub async fn get_lighthouse_pending_consolidations(
+        &self,
+        state_id: StateId,
+    ) -> Result<GenericResponse<Vec<PendingConsolidation>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("states")
+            .push(&state_id.to_string())
+            .push("pending_consolidations");
+
+        self.get(path).await
+    }
+
     /*
      Analysis endpoints.
     */
@@ -431,6 +953,31 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET` lighthouse/analysis/block_rewards?start_slot,end_slot,include_attestation_breakdown
+    ///
+    /// As per `get_lighthouse_analysis_block_rewards`, but also attributes each attestation's
+    /// reward to the individual attesters. The response is considerably larger.
+    pub async fn get_lighthouse_analysis_block_rewards_with_breakdown(
+        &self,
+        start_slot: Slot,
+        end_slot: Slot,
+    ) -> Result<Vec<BlockReward>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("analysis")
+            .push("block_rewards");
+
+        path.query_pairs_mut()
+            .append_pair("start_slot", &start_slot.to_string())
+            .append_pair("end_slot", &end_slot.to_string())
+            .append_pair("include_attestation_breakdown", "true");
+
+        self.get(path).await
+    }
+
     /// `GET` lighthouse/analysis/block_packing?start_epoch,end_epoch
     pub async fn get_lighthouse_analysis_block_packing(
         &self,
@@ -452,6 +999,22 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET` lighthouse/analysis/block_packing?start_epoch,end_epoch
+    ///
+    /// Returns the packing efficiency of the blocks proposed by `proposer_index` only.
+    pub async fn get_lighthouse_analysis_block_packing_for_proposer(
+        &self,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+        proposer_index: u64,
+    ) -> Result<Vec<BlockPackingEfficiency>, Error> {
+        let mut block_packing = self
+            .get_lighthouse_analysis_block_packing(start_epoch, end_epoch)
+            .await?;
+        block_packing.retain(|block| block.proposer_info.validator_index == proposer_index);
+        Ok(block_packing)
+    }
+
     /// `GET` lighthouse/analysis/attestation_performance/{index}?start_epoch,end_epoch
     pub async fn get_lighthouse_analysis_attestation_performance(
         &self,
@@ -474,4 +1037,63 @@ impl BeaconNodeHttpClient {
 
         self.get(path).await
     }
+
+    /// `GET` lighthouse/analysis/sync_committee_performance/{targets}?start_epoch,end_epoch
+    ///
+    /// `targets` is either `global` or a comma-separated list of validator indices.
+    pub async fn get_lighthouse_analysis_sync_committee_performance(
+        &self,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+        targets: String,
+    ) -> Result<Vec<SyncCommitteePerformance>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("analysis")
+            .push("sync_committee_performance")
+            .push(&targets);
+
+        path.query_pairs_mut()
+            .append_pair("start_epoch", &start_epoch.to_string())
+            .append_pair("end_epoch", &end_epoch.to_string());
+
+        self.get(path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eth1_sync_status(
+        head_block_timestamp: Option<u64>,
+        latest_cached_block_timestamp: Option<u64>,
+        voting_target_timestamp: u64,
+    ) -> Eth1SyncStatusData {
+        Eth1SyncStatusData {
+            head_block_number: head_block_timestamp.map(|_| 100),
+            head_block_timestamp,
+            latest_cached_block_number: latest_cached_block_timestamp.map(|_| 90),
+            latest_cached_block_timestamp,
+            voting_target_timestamp,
+            eth1_node_sync_status_percentage: 100.0,
+            lighthouse_is_cached_and_ready: false,
+        }
+    }
+
+    #[test]
+    fn eth1_staleness() {
+        let now = 10_000;
+        let threshold = 100;
+
+        assert!(!eth1_sync_status(Some(now), Some(1_000), 1_000).is_eth1_stale(now, threshold));
+        assert!(!eth1_sync_status(Some(now - 100), Some(900), 1_000).is_eth1_stale(now, threshold));
+        assert!(eth1_sync_status(Some(now), Some(899), 1_000).is_eth1_stale(now, threshold));
+        assert!(eth1_sync_status(Some(now - 101), Some(1_000), 1_000).is_eth1_stale(now, threshold));
+        assert!(eth1_sync_status(None, Some(1_000), 1_000).is_eth1_stale(now, threshold));
+        assert!(eth1_sync_status(Some(now), None, 1_000).is_eth1_stale(now, threshold));
+    }
 }
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::io::{self, Write};
 use types::Epoch;
 
 #[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
@@ -29,6 +30,37 @@ impl AttestationPerformance {
         }
         vec
     }
+
+    /// Writes `performances` as CSV to `writer`, one row per validator and epoch in ascending
+    /// order. A missing `delay` is written as an empty field.
+    ///
+    /// Rows are written as they are produced, so large results can be streamed to disk by passing
+    /// a buffered file as `writer`.
+    pub fn write_csv<W: Write>(performances: &[Self], mut writer: W) -> io::Result<()> {
+        writeln!(writer, "index,epoch,active,head,target,source,delay")?;
+        for performance in performances {
+            let mut epochs = performance.epochs.iter().collect::<Vec<_>>();
+            epochs.sort_unstable_by_key(|(epoch, _)| **epoch);
+            for (epoch, statistics) in epochs {
+                let delay = statistics
+                    .delay
+                    .map(|delay| delay.to_string())
+                    .unwrap_or_default();
+                writeln!(
+                    writer,
+                    "{},{},{},{},{},{},{}",
+                    performance.index,
+                    epoch,
+                    statistics.active,
+                    statistics.head,
+                    statistics.target,
+                    statistics.source,
+                    delay
+                )?;
+            }
+        }
+        writer.flush()
+    }
 }
 
 /// Query parameters for the `/lighthouse/analysis/attestation_performance` endpoint.
@@ -37,3 +69,32 @@ pub struct AttestationPerformanceQuery {
     pub start_epoch: Epoch,
     pub end_epoch: Epoch,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_csv_sorts_epochs() {
+        let statistics = |delay| AttestationPerformanceStatistics {
+            active: true,
+            head: delay == Some(1),
+            target: true,
+            source: true,
+            delay,
+        };
+        let performances = vec![AttestationPerformance {
+            index: 7,
+            epochs: HashMap::from([(2, statistics(None)), (1, statistics(Some(1)))]),
+        }];
+
+        let mut csv = vec![];
+        AttestationPerformance::write_csv(&performances, &mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "index,epoch,active,head,target,source,delay\n\
+             7,1,true,true,true,true,1\n\
+             7,2,true,false,true,true,\n"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use super::UniqueAttestation;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use types::{AttestationData, Hash256, Slot};
@@ -45,6 +46,20 @@ pub struct AttestationRewards {
     /// The attestations themselves (optional).
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub attestations: Vec<AttestationData>,
+    /// Breakdown of the rewards for each attestation included (optional).
+    ///
+    /// Each element of the vec attributes the rewards of the corresponding element of
+    /// `per_attestation_rewards` to individual attesters, ordered by committee position.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub per_attestation_breakdown: Vec<Vec<AttestationRewardBreakdown>>,
+}
+
+/// The reward paid to a block proposer for including a single attester's vote.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct AttestationRewardBreakdown {
+    pub attestation: UniqueAttestation,
+    pub validator_index: u64,
+    pub reward: u64,
 }
 
 /// Query parameters for the `/lighthouse/block_rewards` endpoint.
@@ -57,4 +72,7 @@ pub struct BlockRewardsQuery {
     /// Include the full attestations themselves?
     #[serde(default)]
     pub include_attestations: bool,
+    /// Include the per-attester breakdown of each attestation's reward?
+    #[serde(default)]
+    pub include_attestation_breakdown: bool,
 }
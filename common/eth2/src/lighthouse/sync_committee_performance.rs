@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use types::Epoch;
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SyncCommitteePerformanceStatistics {
+    /// Whether the validator was a member of the sync committee during the epoch.
+    pub in_sync_committee: bool,
+    /// The number of sync committee messages from the validator that were included in blocks.
+    pub participated: u64,
+    /// The number of sync committee messages from the validator that were absent from blocks.
+    pub missed: u64,
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SyncCommitteePerformance {
+    pub index: u64,
+    pub epochs: HashMap<u64, SyncCommitteePerformanceStatistics>,
+}
+
+impl SyncCommitteePerformance {
+    pub fn initialize(indices: Vec<u64>) -> Vec<Self> {
+        let mut vec = Vec::with_capacity(indices.len());
+        for index in indices {
+            vec.push(Self {
+                index,
+                ..Default::default()
+            })
+        }
+        vec
+    }
+}
+
+/// Query parameters for the `/lighthouse/analysis/sync_committee_performance` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SyncCommitteePerformanceQuery {
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+}
@@ -349,6 +349,22 @@ impl ValidatorClientHttpClient {
         self.get_opt(path).await
     }
 
+    /// `GET lighthouse/signing_ledger/{epoch}`
+    pub async fn get_lighthouse_signing_ledger(
+        &self,
+        epoch: Epoch,
+    ) -> Result<GenericResponse<Vec<ValidatorSignings>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("signing_ledger")
+            .push(&epoch.to_string());
+
+        self.get(path).await
+    }
+
     /// `POST lighthouse/validators`
     pub async fn post_lighthouse_validators(
         &self,
@@ -412,6 +428,23 @@ impl ValidatorClientHttpClient {
         self.post(path, &request).await
     }
 
+    /// `POST lighthouse/validators/{validator_pubkey}/refresh_duties`
+    pub async fn refresh_validator_duties(
+        &self,
+        voting_pubkey: &PublicKeyBytes,
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push(&voting_pubkey.to_string())
+            .push("refresh_duties");
+
+        self.post(path, &()).await
+    }
+
     /// `PATCH lighthouse/validators/{validator_pubkey}`
     #[allow(clippy::too_many_arguments)]
     pub async fn patch_lighthouse_validators(
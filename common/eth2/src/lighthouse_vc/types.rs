@@ -197,3 +197,18 @@ pub struct SingleExportKeystoresResponse {
 pub struct SetGraffitiRequest {
     pub graffiti: GraffitiString,
 }
+
+/// The messages signed by a validator in an epoch, as returned by
+/// `lighthouse/signing_ledger/{epoch}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorSignings {
+    pub voting_pubkey: PublicKeyBytes,
+    pub signings: Vec<SignedMessage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedMessage {
+    /// One of `block`, `attestation` or `sync_committee_message`.
+    pub message_type: String,
+    pub slot: Slot,
+}
@@ -0,0 +1,47 @@
+//! Errors returned when gathering the metrics served by the beacon node and validator client
+//! metrics servers.
+
+use std::panic::{catch_unwind, UnwindSafe};
+use std::string::FromUtf8Error;
+
+/// The stage at which gathering the Prometheus metrics failed.
+#[derive(Debug)]
+pub enum MetricsGatherError {
+    /// Scraping the allocator metrics panicked, e.g. because the allocator is not glibc.
+    AllocatorScrape(String),
+    /// Scraping the process and system health metrics panicked.
+    HealthScrape(String),
+    /// The gathered metrics could not be encoded in the text exposition format.
+    Encode(String),
+    /// The encoded metrics were not valid UTF-8.
+    Utf8(FromUtf8Error),
+}
+
+/// Runs `scrape`, returning the panic message if it panics.
+pub fn catch_scrape_panic(scrape: impl FnOnce() + UnwindSafe) -> Result<(), String> {
+    catch_unwind(scrape).map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catch_scrape_panic_returns_message() {
+        assert_eq!(catch_scrape_panic(|| {}), Ok(()));
+        assert_eq!(
+            catch_scrape_panic(|| panic!("unsupported allocator")),
+            Err("unsupported allocator".to_string())
+        );
+        assert_eq!(
+            catch_scrape_panic(|| panic!("{} failed", "observe")),
+            Err("observe failed".to_string())
+        );
+    }
+}
@@ -1,2 +1,3 @@
+pub mod gather;
 pub mod metrics;
 pub mod observe;
@@ -0,0 +1,254 @@
+//! Step-by-step replay of block processing, to diagnose why a block is invalid.
+//!
+//! `per_block_processing` stops at the first error, which says which check failed but not which
+//! part of block processing was running. This module replays each `process_*` step of a block
+//! separately on a copy of the pre-state and reports the outcome of each, so that the first
+//! failing operation is reported along with the index attached to it by `IntoWithIndex`.
+
+use crate::common::update_progressive_balances_cache::initialize_progressive_balances_cache;
+use crate::epoch_cache::initialize_epoch_cache;
+use crate::per_block_processing::errors::BlockProcessingError;
+use crate::per_block_processing::process_operations::{
+    process_attestations, process_attester_slashings, process_bls_to_execution_changes,
+    process_consolidation_requests, process_deposit_requests, process_deposits_in_chunks,
+    process_exits, process_proposer_slashings, process_withdrawal_requests,
+    verify_no_execution_requests, DEFAULT_DEPOSIT_VERIFICATION_CHUNK_SIZE,
+};
+use crate::per_block_processing::{
+    is_execution_enabled, process_block_header, process_eth1_data, process_execution_payload,
+    process_randao, process_sync_aggregate, process_withdrawals, verify_block_signature,
+};
+use crate::{ConsensusContext, VerifyBlockRoot, VerifySignatures};
+use std::fmt;
+use types::{
+    AbstractExecPayload, BeaconState, ChainSpec, EthSpec, RelativeEpoch, SignedBeaconBlock,
+};
+
+/// The outcome of a single step of block processing.
+#[derive(Debug, PartialEq)]
+pub struct StepOutcome {
+    /// The name of the step, e.g. `"proposer_slashings"`.
+    pub step: &'static str,
+    pub result: Result<(), BlockProcessingError>,
+}
+
+impl fmt::Display for StepOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.result {
+            Ok(()) => write!(f, "{}: ok", self.step),
+            Err(e) => write!(f, "{}: {:?}", self.step, e),
+        }
+    }
+}
+
+/// Replays `signed_block` upon a copy of `state` one `process_*` step at a time, returning the
+/// outcome of each step in the order they are applied by `per_block_processing`.
+///
+/// `state` must already be advanced to the slot of the block, and is left untouched. Since each
+/// step depends on the state produced by the previous ones, the steps following a failure are not
+/// run and the failing step is the last one returned. Steps which do not apply to the fork of the
+/// block are skipped.
+pub fn diagnose_block<E: EthSpec, Payload: AbstractExecPayload<E>>(
+    state: &BeaconState<E>,
+    signed_block: &SignedBeaconBlock<E, Payload>,
+    verify_signatures: VerifySignatures,
+    ctxt: &mut ConsensusContext<E>,
+    spec: &ChainSpec,
+) -> Vec<StepOutcome> {
+    let mut state = state.clone();
+    let block = signed_block.message();
+    let body = block.body();
+    let mut outcomes = vec![];
+
+    macro_rules! step {
+        ($step: expr, $result: expr) => {{
+            let result: Result<_, BlockProcessingError> = $result;
+            match result {
+                Ok(value) => {
+                    outcomes.push(StepOutcome {
+                        step: $step,
+                        result: Ok(()),
+                    });
+                    value
+                }
+                Err(e) => {
+                    outcomes.push(StepOutcome {
+                        step: $step,
+                        result: Err(e),
+                    });
+                    return outcomes;
+                }
+            }
+        }};
+    }
+
+    step!(
+        "fork",
+        (|| -> Result<_, BlockProcessingError> {
+            signed_block
+                .fork_name(spec)
+                .map_err(BlockProcessingError::InconsistentBlockFork)?;
+            state
+                .fork_name(spec)
+                .map_err(BlockProcessingError::InconsistentStateFork)?;
+            Ok(())
+        })()
+    );
+
+    step!(
+        "caches",
+        (|| -> Result<_, BlockProcessingError> {
+            initialize_epoch_cache(&mut state, spec)?;
+            initialize_progressive_balances_cache(&mut state, spec)?;
+            state.build_slashings_cache()?;
+            Ok(())
+        })()
+    );
+
+    let proposer_index = step!(
+        "block_header",
+        process_block_header(
+            &mut state,
+            block.temporary_block_header(),
+            VerifyBlockRoot::True,
+            ctxt,
+            spec,
+        )
+        .map_err(Into::into)
+    );
+
+    if verify_signatures.is_true() {
+        step!(
+            "block_signature",
+            verify_block_signature(&state, signed_block, ctxt, spec).map_err(Into::into)
+        );
+    }
+
+    step!(
+        "committee_caches",
+        (|| -> Result<_, BlockProcessingError> {
+            state.build_committee_cache(RelativeEpoch::Previous, spec)?;
+            state.build_committee_cache(RelativeEpoch::Current, spec)?;
+            Ok(())
+        })()
+    );
+
+    if is_execution_enabled(&state, body) {
+        step!(
+            "withdrawals",
+            (|| -> Result<_, BlockProcessingError> {
+                process_withdrawals::<E, Payload>(&mut state, body.execution_payload()?, spec)
+            })()
+        );
+        step!(
+            "execution_payload",
+            process_execution_payload::<E, Payload>(&mut state, body, spec)
+        );
+    }
+
+    step!(
+        "randao",
+        process_randao(&mut state, block, verify_signatures, ctxt, spec)
+    );
+    step!(
+        "eth1_data",
+        process_eth1_data(&mut state, body.eth1_data()).map_err(Into::into)
+    );
+
+    step!(
+        "proposer_slashings",
+        process_proposer_slashings(
+            &mut state,
+            body.proposer_slashings(),
+            verify_signatures,
+            ctxt,
+            spec,
+        )
+    );
+    step!(
+        "attester_slashings",
+        process_attester_slashings(
+            &mut state,
+            body.attester_slashings(),
+            verify_signatures,
+            ctxt,
+            spec,
+        )
+    );
+    step!(
+        "attestations",
+        process_attestations(&mut state, body, verify_signatures, ctxt, spec)
+    );
+    step!(
+        "deposits",
+        process_deposits_in_chunks(
+            &mut state,
+            body.deposits(),
+            DEFAULT_DEPOSIT_VERIFICATION_CHUNK_SIZE,
+            spec,
+        )
+    );
+    step!(
+        "voluntary_exits",
+        process_exits(&mut state, body.voluntary_exits(), verify_signatures, spec)
+    );
+
+    if let Ok(bls_to_execution_changes) = body.bls_to_execution_changes() {
+        step!(
+            "bls_to_execution_changes",
+            process_bls_to_execution_changes(
+                &mut state,
+                bls_to_execution_changes,
+                verify_signatures,
+                spec,
+            )
+        );
+    }
+
+    if state.fork_name_unchecked().electra_enabled() {
+        step!(
+            "deposit_requests",
+            (|| -> Result<_, BlockProcessingError> {
+                state.update_pubkey_cache()?;
+                process_deposit_requests(&mut state, &body.execution_requests()?.deposits, spec)
+            })()
+        );
+        step!(
+            "withdrawal_requests",
+            (|| -> Result<_, BlockProcessingError> {
+                process_withdrawal_requests(
+                    &mut state,
+                    &body.execution_requests()?.withdrawals,
+                    spec,
+                )
+            })()
+        );
+        step!(
+            "consolidation_requests",
+            (|| -> Result<_, BlockProcessingError> {
+                process_consolidation_requests(
+                    &mut state,
+                    &body.execution_requests()?.consolidations,
+                    spec,
+                )
+            })()
+        );
+    } else {
+        step!("execution_requests", verify_no_execution_requests(body));
+    }
+
+    if let Ok(sync_aggregate) = body.sync_aggregate() {
+        step!(
+            "sync_aggregate",
+            process_sync_aggregate(
+                &mut state,
+                sync_aggregate,
+                proposer_index,
+                verify_signatures,
+                spec,
+            )
+        );
+    }
+
+    outcomes
+}
@@ -1,5 +1,8 @@
 use crate::common::{attesting_indices_base, attesting_indices_electra};
 use crate::per_block_processing::errors::{AttestationInvalid, BlockOperationError};
+use crate::per_block_processing::process_operations::{
+    ConsolidationRequestOutcome, DepositOutcome,
+};
 use crate::EpochCacheError;
 use std::collections::{hash_map::Entry, HashMap};
 use tree_hash::TreeHash;
@@ -22,6 +25,12 @@ pub struct ConsensusContext<E: EthSpec> {
     pub current_block_root: Option<Hash256>,
     /// Cache of indexed attestations constructed during block processing.
     pub indexed_attestations: HashMap<Hash256, IndexedAttestation<E>>,
+    /// Outcomes of the deposits applied during block processing.
+    pub deposit_outcomes: Vec<DepositOutcome>,
+    /// Outcomes of the consolidation requests applied during block processing.
+    pub consolidation_request_outcomes: Vec<ConsolidationRequestOutcome>,
+    /// Number of signatures of each operation type included for verification with the block.
+    pub operation_signature_counts: Vec<(&'static str, u64)>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -55,6 +64,9 @@ impl<E: EthSpec> ConsensusContext<E> {
             proposer_index: None,
             current_block_root: None,
             indexed_attestations: HashMap::new(),
+            deposit_outcomes: vec![],
+            consolidation_request_outcomes: vec![],
+            operation_signature_counts: vec![],
         }
     }
 
@@ -17,11 +17,13 @@ mod macros;
 mod metrics;
 
 pub mod all_caches;
+pub mod block_diagnosis;
 pub mod block_replayer;
 pub mod common;
 pub mod consensus_context;
 pub mod epoch_cache;
 pub mod genesis;
+pub mod operation_selection;
 pub mod per_block_processing;
 pub mod per_epoch_processing;
 pub mod per_slot_processing;
@@ -37,8 +39,9 @@ pub use genesis::{
     process_activations,
 };
 pub use per_block_processing::{
-    block_signature_verifier, errors::BlockProcessingError, per_block_processing, signature_sets,
-    BlockSignatureStrategy, BlockSignatureVerifier, VerifyBlockRoot, VerifySignatures,
+    block_signature_verifier, errors::BlockProcessingError, per_block_processing,
+    per_block_processing_with_deposit_chunk_size, signature_sets, BlockSignatureStrategy,
+    BlockSignatureVerifier, VerifyBlockRoot, VerifySignatures,
 };
 pub use per_epoch_processing::{
     errors::EpochProcessingError, process_epoch as per_epoch_processing,
@@ -41,6 +41,14 @@ pub static PROCESS_EPOCH_TIME: LazyLock<Result<Histogram>> = LazyLock::new(|| {
         "Time required for process_epoch",
     )
 });
+pub static PROCESS_EPOCH_PHASE_TIME: LazyLock<Result<HistogramVec>> = LazyLock::new(|| {
+    try_create_histogram_vec(
+        "beacon_state_processing_process_epoch_phase_seconds",
+        "Time required for each phase of process_epoch. From Altair the phases applied per \
+         validator in a single pass are interleaved and timed together as single_pass",
+        &["phase"],
+    )
+});
 pub static BUILD_EPOCH_CACHE_TIME: LazyLock<Result<Histogram>> = LazyLock::new(|| {
     try_create_histogram(
         "beacon_state_processing_epoch_cache",
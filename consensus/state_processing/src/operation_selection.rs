@@ -0,0 +1,165 @@
+//! Selection of the operations to include in a block from a pool of candidates.
+//!
+//! Block production needs the subset of candidate operations which are valid against the
+//! pre-state, do not invalidate each other, and fit within the per-block limits. Slashings are
+//! selected greedily by the effective balance they would slash, so that the most valuable ones are
+//! preferred when the limits are exceeded.
+
+use crate::per_block_processing::{
+    verify_attester_slashing, verify_bls_to_execution_change, verify_exit, verify_proposer_slashing,
+};
+use crate::VerifySignatures;
+use std::collections::HashSet;
+use types::{
+    AttesterSlashing, BeaconState, ChainSpec, EthSpec, ProposerSlashing,
+    SignedBlsToExecutionChange, SignedVoluntaryExit, Unsigned,
+};
+
+/// The operations of a block body which are selected from a pool.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockOperations<E: EthSpec> {
+    pub proposer_slashings: Vec<ProposerSlashing>,
+    pub attester_slashings: Vec<AttesterSlashing<E>>,
+    pub voluntary_exits: Vec<SignedVoluntaryExit>,
+    pub bls_to_execution_changes: Vec<SignedBlsToExecutionChange>,
+}
+
+impl<E: EthSpec> Default for BlockOperations<E> {
+    fn default() -> Self {
+        Self {
+            proposer_slashings: vec![],
+            attester_slashings: vec![],
+            voluntary_exits: vec![],
+            bls_to_execution_changes: vec![],
+        }
+    }
+}
+
+/// Returns the subset of `candidates` which may be included together in a block built upon
+/// `state`.
+///
+/// Each operation is checked with the same `verify_*` function used by block processing. On top
+/// of that, the selection:
+///
+/// - Prefers the slashings which slash the most effective balance.
+/// - Skips operations made redundant or invalid by operations selected before them, i.e. slashings
+///   of validators already slashed in the selection, exits of those validators, and duplicate
+///   exits or BLS to execution changes for a validator.
+/// - Respects the per-block limits of each operation type for the fork of `state`.
+///
+/// Invalid candidates are dropped silently.
+pub fn select_operations<E: EthSpec>(
+    state: &BeaconState<E>,
+    candidates: BlockOperations<E>,
+    verify_signatures: VerifySignatures,
+    spec: &ChainSpec,
+) -> BlockOperations<E> {
+    let fork_name = state.fork_name_unchecked();
+    let mut to_be_slashed = HashSet::new();
+
+    // Proposer slashings slash a single validator, prefer the highest effective balance.
+    let mut proposer_slashings = candidates
+        .proposer_slashings
+        .into_iter()
+        .filter(|slashing| {
+            verify_proposer_slashing(slashing, state, verify_signatures, spec).is_ok()
+        })
+        .map(|slashing| {
+            let value = effective_balance(state, slashing.proposer_index());
+            (value, slashing)
+        })
+        .collect::<Vec<_>>();
+    proposer_slashings.sort_by(|(value_a, _), (value_b, _)| value_b.cmp(value_a));
+    let proposer_slashings = proposer_slashings
+        .into_iter()
+        .filter(|(_, slashing)| to_be_slashed.insert(slashing.proposer_index()))
+        .map(|(_, slashing)| slashing)
+        .take(E::MaxProposerSlashings::to_usize())
+        .collect();
+
+    // Attester slashings may slash many validators, some of which may already be slashed by the
+    // selected slashings. Greedily select the slashing adding the most effective balance.
+    let max_attester_slashings = if fork_name.electra_enabled() {
+        E::max_attester_slashings_electra()
+    } else {
+        E::MaxAttesterSlashings::to_usize()
+    };
+    let mut attester_slashing_candidates = candidates
+        .attester_slashings
+        .into_iter()
+        .filter(|slashing| {
+            matches!(slashing, AttesterSlashing::Electra(_)) == fork_name.electra_enabled()
+        })
+        .filter_map(|slashing| {
+            let slashable_indices =
+                verify_attester_slashing(state, slashing.to_ref(), verify_signatures, spec).ok()?;
+            Some((slashable_indices, slashing))
+        })
+        .collect::<Vec<_>>();
+    let mut attester_slashings = vec![];
+    while attester_slashings.len() < max_attester_slashings {
+        let best = attester_slashing_candidates
+            .iter()
+            .enumerate()
+            .map(|(i, (slashable_indices, _))| {
+                let value = slashable_indices
+                    .iter()
+                    .filter(|index| !to_be_slashed.contains(*index))
+                    .fold(0u64, |value, index| {
+                        value.saturating_add(effective_balance(state, *index))
+                    });
+                (i, value)
+            })
+            .filter(|(_, value)| *value > 0)
+            .max_by_key(|(_, value)| *value);
+        let Some((i, _)) = best else {
+            break;
+        };
+        let (slashable_indices, slashing) = attester_slashing_candidates.swap_remove(i);
+        to_be_slashed.extend(slashable_indices);
+        attester_slashings.push(slashing);
+    }
+
+    // Slashed validators are exited by their slashing.
+    let mut exiting = HashSet::new();
+    let voluntary_exits = candidates
+        .voluntary_exits
+        .into_iter()
+        .filter(|exit| {
+            let validator_index = exit.message.validator_index;
+            !to_be_slashed.contains(&validator_index)
+                && verify_exit(state, None, exit, verify_signatures, spec).is_ok()
+                && exiting.insert(validator_index)
+        })
+        .take(E::MaxVoluntaryExits::to_usize())
+        .collect();
+
+    let bls_to_execution_changes = if fork_name.capella_enabled() {
+        let mut changing = HashSet::new();
+        candidates
+            .bls_to_execution_changes
+            .into_iter()
+            .filter(|change| {
+                verify_bls_to_execution_change(state, change, verify_signatures, spec).is_ok()
+                    && changing.insert(change.message.validator_index)
+            })
+            .take(E::max_bls_to_execution_changes())
+            .collect()
+    } else {
+        vec![]
+    };
+
+    BlockOperations {
+        proposer_slashings,
+        attester_slashings,
+        voluntary_exits,
+        bls_to_execution_changes,
+    }
+}
+
+fn effective_balance<E: EthSpec>(state: &BeaconState<E>, validator_index: u64) -> u64 {
+    state
+        .validators()
+        .get(validator_index as usize)
+        .map_or(0, |validator| validator.effective_balance)
+}
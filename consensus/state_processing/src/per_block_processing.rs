@@ -4,6 +4,7 @@ use rayon::prelude::*;
 use safe_arith::{ArithError, SafeArith, SafeArithIter};
 use signature_sets::{block_proposal_signature_set, get_pubkey_from_state, randao_signature_set};
 use std::borrow::Cow;
+use std::num::NonZeroUsize;
 use tree_hash::TreeHash;
 use types::*;
 
@@ -14,7 +15,7 @@ pub use self::verify_proposer_slashing::verify_proposer_slashing;
 pub use altair::sync_committee::process_sync_aggregate;
 pub use block_signature_verifier::{BlockSignatureVerifier, ParallelSignatureSets};
 pub use is_valid_indexed_attestation::is_valid_indexed_attestation;
-pub use process_operations::process_operations;
+pub use process_operations::{process_operations, DEFAULT_DEPOSIT_VERIFICATION_CHUNK_SIZE};
 pub use verify_attestation::{
     verify_attestation_for_block_inclusion, verify_attestation_for_state,
 };
@@ -104,6 +105,28 @@ pub fn per_block_processing<E: EthSpec, Payload: AbstractExecPayload<E>>(
     verify_block_root: VerifyBlockRoot,
     ctxt: &mut ConsensusContext<E>,
     spec: &ChainSpec,
+) -> Result<(), BlockProcessingError> {
+    per_block_processing_with_deposit_chunk_size(
+        state,
+        signed_block,
+        block_signature_strategy,
+        verify_block_root,
+        DEFAULT_DEPOSIT_VERIFICATION_CHUNK_SIZE,
+        ctxt,
+        spec,
+    )
+}
+
+/// Equivalent to `per_block_processing`, but verifies the merkle proofs of
+/// `deposit_verification_chunk_size` deposits per parallel task.
+pub fn per_block_processing_with_deposit_chunk_size<E: EthSpec, Payload: AbstractExecPayload<E>>(
+    state: &mut BeaconState<E>,
+    signed_block: &SignedBeaconBlock<E, Payload>,
+    block_signature_strategy: BlockSignatureStrategy,
+    verify_block_root: VerifyBlockRoot,
+    deposit_verification_chunk_size: NonZeroUsize,
+    ctxt: &mut ConsensusContext<E>,
+    spec: &ChainSpec,
 ) -> Result<(), BlockProcessingError> {
     let block = signed_block.message();
 
@@ -176,7 +199,14 @@ pub fn per_block_processing<E: EthSpec, Payload: AbstractExecPayload<E>>(
 
     process_randao(state, block, verify_randao, ctxt, spec)?;
     process_eth1_data(state, block.body().eth1_data())?;
-    process_operations(state, block.body(), verify_signatures, ctxt, spec)?;
+    process_operations(
+        state,
+        block.body(),
+        verify_signatures,
+        deposit_verification_chunk_size,
+        ctxt,
+        spec,
+    )?;
 
     if let Ok(sync_aggregate) = block.body().sync_aggregate() {
         process_sync_aggregate(
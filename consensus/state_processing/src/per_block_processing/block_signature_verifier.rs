@@ -171,6 +171,25 @@ where
         self.include_sync_aggregate(block)?;
         self.include_bls_to_execution_changes(block)?;
 
+        let body = block.message().body();
+        ctxt.operation_signature_counts = vec![
+            (
+                "proposer_slashing",
+                body.proposer_slashings().len() as u64 * 2,
+            ),
+            (
+                "attester_slashing",
+                body.attester_slashings_len() as u64 * 2,
+            ),
+            ("attestation", body.attestations_len() as u64),
+            ("voluntary_exit", body.voluntary_exits().len() as u64),
+            (
+                "bls_to_execution_change",
+                body.bls_to_execution_changes()
+                    .map_or(0, |changes| changes.len() as u64),
+            ),
+        ];
+
         Ok(())
     }
 
@@ -90,6 +90,8 @@ pub enum BlockProcessingError {
     },
     WithdrawalCredentialsInvalid,
     PendingAttestationInElectra,
+    /// The block body carries execution requests, but the state is prior to Electra.
+    ExecutionRequestsBeforeElectra,
 }
 
 impl From<BeaconStateError> for BlockProcessingError {
@@ -289,6 +291,8 @@ pub enum ProposerSlashingInvalid {
     ProposalsIdentical,
     /// The specified proposer cannot be slashed because they are already slashed, or not active.
     ProposerNotSlashable(u64),
+    /// The specified proposer is slashed by an earlier proposer slashing in the same block.
+    DuplicateInBlock(u64),
     /// The first proposal signature was invalid.
     BadProposal1Signature,
     /// The second proposal signature was invalid.
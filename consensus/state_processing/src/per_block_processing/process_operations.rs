@@ -3,15 +3,25 @@ use crate::common::{
     get_attestation_participation_flag_indices, increase_balance, initiate_validator_exit,
     slash_validator,
 };
-use crate::per_block_processing::errors::{BlockProcessingError, IntoWithIndex};
+use crate::per_block_processing::errors::{
+    BlockProcessingError, IntoWithIndex, ProposerSlashingInvalid,
+};
 use crate::VerifySignatures;
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
 use types::consts::altair::{PARTICIPATION_FLAG_WEIGHTS, PROPOSER_WEIGHT, WEIGHT_DENOMINATOR};
 use types::typenum::U33;
 
+/// The default number of deposits whose merkle proofs are verified by each parallel task.
+///
+/// A chunk size of one verifies every deposit in its own task.
+pub const DEFAULT_DEPOSIT_VERIFICATION_CHUNK_SIZE: NonZeroUsize = NonZeroUsize::MIN;
+
 pub fn process_operations<E: EthSpec, Payload: AbstractExecPayload<E>>(
     state: &mut BeaconState<E>,
     block_body: BeaconBlockBodyRef<E, Payload>,
     verify_signatures: VerifySignatures,
+    deposit_verification_chunk_size: NonZeroUsize,
     ctxt: &mut ConsensusContext<E>,
     spec: &ChainSpec,
 ) -> Result<(), BlockProcessingError> {
@@ -30,7 +40,12 @@ pub fn process_operations<E: EthSpec, Payload: AbstractExecPayload<E>>(
         spec,
     )?;
     process_attestations(state, block_body, verify_signatures, ctxt, spec)?;
-    process_deposits(state, block_body.deposits(), spec)?;
+    ctxt.deposit_outcomes = process_deposits_in_chunks(
+        state,
+        block_body.deposits(),
+        deposit_verification_chunk_size,
+        spec,
+    )?;
     process_exits(state, block_body.voluntary_exits(), verify_signatures, spec)?;
 
     if let Ok(bls_to_execution_changes) = block_body.bls_to_execution_changes() {
@@ -41,16 +56,35 @@ pub fn process_operations<E: EthSpec, Payload: AbstractExecPayload<E>>(
         state.update_pubkey_cache()?;
         process_deposit_requests(state, &block_body.execution_requests()?.deposits, spec)?;
         process_withdrawal_requests(state, &block_body.execution_requests()?.withdrawals, spec)?;
-        process_consolidation_requests(
+        ctxt.consolidation_request_outcomes = process_consolidation_requests(
             state,
             &block_body.execution_requests()?.consolidations,
             spec,
         )?;
+    } else {
+        verify_no_execution_requests(block_body)?;
     }
 
     Ok(())
 }
 
+/// Verifies that a block body processed prior to Electra contains no execution requests.
+///
+/// Execution requests are only valid from Electra, they are rejected rather than ignored.
+pub fn verify_no_execution_requests<E: EthSpec, Payload: AbstractExecPayload<E>>(
+    block_body: BeaconBlockBodyRef<E, Payload>,
+) -> Result<(), BlockProcessingError> {
+    if let Ok(execution_requests) = block_body.execution_requests() {
+        if !execution_requests.deposits.is_empty()
+            || !execution_requests.withdrawals.is_empty()
+            || !execution_requests.consolidations.is_empty()
+        {
+            return Err(BlockProcessingError::ExecutionRequestsBeforeElectra);
+        }
+    }
+    Ok(())
+}
+
 pub mod base {
     use super::*;
 
@@ -227,20 +261,26 @@ pub fn process_proposer_slashings<E: EthSpec>(
     // Verify and apply proposer slashings in series.
     // We have to verify in series because an invalid block may contain multiple slashings
     // for the same validator, and we need to correctly detect and reject that.
+    let mut slashed_proposers = HashSet::with_capacity(proposer_slashings.len());
     proposer_slashings
         .iter()
         .enumerate()
         .try_for_each(|(i, proposer_slashing)| {
+            let proposer_index = proposer_slashing.signed_header_1.message.proposer_index;
+            // The repeated slashing would fail verification as the validator is already slashed.
+            // Report it distinctly so that a malicious block can be distinguished from a bug in
+            // our own block production.
+            if !slashed_proposers.insert(proposer_index) {
+                return Err(BlockOperationError::invalid(
+                    ProposerSlashingInvalid::DuplicateInBlock(proposer_index),
+                )
+                .into_with_index(i));
+            }
+
             verify_proposer_slashing(proposer_slashing, state, verify_signatures, spec)
                 .map_err(|e| e.into_with_index(i))?;
 
-            slash_validator(
-                state,
-                proposer_slashing.signed_header_1.message.proposer_index as usize,
-                None,
-                ctxt,
-                spec,
-            )?;
+            slash_validator(state, proposer_index as usize, None, ctxt, spec)?;
 
             Ok(())
         })
@@ -359,6 +399,28 @@ pub fn process_deposits<E: EthSpec>(
     deposits: &[Deposit],
     spec: &ChainSpec,
 ) -> Result<(), BlockProcessingError> {
+    process_deposits_in_chunks(
+        state,
+        deposits,
+        DEFAULT_DEPOSIT_VERIFICATION_CHUNK_SIZE,
+        spec,
+    )
+    .map(|_| ())
+}
+
+/// Equivalent to `process_deposits`, but verifies the merkle proofs of `chunk_size` deposits per
+/// parallel task.
+///
+/// Larger chunks bound the number of parallel tasks spawned for a block with many deposits. The
+/// outcome, including the index reported for an invalid deposit, does not depend on `chunk_size`.
+///
+/// Returns the outcome of each deposit, in order.
+pub fn process_deposits_in_chunks<E: EthSpec>(
+    state: &mut BeaconState<E>,
+    deposits: &[Deposit],
+    chunk_size: NonZeroUsize,
+    spec: &ChainSpec,
+) -> Result<Vec<DepositOutcome>, BlockProcessingError> {
     // [Modified in Electra:EIP6110]
     // Disable former deposit mechanism once all prior deposits are processed
     let deposit_requests_start_index = state.deposit_requests_start_index().unwrap_or(u64::MAX);
@@ -389,26 +451,65 @@ pub fn process_deposits<E: EthSpec>(
         );
     }
 
-    // Verify merkle proofs in parallel.
+    // Verify merkle proofs in parallel, one chunk per task.
+    let chunk_size = chunk_size.get();
     deposits
-        .par_iter()
+        .par_chunks(chunk_size)
         .enumerate()
-        .try_for_each(|(i, deposit)| {
-            verify_deposit_merkle_proof(
-                state,
-                deposit,
-                state.eth1_deposit_index().safe_add(i as u64)?,
-                spec,
-            )
-            .map_err(|e| e.into_with_index(i))
+        .try_for_each(|(chunk_index, chunk)| {
+            chunk.iter().enumerate().try_for_each(|(j, deposit)| {
+                let i = chunk_index.safe_mul(chunk_size)?.safe_add(j)?;
+                verify_deposit_merkle_proof(
+                    state,
+                    deposit,
+                    state.eth1_deposit_index().safe_add(i as u64)?,
+                    spec,
+                )
+                .map_err(|e| e.into_with_index(i))
+            })
         })?;
 
     // Update the state in series.
+    let mut outcomes = Vec::with_capacity(deposits.len());
     for deposit in deposits {
-        apply_deposit(state, deposit.data.clone(), None, true, spec)?;
+        let outcome = apply_deposit(state, deposit.data.clone(), None, true, spec)?;
+        outcomes.push(outcome);
     }
 
-    Ok(())
+    Ok(outcomes)
+}
+
+/// The effect of applying a `Deposit` to the state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepositOutcome {
+    /// A new validator was added to the registry.
+    NewValidator,
+    /// The balance of an existing validator was increased.
+    TopUp,
+    /// A deposit to an existing validator was queued in `pending_deposits`.
+    Pending,
+    /// The deposit was for a new validator but its signature was invalid, so it was ignored.
+    InvalidSignature,
+}
+
+impl DepositOutcome {
+    /// The label of the outcome in the `beacon_deposits_processed_total` metric.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DepositOutcome::NewValidator => "new",
+            DepositOutcome::TopUp => "topup",
+            DepositOutcome::Pending => "pending",
+            DepositOutcome::InvalidSignature => "invalid_signature",
+        }
+    }
+
+    /// Whether the signature of the deposit was verified, which is only done for new validators.
+    pub fn signature_verified(&self) -> bool {
+        matches!(
+            self,
+            DepositOutcome::NewValidator | DepositOutcome::InvalidSignature
+        )
+    }
 }
 
 /// Process a single deposit, verifying its merkle proof if provided.
@@ -418,7 +519,7 @@ pub fn apply_deposit<E: EthSpec>(
     proof: Option<FixedVector<Hash256, U33>>,
     increment_eth1_deposit_index: bool,
     spec: &ChainSpec,
-) -> Result<(), BlockProcessingError> {
+) -> Result<DepositOutcome, BlockProcessingError> {
     let deposit_index = state.eth1_deposit_index() as usize;
     if let Some(proof) = proof {
         let deposit = Deposit {
@@ -450,9 +551,11 @@ pub fn apply_deposit<E: EthSpec>(
                 signature: deposit_data.signature,
                 slot: spec.genesis_slot, // Use `genesis_slot` to distinguish from a pending deposit request
             })?;
+            Ok(DepositOutcome::Pending)
         } else {
             // Update the existing validator balance.
             increase_balance(state, index as usize, amount)?;
+            Ok(DepositOutcome::TopUp)
         }
     }
     // New validator
@@ -460,7 +563,7 @@ pub fn apply_deposit<E: EthSpec>(
         // The signature should be checked for new validators. Return early for a bad
         // signature.
         if is_valid_deposit_signature(&deposit_data, spec).is_err() {
-            return Ok(());
+            return Ok(DepositOutcome::InvalidSignature);
         }
 
         state.add_validator_to_registry(
@@ -484,9 +587,9 @@ pub fn apply_deposit<E: EthSpec>(
                 slot: spec.genesis_slot, // Use `genesis_slot` to distinguish from a pending deposit request
             })?;
         }
-    }
 
-    Ok(())
+        Ok(DepositOutcome::NewValidator)
+    }
 }
 
 // Make sure to build the pubkey cache before calling this function
@@ -617,12 +720,11 @@ pub fn process_consolidation_requests<E: EthSpec>(
     state: &mut BeaconState<E>,
     consolidation_requests: &[ConsolidationRequest],
     spec: &ChainSpec,
-) -> Result<(), BlockProcessingError> {
-    for request in consolidation_requests {
-        process_consolidation_request(state, request, spec)?;
-    }
-
-    Ok(())
+) -> Result<Vec<ConsolidationRequestOutcome>, BlockProcessingError> {
+    consolidation_requests
+        .iter()
+        .map(|request| process_consolidation_request(state, request, spec))
+        .collect()
 }
 
 fn is_valid_switch_to_compounding_request<E: EthSpec>(
@@ -680,11 +782,58 @@ fn is_valid_switch_to_compounding_request<E: EthSpec>(
     Ok(true)
 }
 
+/// The outcome of processing a single `ConsolidationRequest`.
+///
+/// Invalid consolidation requests do not invalidate the block, they are ignored. The outcome
+/// reports why a request was ignored, e.g. for debugging endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsolidationRequestOutcome {
+    /// The source validator was switched to compounding withdrawal credentials.
+    SwitchedToCompounding,
+    /// The source validator exit was initiated and a pending consolidation was queued.
+    Consolidated,
+    /// The request was ignored and the state was not mutated.
+    Ignored(ConsolidationIgnoredReason),
+}
+
+/// The reason a `ConsolidationRequest` was ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsolidationIgnoredReason {
+    /// The source and target are equal but the request is not a valid switch to compounding.
+    InvalidSwitchToCompounding,
+    QueueFull,
+    ChurnLimitTooLow,
+    UnknownSource,
+    UnknownTarget,
+    SourceAddressMismatch,
+    SourceNoExecutionWithdrawalCredentials,
+    TargetNotCompounding,
+    SourceInactive,
+    TargetInactive,
+    SourceExiting,
+    TargetExiting,
+    SourceNotActiveLongEnough,
+    SourcePendingWithdrawals,
+}
+
+impl ConsolidationRequestOutcome {
+    /// The label of the outcome in the `beacon_consolidation_requests_total` metric.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ConsolidationRequestOutcome::SwitchedToCompounding => "switch_to_compounding",
+            ConsolidationRequestOutcome::Consolidated => "consolidation",
+            ConsolidationRequestOutcome::Ignored(_) => "rejected",
+        }
+    }
+}
+
 pub fn process_consolidation_request<E: EthSpec>(
     state: &mut BeaconState<E>,
     consolidation_request: &ConsolidationRequest,
     spec: &ChainSpec,
-) -> Result<(), BlockProcessingError> {
+) -> Result<ConsolidationRequestOutcome, BlockProcessingError> {
+    use ConsolidationIgnoredReason::*;
+
     if is_valid_switch_to_compounding_request(state, consolidation_request, spec)? {
         let Some(source_index) = state
             .pubkey_cache()
@@ -692,24 +841,26 @@ pub fn process_consolidation_request<E: EthSpec>(
         else {
             // source validator doesn't exist. This is unreachable as `is_valid_switch_to_compounding_request`
             // will return false in that case.
-            return Ok(());
+            return Ok(ConsolidationRequestOutcome::Ignored(UnknownSource));
         };
         state.switch_to_compounding_validator(source_index, spec)?;
-        return Ok(());
+        return Ok(ConsolidationRequestOutcome::SwitchedToCompounding);
     }
 
     // Verify that source != target, so a consolidation cannot be used as an exit.
     if consolidation_request.source_pubkey == consolidation_request.target_pubkey {
-        return Ok(());
+        return Ok(ConsolidationRequestOutcome::Ignored(
+            InvalidSwitchToCompounding,
+        ));
     }
 
     // If the pending consolidations queue is full, consolidation requests are ignored
     if state.pending_consolidations()?.len() == E::PendingConsolidationsLimit::to_usize() {
-        return Ok(());
+        return Ok(ConsolidationRequestOutcome::Ignored(QueueFull));
     }
     // If there is too little available consolidation churn limit, consolidation requests are ignored
     if state.get_consolidation_churn_limit(spec)? <= spec.min_activation_balance {
-        return Ok(());
+        return Ok(ConsolidationRequestOutcome::Ignored(ChurnLimitTooLow));
     }
 
     let Some(source_index) = state
@@ -717,45 +868,49 @@ pub fn process_consolidation_request<E: EthSpec>(
         .get(&consolidation_request.source_pubkey)
     else {
         // source validator doesn't exist
-        return Ok(());
+        return Ok(ConsolidationRequestOutcome::Ignored(UnknownSource));
     };
     let Some(target_index) = state
         .pubkey_cache()
         .get(&consolidation_request.target_pubkey)
     else {
         // target validator doesn't exist
-        return Ok(());
+        return Ok(ConsolidationRequestOutcome::Ignored(UnknownTarget));
     };
 
     let source_validator = state.get_validator(source_index)?;
     // Verify the source withdrawal credentials
     if let Some(withdrawal_address) = source_validator.get_execution_withdrawal_address(spec) {
         if withdrawal_address != consolidation_request.source_address {
-            return Ok(());
+            return Ok(ConsolidationRequestOutcome::Ignored(SourceAddressMismatch));
         }
     } else {
         // Source doen't have execution withdrawal credentials
-        return Ok(());
+        return Ok(ConsolidationRequestOutcome::Ignored(
+            SourceNoExecutionWithdrawalCredentials,
+        ));
     }
 
     let target_validator = state.get_validator(target_index)?;
     // Verify the target has compounding withdrawal credentials
     if !target_validator.has_compounding_withdrawal_credential(spec) {
-        return Ok(());
+        return Ok(ConsolidationRequestOutcome::Ignored(TargetNotCompounding));
     }
 
     // Verify the source and target are active
     let current_epoch = state.current_epoch();
-    if !source_validator.is_active_at(current_epoch)
-        || !target_validator.is_active_at(current_epoch)
-    {
-        return Ok(());
+    if !source_validator.is_active_at(current_epoch) {
+        return Ok(ConsolidationRequestOutcome::Ignored(SourceInactive));
+    }
+    if !target_validator.is_active_at(current_epoch) {
+        return Ok(ConsolidationRequestOutcome::Ignored(TargetInactive));
     }
     // Verify exits for source and target have not been initiated
-    if source_validator.exit_epoch != spec.far_future_epoch
-        || target_validator.exit_epoch != spec.far_future_epoch
-    {
-        return Ok(());
+    if source_validator.exit_epoch != spec.far_future_epoch {
+        return Ok(ConsolidationRequestOutcome::Ignored(SourceExiting));
+    }
+    if target_validator.exit_epoch != spec.far_future_epoch {
+        return Ok(ConsolidationRequestOutcome::Ignored(TargetExiting));
     }
     // Verify the source has been active long enough
     if current_epoch
@@ -763,11 +918,15 @@ pub fn process_consolidation_request<E: EthSpec>(
             .activation_epoch
             .safe_add(spec.shard_committee_period)?
     {
-        return Ok(());
+        return Ok(ConsolidationRequestOutcome::Ignored(
+            SourceNotActiveLongEnough,
+        ));
     }
     // Verify the source has no pending withdrawals in the queue
     if state.get_pending_balance_to_withdraw(source_index)? > 0 {
-        return Ok(());
+        return Ok(ConsolidationRequestOutcome::Ignored(
+            SourcePendingWithdrawals,
+        ));
     }
 
     // Initiate source validator exit and append pending consolidation
@@ -784,5 +943,5 @@ pub fn process_consolidation_request<E: EthSpec>(
             target_index: target_index as u64,
         })?;
 
-    Ok(())
+    Ok(ConsolidationRequestOutcome::Consolidated)
 }
@@ -1,5 +1,7 @@
 #![cfg(all(test, not(feature = "fake_crypto"), not(debug_assertions)))]
 
+use crate::block_diagnosis::{diagnose_block, StepOutcome};
+use crate::operation_selection::{select_operations, BlockOperations};
 use crate::per_block_processing::errors::{
     AttestationInvalid, AttesterSlashingInvalid, BlockOperationError, BlockProcessingError,
     DepositInvalid, HeaderInvalid, IndexedAttestationInvalid, IntoWithIndex,
@@ -7,7 +9,14 @@ use crate::per_block_processing::errors::{
 };
 use crate::{per_block_processing, BlockReplayError, BlockReplayer};
 use crate::{
-    per_block_processing::{process_operations, verify_exit::verify_exit},
+    per_block_processing::{
+        process_operations,
+        process_operations::{
+            process_consolidation_request, ConsolidationIgnoredReason, ConsolidationRequestOutcome,
+            DEFAULT_DEPOSIT_VERIFICATION_CHUNK_SIZE,
+        },
+        verify_exit::verify_exit,
+    },
     BlockSignatureStrategy, ConsensusContext, VerifyBlockRoot, VerifySignatures,
 };
 use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
@@ -227,6 +236,55 @@ async fn valid_4_deposits() {
     assert_eq!(result, Ok(()));
 }
 
+#[tokio::test]
+async fn deposit_verification_chunk_size_does_not_affect_outcome() {
+    let spec = MainnetEthSpec::default_spec();
+    let harness = get_harness::<MainnetEthSpec>(EPOCH_OFFSET, VALIDATOR_COUNT).await;
+    let mut state = harness.get_current_state();
+
+    let (deposits, state) = harness.make_deposits(&mut state, 4, None, None);
+    let chunk_sizes = [1, 3, 16].map(|size| std::num::NonZeroUsize::new(size).unwrap());
+
+    // All chunk sizes should produce the same post-state for valid deposits.
+    let mut expected_state = state.clone();
+    process_operations::process_deposits(&mut expected_state, &deposits, &spec).unwrap();
+    for chunk_size in chunk_sizes {
+        let mut chunked_state = state.clone();
+        let result = process_operations::process_deposits_in_chunks(
+            &mut chunked_state,
+            &deposits,
+            chunk_size,
+            &spec,
+        );
+        assert_eq!(
+            result.map(|outcomes| outcomes.len()),
+            Ok(deposits.len()),
+            "chunk size {chunk_size}"
+        );
+        assert_eq!(chunked_state, expected_state, "chunk size {chunk_size}");
+    }
+
+    // An invalid proof should be reported at the same index regardless of the chunk size.
+    let mut invalid_deposits = deposits.clone();
+    invalid_deposits[2].proof[0] = Hash256::repeat_byte(0x42);
+    for chunk_size in chunk_sizes {
+        let result = process_operations::process_deposits_in_chunks(
+            &mut state.clone(),
+            &invalid_deposits,
+            chunk_size,
+            &spec,
+        );
+        assert_eq!(
+            result,
+            Err(BlockProcessingError::DepositInvalid {
+                index: 2,
+                reason: DepositInvalid::BadMerkleProof
+            }),
+            "chunk size {chunk_size}"
+        );
+    }
+}
+
 #[tokio::test]
 async fn invalid_deposit_deposit_count_too_big() {
     let spec = MainnetEthSpec::default_spec();
@@ -1009,6 +1067,89 @@ async fn invalid_proposer_slashing_proposal_epoch_mismatch() {
     );
 }
 
+#[tokio::test]
+async fn diagnose_block_reports_first_invalid_operation() {
+    let spec = MainnetEthSpec::default_spec();
+    let harness = get_harness::<MainnetEthSpec>(EPOCH_OFFSET, VALIDATOR_COUNT).await;
+
+    // The second slashing of the same proposer is invalid.
+    let proposer_slashing = harness.make_proposer_slashing(1);
+    let state = harness.get_current_state();
+    let slot = state.slot() + 1;
+    let ((block, _), mut state) = harness
+        .make_block_with_modifier(state, slot, |block| {
+            let proposer_slashings = block.body_mut().proposer_slashings_mut();
+            proposer_slashings.push(proposer_slashing.clone()).unwrap();
+            proposer_slashings.push(proposer_slashing).unwrap();
+        })
+        .await;
+    let state_root = state.canonical_root().unwrap();
+
+    let mut ctxt = ConsensusContext::new(block.slot());
+    let outcomes = diagnose_block(&state, &block, VerifySignatures::True, &mut ctxt, &spec);
+
+    let (failed, preceding) = outcomes.split_last().unwrap();
+    assert_eq!(failed.step, "proposer_slashings");
+    assert_eq!(
+        failed.result,
+        Err(BlockProcessingError::ProposerSlashingInvalid {
+            index: 1,
+            reason: ProposerSlashingInvalid::DuplicateInBlock(1)
+        })
+    );
+    assert!(preceding.iter().all(|outcome| outcome.result.is_ok()));
+    assert!(preceding
+        .iter()
+        .any(|outcome| outcome.step == "block_signature"));
+
+    // The state is left untouched.
+    assert_eq!(state.canonical_root().unwrap(), state_root);
+}
+
+#[tokio::test]
+async fn diagnose_block_reports_inconsistent_block_fork() {
+    let spec = Arc::new(ForkName::Deneb.make_genesis_spec(MainnetEthSpec::default_spec()));
+    let harness = BeaconChainHarness::builder(MainnetEthSpec)
+        .spec(spec.clone())
+        .deterministic_keypairs(VALIDATOR_COUNT)
+        .mock_execution_layer()
+        .fresh_ephemeral_store()
+        .build();
+    let state = harness.get_current_state();
+
+    // An Electra block with execution requests at a Deneb slot is rejected before its operations.
+    let mut block = BeaconBlockElectra::<MainnetEthSpec, FullPayload<MainnetEthSpec>>::empty(&spec);
+    block.slot = state.slot();
+    block
+        .body
+        .execution_requests
+        .deposits
+        .push(DepositRequest {
+            pubkey: PublicKeyBytes::empty(),
+            withdrawal_credentials: Hash256::zero(),
+            amount: spec.min_activation_balance,
+            signature: SignatureBytes::empty(),
+            index: 0,
+        })
+        .unwrap();
+    let block = SignedBeaconBlock::from_block(BeaconBlock::Electra(block), Signature::empty());
+
+    let mut ctxt = ConsensusContext::new(block.slot());
+    let outcomes = diagnose_block(&state, &block, VerifySignatures::False, &mut ctxt, &spec);
+    assert_eq!(
+        outcomes,
+        vec![StepOutcome {
+            step: "fork",
+            result: Err(BlockProcessingError::InconsistentBlockFork(
+                InconsistentFork {
+                    fork_at_slot: ForkName::Deneb,
+                    object_fork: ForkName::Electra,
+                }
+            )),
+        }]
+    );
+}
+
 #[tokio::test]
 async fn fork_spanning_exit() {
     let mut spec = MainnetEthSpec::default_spec();
@@ -1142,3 +1283,125 @@ async fn block_replayer_peeking_state_roots() {
         (dummy_state_root, dummy_slot)
     );
 }
+
+#[tokio::test]
+async fn ignored_consolidation_request_outcomes() {
+    let spec = Arc::new(ForkName::Electra.make_genesis_spec(MainnetEthSpec::default_spec()));
+    let harness = BeaconChainHarness::builder(MainnetEthSpec)
+        .spec(spec.clone())
+        .deterministic_keypairs(VALIDATOR_COUNT)
+        .mock_execution_layer()
+        .fresh_ephemeral_store()
+        .build();
+
+    let mut state = harness.get_current_state();
+    state.update_pubkey_cache().unwrap();
+    let pubkey = |state: &BeaconState<MainnetEthSpec>, index: usize| {
+        state.get_validator(index).unwrap().pubkey
+    };
+    let pre_state_root = state.canonical_root().unwrap();
+
+    // A self-consolidation from an address that does not match the withdrawal credentials is not
+    // a valid switch to compounding.
+    let request = ConsolidationRequest {
+        source_address: Address::repeat_byte(0xff),
+        source_pubkey: pubkey(&state, 0),
+        target_pubkey: pubkey(&state, 0),
+    };
+    assert_eq!(
+        process_consolidation_request(&mut state, &request, &spec).unwrap(),
+        ConsolidationRequestOutcome::Ignored(
+            ConsolidationIgnoredReason::InvalidSwitchToCompounding
+        )
+    );
+
+    // The consolidation churn of a small validator set is entirely used by activations and exits.
+    let request = ConsolidationRequest {
+        source_address: Address::repeat_byte(0xff),
+        source_pubkey: pubkey(&state, 0),
+        target_pubkey: pubkey(&state, 1),
+    };
+    assert_eq!(
+        process_consolidation_request(&mut state, &request, &spec).unwrap(),
+        ConsolidationRequestOutcome::Ignored(ConsolidationIgnoredReason::ChurnLimitTooLow)
+    );
+
+    // Ignored requests do not mutate the state.
+    assert_eq!(state.canonical_root().unwrap(), pre_state_root);
+}
+
+#[tokio::test]
+async fn execution_requests_before_electra_are_rejected() {
+    let spec = Arc::new(ForkName::Deneb.make_genesis_spec(MainnetEthSpec::default_spec()));
+    let harness = BeaconChainHarness::builder(MainnetEthSpec)
+        .spec(spec.clone())
+        .deterministic_keypairs(VALIDATOR_COUNT)
+        .mock_execution_layer()
+        .fresh_ephemeral_store()
+        .build();
+    let mut state = harness.get_current_state();
+    assert!(!state.fork_name_unchecked().electra_enabled());
+
+    let mut block = BeaconBlockElectra::<MainnetEthSpec, FullPayload<MainnetEthSpec>>::empty(&spec);
+    block
+        .body
+        .execution_requests
+        .deposits
+        .push(DepositRequest {
+            pubkey: PublicKeyBytes::empty(),
+            withdrawal_credentials: Hash256::zero(),
+            amount: spec.min_activation_balance,
+            signature: SignatureBytes::empty(),
+            index: 0,
+        })
+        .unwrap();
+
+    let mut ctxt = ConsensusContext::new(state.slot());
+    let result = process_operations(
+        &mut state,
+        BeaconBlockBodyRef::Electra(&block.body),
+        VerifySignatures::False,
+        DEFAULT_DEPOSIT_VERIFICATION_CHUNK_SIZE,
+        &mut ctxt,
+        &spec,
+    );
+    assert_eq!(
+        result,
+        Err(BlockProcessingError::ExecutionRequestsBeforeElectra)
+    );
+}
+
+#[tokio::test]
+async fn select_operations_skips_invalid_and_redundant_slashings() {
+    let spec = MainnetEthSpec::default_spec();
+    let harness = get_harness::<MainnetEthSpec>(EPOCH_OFFSET, VALIDATOR_COUNT).await;
+    let state = harness.get_current_state();
+
+    let proposer_slashing = harness.make_proposer_slashing(1);
+    let mut invalid_proposer_slashing = harness.make_proposer_slashing(4);
+    invalid_proposer_slashing.signed_header_1.signature = Signature::empty();
+    let redundant_attester_slashing = harness.make_attester_slashing(vec![1]);
+    let attester_slashing = harness.make_attester_slashing(vec![2, 3]);
+
+    let candidates = BlockOperations {
+        proposer_slashings: vec![
+            proposer_slashing.clone(),
+            invalid_proposer_slashing,
+            proposer_slashing.clone(),
+        ],
+        attester_slashings: vec![redundant_attester_slashing, attester_slashing.clone()],
+        ..BlockOperations::default()
+    };
+    let selected = select_operations(&state, candidates, VerifySignatures::True, &spec);
+
+    // The invalid proposer slashing and the duplicate are dropped, and the attester slashing of
+    // an already slashed validator is redundant.
+    assert_eq!(
+        selected,
+        BlockOperations {
+            proposer_slashings: vec![proposer_slashing],
+            attester_slashings: vec![attester_slashing],
+            ..BlockOperations::default()
+        }
+    );
+}
@@ -3,6 +3,7 @@ use crate::common::update_progressive_balances_cache::{
     initialize_progressive_balances_cache, update_progressive_balances_on_epoch_transition,
 };
 use crate::epoch_cache::initialize_epoch_cache;
+use crate::metrics;
 use crate::per_epoch_processing::single_pass::{process_epoch_single_pass, SinglePassConfig};
 use crate::per_epoch_processing::{
     capella::process_historical_summaries_update,
@@ -27,18 +28,25 @@ pub fn process_epoch<E: EthSpec>(
     spec: &ChainSpec,
 ) -> Result<EpochProcessingSummary<E>, Error> {
     // Ensure the required caches are built.
+    let timer = metrics::start_timer_vec(&metrics::PROCESS_EPOCH_PHASE_TIME, &["caches"]);
     state.build_committee_cache(RelativeEpoch::Previous, spec)?;
     state.build_committee_cache(RelativeEpoch::Current, spec)?;
     state.build_committee_cache(RelativeEpoch::Next, spec)?;
     state.build_total_active_balance_cache(spec)?;
     initialize_epoch_cache(state, spec)?;
     initialize_progressive_balances_cache::<E>(state, spec)?;
+    metrics::stop_timer(timer);
 
     let sync_committee = state.current_sync_committee()?.clone();
 
     // Justification and finalization.
+    let timer = metrics::start_timer_vec(
+        &metrics::PROCESS_EPOCH_PHASE_TIME,
+        &["justification_and_finalization"],
+    );
     let justification_and_finalization_state = process_justification_and_finalization(state)?;
     justification_and_finalization_state.apply_changes_to_state(state);
+    metrics::stop_timer(timer);
 
     // In a single pass:
     // - Inactivity updates
@@ -48,11 +56,13 @@ pub fn process_epoch<E: EthSpec>(
     // - Effective balance updates
     //
     // The `process_eth1_data_reset` is not covered in the single pass, but happens afterwards
-    // without loss of correctness.
+    // without loss of correctness. These phases are timed together as `single_pass`.
     let current_epoch_progressive_balances = state.progressive_balances_cache().clone();
     let current_epoch_total_active_balance = state.get_total_active_balance()?;
+    let timer = metrics::start_timer_vec(&metrics::PROCESS_EPOCH_PHASE_TIME, &["single_pass"]);
     let participation_summary =
         process_epoch_single_pass(state, spec, SinglePassConfig::default())?;
+    metrics::stop_timer(timer);
 
     // Reset eth1 data votes.
     process_eth1_data_reset(state)?;
@@ -75,7 +85,12 @@ pub fn process_epoch<E: EthSpec>(
     // Rotate current/previous epoch participation
     process_participation_flag_updates(state)?;
 
+    let timer = metrics::start_timer_vec(
+        &metrics::PROCESS_EPOCH_PHASE_TIME,
+        &["sync_committee_updates"],
+    );
     process_sync_committee_updates(state, spec)?;
+    metrics::stop_timer(timer);
 
     // Rotate the epoch caches to suit the epoch transition.
     state.advance_caches()?;
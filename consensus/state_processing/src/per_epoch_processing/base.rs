@@ -1,5 +1,6 @@
 use super::{process_registry_updates, process_slashings, EpochProcessingSummary, Error};
 use crate::epoch_cache::initialize_epoch_cache;
+use crate::metrics;
 use crate::per_epoch_processing::{
     effective_balance_updates::process_effective_balance_updates,
     historical_roots_update::process_historical_roots_update,
@@ -21,11 +22,13 @@ pub fn process_epoch<E: EthSpec>(
     spec: &ChainSpec,
 ) -> Result<EpochProcessingSummary<E>, Error> {
     // Ensure the committee caches are built.
+    let timer = metrics::start_timer_vec(&metrics::PROCESS_EPOCH_PHASE_TIME, &["caches"]);
     state.build_committee_cache(RelativeEpoch::Previous, spec)?;
     state.build_committee_cache(RelativeEpoch::Current, spec)?;
     state.build_committee_cache(RelativeEpoch::Next, spec)?;
     state.build_total_active_balance_cache(spec)?;
     initialize_epoch_cache(state, spec)?;
+    metrics::stop_timer(timer);
 
     // Load the struct we use to assign validators into sets based on their participation.
     //
@@ -34,28 +37,47 @@ pub fn process_epoch<E: EthSpec>(
     validator_statuses.process_attestations(state)?;
 
     // Justification and finalization.
+    let timer = metrics::start_timer_vec(
+        &metrics::PROCESS_EPOCH_PHASE_TIME,
+        &["justification_and_finalization"],
+    );
     let justification_and_finalization_state =
         process_justification_and_finalization(state, &validator_statuses.total_balances, spec)?;
     justification_and_finalization_state.apply_changes_to_state(state);
+    metrics::stop_timer(timer);
 
     // Rewards and Penalties.
+    let timer = metrics::start_timer_vec(
+        &metrics::PROCESS_EPOCH_PHASE_TIME,
+        &["rewards_and_penalties"],
+    );
     process_rewards_and_penalties(state, &validator_statuses, spec)?;
+    metrics::stop_timer(timer);
 
     // Registry Updates.
+    let timer = metrics::start_timer_vec(&metrics::PROCESS_EPOCH_PHASE_TIME, &["registry_updates"]);
     process_registry_updates(state, spec)?;
+    metrics::stop_timer(timer);
 
     // Slashings.
+    let timer = metrics::start_timer_vec(&metrics::PROCESS_EPOCH_PHASE_TIME, &["slashings"]);
     process_slashings(
         state,
         validator_statuses.total_balances.current_epoch(),
         spec,
     )?;
+    metrics::stop_timer(timer);
 
     // Reset eth1 data votes.
     process_eth1_data_reset(state)?;
 
     // Update effective balances with hysteresis (lag).
+    let timer = metrics::start_timer_vec(
+        &metrics::PROCESS_EPOCH_PHASE_TIME,
+        &["effective_balance_updates"],
+    );
     process_effective_balance_updates(state, spec)?;
+    metrics::stop_timer(timer);
 
     // Reset slashings
     process_slashings_reset(state)?;
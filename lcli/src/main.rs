@@ -230,6 +230,16 @@ fn main() {
                             the block.")
                         .display_order(0)
                 )
+                .arg(
+                    Arg::new("diagnose")
+                        .long("diagnose")
+                        .action(ArgAction::SetTrue)
+                        .help_heading(FLAG_HEADER)
+                        .help("If present, replay each step of block processing separately \
+                            instead of performing any runs, and report the first failing \
+                            operation along with its index in the block.")
+                        .display_order(0)
+                )
         )
         .subcommand(
             Command::new("pretty-ssz")
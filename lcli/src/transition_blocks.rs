@@ -61,6 +61,18 @@
 //!     --exclude-cache-builds \
 //!     --exclude-post-block-thc
 //! ```
+//!
+//! ### Diagnose an invalid block
+//!
+//! Replay each step of block processing separately to find the first invalid operation of a
+//! block, without performing any runs:
+//!
+//! ```ignore
+//! lcli transition-blocks \
+//!     --block-path /tmp/block-0x6c69.ssz \
+//!     --pre-state-path /tmp/pre-state-0x6c69.ssz \
+//!     --diagnose
+//! ```
 use beacon_chain::{
     test_utils::EphemeralHarnessType, validator_pubkey_cache::ValidatorPubkeyCache,
 };
@@ -72,13 +84,14 @@ use eth2::{
     BeaconNodeHttpClient, SensitiveUrl, Timeouts,
 };
 use eth2_network_config::Eth2NetworkConfig;
-use log::{debug, info};
+use log::{debug, error, info};
 use sloggers::{null::NullLoggerBuilder, Build};
 use ssz::Encode;
+use state_processing::block_diagnosis::diagnose_block;
 use state_processing::state_advance::complete_state_advance;
 use state_processing::{
     block_signature_verifier::BlockSignatureVerifier, per_block_processing, AllCaches,
-    BlockSignatureStrategy, ConsensusContext, VerifyBlockRoot,
+    BlockSignatureStrategy, ConsensusContext, VerifyBlockRoot, VerifySignatures,
 };
 use std::borrow::Cow;
 use std::fs::File;
@@ -96,6 +109,7 @@ struct Config {
     no_signature_verification: bool,
     exclude_cache_builds: bool,
     exclude_post_block_thc: bool,
+    diagnose: bool,
 }
 
 pub fn run<E: EthSpec>(
@@ -117,11 +131,16 @@ pub fn run<E: EthSpec>(
     let pre_state_output_path: Option<PathBuf> = parse_optional(matches, "pre-state-output-path")?;
     let block_output_path: Option<PathBuf> = parse_optional(matches, "block-output-path")?;
     let beacon_url: Option<SensitiveUrl> = parse_optional(matches, "beacon-url")?;
-    let runs: usize = parse_required(matches, "runs")?;
     let config = Config {
         no_signature_verification: matches.get_flag("no-signature-verification"),
         exclude_cache_builds: matches.get_flag("exclude-cache-builds"),
         exclude_post_block_thc: matches.get_flag("exclude-post-block-thc"),
+        diagnose: matches.get_flag("diagnose"),
+    };
+    let runs: usize = if config.diagnose {
+        0
+    } else {
+        parse_required(matches, "runs")?
     };
 
     info!("Using {} spec", E::spec_name());
@@ -233,6 +252,21 @@ pub fn run<E: EthSpec>(
         state_root_opt = Some(state_root);
     }
 
+    /*
+     * Diagnose the block instead of performing runs, if required.
+     */
+
+    if config.diagnose {
+        diagnose_transition(
+            pre_state.clone(),
+            block_root,
+            &block,
+            state_root_opt,
+            &config,
+            &spec,
+        )?;
+    }
+
     /*
      * Perform the core "runs".
      */
@@ -423,6 +457,54 @@ fn do_transition<E: EthSpec>(
     Ok(pre_state)
 }
 
+/// Replays each step of block processing separately, logging the outcome of each step and
+/// returning an error describing the first failing one.
+fn diagnose_transition<E: EthSpec>(
+    mut pre_state: BeaconState<E>,
+    block_root: Hash256,
+    block: &SignedBeaconBlock<E>,
+    state_root_opt: Option<Hash256>,
+    config: &Config,
+    spec: &ChainSpec,
+) -> Result<(), String> {
+    let state_root = match state_root_opt {
+        Some(state_root) => state_root,
+        None => pre_state
+            .update_tree_hash_cache()
+            .map_err(|e| format!("Unable to build tree hash cache: {:?}", e))?,
+    };
+
+    // Transition the parent state to the block slot.
+    complete_state_advance(&mut pre_state, Some(state_root), block.slot(), spec)
+        .map_err(|e| format!("Unable to perform complete advance: {e:?}"))?;
+
+    let verify_signatures = if config.no_signature_verification {
+        VerifySignatures::False
+    } else {
+        VerifySignatures::True
+    };
+    let mut ctxt = ConsensusContext::new(pre_state.slot())
+        .set_current_block_root(block_root)
+        .set_proposer_index(block.message().proposer_index());
+
+    let outcomes = diagnose_block(&pre_state, block, verify_signatures, &mut ctxt, spec);
+    for outcome in &outcomes {
+        if outcome.result.is_ok() {
+            info!("{}", outcome);
+        } else {
+            error!("{}", outcome);
+        }
+    }
+
+    match outcomes.last() {
+        Some(outcome) if outcome.result.is_err() => Err(format!("Invalid block: {}", outcome)),
+        _ => {
+            info!("Block is valid");
+            Ok(())
+        }
+    }
+}
+
 pub fn load_from_ssz_with<T>(
     path: &Path,
     spec: &ChainSpec,
@@ -20,6 +20,7 @@ use std::fs::create_dir_all;
 use std::io::{Result as IOResult, Write};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use task_executor::{ShutdownReason, TaskExecutor};
 use tokio::runtime::{Builder as RuntimeBuilder, Runtime};
 use types::{EthSpec, GnosisEthSpec, MainnetEthSpec, MinimalEthSpec};
@@ -524,6 +525,17 @@ impl<E: EthSpec> Environment<E> {
         }
     }
 
+    /// Keep the `tokio` runtime running for `grace_period` after the exit signal has fired.
+    ///
+    /// Shutting down the runtime drops all remaining tasks, so this must be called before
+    /// `Self::shutdown_on_idle` for tasks which keep running for a grace period after the exit
+    /// signal, such as the validator client metrics server.
+    pub fn wait_for_shutdown_grace_period(&self, grace_period: Duration) {
+        if !grace_period.is_zero() {
+            self.runtime.block_on(tokio::time::sleep(grace_period));
+        }
+    }
+
     /// Shutdown the `tokio` runtime when all tasks are idle.
     pub fn shutdown_on_idle(self) {
         match Arc::try_unwrap(self.runtime) {
@@ -22,6 +22,7 @@ use std::backtrace::Backtrace;
 use std::path::PathBuf;
 use std::process::exit;
 use std::sync::LazyLock;
+use std::time::Duration;
 use task_executor::ShutdownReason;
 use types::{EthSpec, EthSpecId};
 use validator_client::ProductionValidatorClient;
@@ -673,6 +674,9 @@ fn run<E: EthSpec>(
         return Ok(());
     }
 
+    // How long the runtime is kept alive after the exit signal for services still shutting down.
+    let mut shutdown_grace_period = Duration::ZERO;
+
     match LighthouseSubcommands::from_arg_matches(matches) {
         Ok(LighthouseSubcommands::DatabaseManager(db_manager_config)) => {
             info!(log, "Running database manager for {} network", network_name);
@@ -698,6 +702,10 @@ fn run<E: EthSpec>(
                 return Ok(());
             }
 
+            if config.http_metrics.enabled {
+                shutdown_grace_period = config.http_metrics.shutdown_grace_period;
+            }
+
             executor.clone().spawn(
                 async move {
                     if let Err(e) = ProductionValidatorClient::new(context, config)
@@ -785,6 +793,9 @@ fn run<E: EthSpec>(
 
     environment.fire_signal();
 
+    // Keep serving final metrics scrapes while the other services shut down.
+    environment.wait_for_shutdown_grace_period(shutdown_grace_period);
+
     // Shutdown the environment once all tasks have completed.
     environment.shutdown_on_idle();
 
@@ -802,6 +802,70 @@ fn network_target_peers_flag() {
         });
 }
 #[test]
+fn network_custody_peers_grace_period_flag() {
+    CommandLineTest::new()
+        .flag("custody-peers-grace-period", Some("5"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.network.custody_peers_grace_period,
+                Duration::from_secs(5)
+            );
+        });
+}
+#[test]
+fn network_max_parent_lookup_depth_flag() {
+    CommandLineTest::new()
+        .flag("max-parent-lookup-depth", Some("8"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.max_parent_lookup_depth, 8));
+}
+#[test]
+fn network_parent_lookup_denylist_flag() {
+    let root_1 = "0x0101010101010101010101010101010101010101010101010101010101010101";
+    let root_2 = "0x0202020202020202020202020202020202020202020202020202020202020202";
+    CommandLineTest::new()
+        .flag(
+            "parent-lookup-denylist",
+            Some(&format!("{root_1},{root_2}")),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.network.parent_lookup_denylist,
+                vec![Hash256::repeat_byte(1), Hash256::repeat_byte(2)]
+            )
+        });
+}
+#[test]
+#[should_panic]
+fn network_parent_lookup_denylist_flag_invalid() {
+    CommandLineTest::new()
+        .flag("parent-lookup-denylist", Some("0x01"))
+        .run_with_zero_port();
+}
+#[test]
+fn network_min_synced_peers_for_core_topics_flag() {
+    CommandLineTest::new()
+        .flag("min-synced-peers-for-core-topics", Some("3"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.min_synced_peers_for_core_topics, 3));
+}
+#[test]
+fn network_max_concurrent_custody_requests_flag() {
+    CommandLineTest::new()
+        .flag("max-concurrent-custody-requests", Some("4"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.max_concurrent_custody_requests, 4));
+}
+#[test]
+fn network_max_concurrent_backfill_batches_flag() {
+    CommandLineTest::new()
+        .flag("max-concurrent-backfill-batches", Some("4"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.max_concurrent_backfill_batches, 4));
+}
+#[test]
 fn network_subscribe_all_data_column_subnets_flag() {
     CommandLineTest::new()
         .flag("subscribe-all-data-column-subnets", None)
@@ -1752,6 +1816,36 @@ fn metrics_allow_origin_all_flag() {
         .run_with_zero_port()
         .with_config(|config| assert_eq!(config.http_metrics.allow_origin, Some("*".to_string())));
 }
+#[test]
+fn metrics_disable_cors_default() {
+    CommandLineTest::new()
+        .flag("metrics", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.http_metrics.cors_enabled));
+}
+#[test]
+fn metrics_disable_cors_flag() {
+    CommandLineTest::new()
+        .flag("metrics", None)
+        .flag("metrics-disable-cors", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.http_metrics.cors_enabled));
+}
+#[test]
+fn metrics_delta_mode_default() {
+    CommandLineTest::new()
+        .flag("metrics", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.http_metrics.delta_mode));
+}
+#[test]
+fn metrics_delta_mode_flag() {
+    CommandLineTest::new()
+        .flag("metrics", None)
+        .flag("metrics-delta-mode", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.http_metrics.delta_mode));
+}
 
 // Tests for Validator Monitor flags.
 #[test]
@@ -1992,6 +2086,37 @@ fn epochs_per_migration_override() {
         .with_config(|config| assert_eq!(config.chain.epochs_per_migration, 128));
 }
 #[test]
+fn deposit_verification_chunk_size_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.deposit_verification_chunk_size,
+                std::num::NonZeroUsize::MIN
+            )
+        });
+}
+#[test]
+fn deposit_verification_chunk_size_override() {
+    CommandLineTest::new()
+        .flag("deposit-verification-chunk-size", Some("4"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.deposit_verification_chunk_size.get(), 4));
+}
+#[test]
+fn persist_observed_sidecars_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.chain.persist_observed_sidecars));
+}
+#[test]
+fn persist_observed_sidecars_flag() {
+    CommandLineTest::new()
+        .flag("persist-observed-sidecars", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.chain.persist_observed_sidecars));
+}
+#[test]
 fn malicious_withhold_count_flag() {
     CommandLineTest::new()
         .flag("malicious-withhold-count", Some("128"))
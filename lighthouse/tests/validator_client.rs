@@ -2,7 +2,7 @@ use beacon_node_fallback::{beacon_node_health::BeaconNodeSyncDistanceTiers, ApiT
 
 use crate::exec::CommandLineTestExec;
 use bls::{Keypair, PublicKeyBytes};
-use initialized_validators::DEFAULT_WEB3SIGNER_KEEP_ALIVE;
+use initialized_validators::{PreferredSigningMethod, DEFAULT_WEB3SIGNER_KEEP_ALIVE};
 use sensitive_url::SensitiveUrl;
 use std::fs::File;
 use std::io::Write;
@@ -435,6 +435,63 @@ fn metrics_allow_origin_all_flag() {
         .with_config(|config| assert_eq!(config.http_metrics.allow_origin, Some("*".to_string())));
 }
 #[test]
+fn metrics_auth_token_path_flag() {
+    CommandLineTest::new()
+        .flag("metrics", None)
+        .flag("metrics-auth-token-path", Some("/tmp/metrics-token"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_metrics.auth_token_path,
+                Some(PathBuf::from("/tmp/metrics-token"))
+            )
+        });
+}
+#[test]
+fn metrics_auth_token_path_default() {
+    CommandLineTest::new()
+        .flag("metrics", None)
+        .run()
+        .with_config(|config| assert_eq!(config.http_metrics.auth_token_path, None));
+}
+#[test]
+fn metrics_shutdown_grace_period_flag() {
+    CommandLineTest::new()
+        .flag("metrics", None)
+        .flag("metrics-shutdown-grace-period", Some("30"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_metrics.shutdown_grace_period,
+                Duration::from_secs(30)
+            )
+        });
+}
+#[test]
+fn metrics_shutdown_grace_period_default() {
+    CommandLineTest::new()
+        .flag("metrics", None)
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.http_metrics.shutdown_grace_period, Duration::ZERO)
+        });
+}
+#[test]
+fn metrics_disable_compression_flag() {
+    CommandLineTest::new()
+        .flag("metrics", None)
+        .flag("metrics-disable-compression", None)
+        .run()
+        .with_config(|config| assert!(!config.http_metrics.compression_enabled));
+}
+#[test]
+fn metrics_compression_default() {
+    CommandLineTest::new()
+        .flag("metrics", None)
+        .run()
+        .with_config(|config| assert!(config.http_metrics.compression_enabled));
+}
+#[test]
 pub fn malloc_tuning_flag() {
     CommandLineTest::new()
         .flag("disable-malloc-tuning", None)
@@ -656,6 +713,24 @@ fn validator_registration_batch_size_zero_value() {
         .run();
 }
 
+#[test]
+fn signing_ledger_default() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert!(!config.validator_store.enable_signing_ledger);
+    });
+}
+
+#[test]
+fn signing_ledger_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("enable-signing-ledger", None)
+        .run()
+        .with_config(|config| {
+            assert!(config.validator_store.enable_signing_ledger);
+        });
+}
+
 #[test]
 fn validator_disable_web3_signer_slashing_protection_default() {
     CommandLineTest::new().run().with_config(|config| {
@@ -696,6 +771,56 @@ fn validator_web3_signer_keep_alive_override() {
         });
 }
 
+#[test]
+fn validator_preferred_signing_method_default() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.initialized_validators.preferred_signing_method, None);
+    });
+}
+
+#[test]
+fn validator_preferred_signing_method_local_keystore() {
+    CommandLineTest::new()
+        .flag("preferred-signing-method", Some("local-keystore"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.initialized_validators.preferred_signing_method,
+                Some(PreferredSigningMethod::LocalKeystore)
+            );
+        });
+}
+
+#[test]
+fn validator_preferred_signing_method_web3signer() {
+    CommandLineTest::new()
+        .flag("preferred-signing-method", Some("web3signer"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.initialized_validators.preferred_signing_method,
+                Some(PreferredSigningMethod::Web3Signer)
+            );
+        });
+}
+
+#[test]
+fn validator_metrics_cardinality_cap_default() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.validator_metrics_cardinality_cap, 10_000);
+    });
+}
+
+#[test]
+fn validator_metrics_cardinality_cap_override() {
+    CommandLineTest::new()
+        .flag("validator-metrics-cardinality-cap", Some("128"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.validator_metrics_cardinality_cap, 128);
+        });
+}
+
 #[test]
 fn validator_proposer_nodes_default_empty() {
     CommandLineTest::new().run().with_config(|config| {
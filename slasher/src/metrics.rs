@@ -7,6 +7,13 @@ pub static SLASHER_DATABASE_SIZE: LazyLock<Result<IntGauge>> = LazyLock::new(||
         "Size of the database backing the slasher, in bytes",
     )
 });
+pub static SLASHER_DATABASE_BACKEND: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "slasher_database_backend",
+        "Set to 1 for the database backend in use by the slasher",
+        &["backend"],
+    )
+});
 pub static SLASHER_RUN_TIME: LazyLock<Result<Histogram>> = LazyLock::new(|| {
     try_create_histogram(
         "slasher_process_batch_time",
@@ -5,8 +5,8 @@ use crate::metrics::{
     SLASHER_NUM_BLOCKS_PROCESSED,
 };
 use crate::{
-    array, AttestationBatch, AttestationQueue, AttesterRecord, BlockQueue, Config, Error,
-    IndexedAttestationId, ProposerSlashingStatus, RwTransaction, SimpleBatch, SlasherDB,
+    array, AttestationBatch, AttestationQueue, AttesterRecord, BlockQueue, Config, DatabaseBackend,
+    Error, IndexedAttestationId, ProposerSlashingStatus, RwTransaction, SimpleBatch, SlasherDB,
 };
 use parking_lot::Mutex;
 use slog::{debug, error, info, Logger};
@@ -46,6 +46,11 @@ impl<E: EthSpec> Slasher<E> {
         log: Logger,
     ) -> Result<Self, Error> {
         config.validate()?;
+        metrics::set_gauge_vec(
+            &metrics::SLASHER_DATABASE_BACKEND,
+            &[&config.backend.to_string()],
+            1,
+        );
         let attester_slashings = Mutex::new(HashSet::new());
         let proposer_slashings = Mutex::new(HashSet::new());
         let attestation_queue = AttestationQueue::default();
@@ -80,6 +85,12 @@ impl<E: EthSpec> Slasher<E> {
         &self.config
     }
 
+    /// Returns the database backend in use, which may differ from the configured one if it was
+    /// overridden to match an existing database.
+    pub fn database_backend(&self) -> DatabaseBackend {
+        self.config.backend
+    }
+
     pub fn log(&self) -> &Logger {
         &self.log
     }
@@ -1,6 +1,11 @@
 #![cfg(feature = "lmdb")]
 
-use slasher::{config::MDBX_DATA_FILENAME, Config, DatabaseBackend, DatabaseBackendOverride};
+use logging::test_logger;
+use slasher::{
+    config::MDBX_DATA_FILENAME,
+    test_utils::{chain_spec, E},
+    Config, DatabaseBackend, DatabaseBackendOverride, Slasher,
+};
 use std::fs::File;
 use tempfile::tempdir;
 
@@ -55,3 +60,13 @@ fn failed_override_with_existing_mdbx_db() {
     );
     assert_eq!(config.backend, DatabaseBackend::Lmdb);
 }
+
+#[test]
+fn open_slasher_reports_backend() {
+    let tempdir = tempdir().unwrap();
+    let mut config = Config::new(tempdir.path().into());
+    config.backend = DatabaseBackend::Lmdb;
+
+    let slasher = Slasher::<E>::open(config, chain_spec(), test_logger()).unwrap();
+    assert_eq!(slasher.database_backend(), DatabaseBackend::Lmdb);
+}
@@ -2,6 +2,8 @@ use super::*;
 use rayon::prelude::*;
 use std::fmt::{Debug, Display, Formatter};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::LazyLock;
 use types::ForkName;
 
 mod bls_aggregate_sigs;
@@ -110,6 +112,17 @@ impl FeatureName {
     }
 }
 
+impl FromStr for FeatureName {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fulu" => Ok(FeatureName::Fulu),
+            other => Err(format!("unknown feature name {other:?}")),
+        }
+    }
+}
+
 impl Display for FeatureName {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -154,6 +167,24 @@ pub trait Case: Debug + Sync {
     fn result(&self, case_index: usize, fork_name: ForkName) -> Result<(), Error>;
 }
 
+/// Environment variable capping the number of threads used to run test cases in parallel.
+pub const MAX_THREADS_ENV_VAR: &str = "EF_TESTS_MAX_THREADS";
+
+/// A bounded thread pool shared by all test runs if `MAX_THREADS_ENV_VAR` is set, otherwise the
+/// global rayon thread pool is used.
+static THREAD_POOL: LazyLock<Option<rayon::ThreadPool>> = LazyLock::new(|| {
+    let max_threads = std::env::var(MAX_THREADS_ENV_VAR).ok()?;
+    let num_threads = max_threads
+        .parse::<usize>()
+        .unwrap_or_else(|e| panic!("invalid {MAX_THREADS_ENV_VAR} value {max_threads:?}: {e}"));
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .thread_name(|i| format!("ef-tests-{i}"))
+        .build()
+        .unwrap_or_else(|e| panic!("failed to build ef tests thread pool: {e}"));
+    Some(pool)
+});
+
 #[derive(Debug)]
 pub struct Cases<T> {
     pub test_cases: Vec<(PathBuf, T)>,
@@ -162,13 +193,19 @@ pub struct Cases<T> {
 impl<T: Case> Cases<T> {
     pub fn test_results(&self, fork_name: ForkName, use_rayon: bool) -> Vec<CaseResult> {
         if use_rayon {
-            self.test_cases
-                .into_par_iter()
-                .enumerate()
-                .map(|(i, (ref path, ref tc))| {
-                    CaseResult::new(i, path, tc, tc.result(i, fork_name))
-                })
-                .collect()
+            let run = || {
+                self.test_cases
+                    .into_par_iter()
+                    .enumerate()
+                    .map(|(i, (ref path, ref tc))| {
+                        CaseResult::new(i, path, tc, tc.result(i, fork_name))
+                    })
+                    .collect::<Vec<_>>()
+            };
+            match THREAD_POOL.as_ref() {
+                Some(pool) => pool.install(run),
+                None => run(),
+            }
         } else {
             self.test_cases
                 .iter()
@@ -63,6 +63,12 @@ pub trait Operation<E: EthSpec>: Debug + Sync + Sized {
         format!("{}.ssz_snappy", Self::handler_name())
     }
 
+    /// The first fork in which the operation exists. Tests are neither run nor expected to exist
+    /// for earlier forks.
+    fn min_fork() -> ForkName {
+        ForkName::Base
+    }
+
     fn is_enabled_for_fork(_fork_name: ForkName) -> bool {
         true
     }
@@ -261,8 +267,8 @@ impl<E: EthSpec> Operation<E> for SyncAggregate<E> {
         "sync_aggregate.ssz_snappy".into()
     }
 
-    fn is_enabled_for_fork(fork_name: ForkName) -> bool {
-        fork_name.altair_enabled()
+    fn min_fork() -> ForkName {
+        ForkName::Altair
     }
 
     fn decode(path: &Path, _fork_name: ForkName, _spec: &ChainSpec) -> Result<Self, Error> {
@@ -289,8 +295,8 @@ impl<E: EthSpec> Operation<E> for BeaconBlockBody<E, FullPayload<E>> {
         "body.ssz_snappy".into()
     }
 
-    fn is_enabled_for_fork(fork_name: ForkName) -> bool {
-        fork_name.bellatrix_enabled()
+    fn min_fork() -> ForkName {
+        ForkName::Bellatrix
     }
 
     fn decode(path: &Path, fork_name: ForkName, _spec: &ChainSpec) -> Result<Self, Error> {
@@ -332,8 +338,8 @@ impl<E: EthSpec> Operation<E> for BeaconBlockBody<E, BlindedPayload<E>> {
         "body.ssz_snappy".into()
     }
 
-    fn is_enabled_for_fork(fork_name: ForkName) -> bool {
-        fork_name.bellatrix_enabled()
+    fn min_fork() -> ForkName {
+        ForkName::Bellatrix
     }
 
     fn decode(path: &Path, fork_name: ForkName, _spec: &ChainSpec) -> Result<Self, Error> {
@@ -392,8 +398,8 @@ impl<E: EthSpec> Operation<E> for WithdrawalsPayload<E> {
         "execution_payload.ssz_snappy".into()
     }
 
-    fn is_enabled_for_fork(fork_name: ForkName) -> bool {
-        fork_name.capella_enabled()
+    fn min_fork() -> ForkName {
+        ForkName::Capella
     }
 
     fn decode(path: &Path, fork_name: ForkName, _spec: &ChainSpec) -> Result<Self, Error> {
@@ -424,8 +430,8 @@ impl<E: EthSpec> Operation<E> for SignedBlsToExecutionChange {
         "address_change.ssz_snappy".into()
     }
 
-    fn is_enabled_for_fork(fork_name: ForkName) -> bool {
-        fork_name.capella_enabled()
+    fn min_fork() -> ForkName {
+        ForkName::Capella
     }
 
     fn decode(path: &Path, _fork_name: ForkName, _spec: &ChainSpec) -> Result<Self, Error> {
@@ -447,8 +453,8 @@ impl<E: EthSpec> Operation<E> for WithdrawalRequest {
         "withdrawal_request".into()
     }
 
-    fn is_enabled_for_fork(fork_name: ForkName) -> bool {
-        fork_name.electra_enabled()
+    fn min_fork() -> ForkName {
+        ForkName::Electra
     }
 
     fn decode(path: &Path, _fork_name: ForkName, _spec: &ChainSpec) -> Result<Self, Error> {
@@ -471,8 +477,8 @@ impl<E: EthSpec> Operation<E> for DepositRequest {
         "deposit_request".into()
     }
 
-    fn is_enabled_for_fork(fork_name: ForkName) -> bool {
-        fork_name.electra_enabled()
+    fn min_fork() -> ForkName {
+        ForkName::Electra
     }
 
     fn decode(path: &Path, _fork_name: ForkName, _spec: &ChainSpec) -> Result<Self, Error> {
@@ -494,8 +500,8 @@ impl<E: EthSpec> Operation<E> for ConsolidationRequest {
         "consolidation_request".into()
     }
 
-    fn is_enabled_for_fork(fork_name: ForkName) -> bool {
-        fork_name.electra_enabled()
+    fn min_fork() -> ForkName {
+        ForkName::Electra
     }
 
     fn decode(path: &Path, _fork_name: ForkName, _spec: &ChainSpec) -> Result<Self, Error> {
@@ -509,7 +515,7 @@ impl<E: EthSpec> Operation<E> for ConsolidationRequest {
         _extra: &Operations<E, Self>,
     ) -> Result<(), BlockProcessingError> {
         state.update_pubkey_cache()?;
-        process_consolidation_requests(state, &[self.clone()], spec)
+        process_consolidation_requests(state, &[self.clone()], spec).map(|_| ())
     }
 }
 
@@ -570,7 +576,7 @@ impl<E: EthSpec, O: Operation<E>> Case for Operations<E, O> {
     }
 
     fn is_enabled_for_fork(fork_name: ForkName) -> bool {
-        O::is_enabled_for_fork(fork_name)
+        fork_name >= O::min_fork() && O::is_enabled_for_fork(fork_name)
     }
 
     fn result(&self, _case_index: usize, fork_name: ForkName) -> Result<(), Error> {
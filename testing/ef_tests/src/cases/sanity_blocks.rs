@@ -54,6 +54,14 @@ impl<E: EthSpec> LoadCase for SanityBlocks<E> {
     }
 }
 
+impl<E: EthSpec> SanityBlocks<E> {
+    fn contains_deposits(&self) -> bool {
+        self.blocks
+            .iter()
+            .any(|block| !block.message().body().deposits().is_empty())
+    }
+}
+
 impl<E: EthSpec> Case for SanityBlocks<E> {
     fn description(&self) -> String {
         self.metadata.description.clone().unwrap_or_default()
@@ -62,6 +70,13 @@ impl<E: EthSpec> Case for SanityBlocks<E> {
     fn result(&self, _case_index: usize, fork_name: ForkName) -> Result<(), Error> {
         self.metadata.bls_setting.unwrap_or_default().check()?;
 
+        // Deposits with invalid signatures are valid but ignored, so the post-state of base cases
+        // depends on real signature verification even though they are not marked as requiring it.
+        if cfg!(feature = "fake_crypto") && fork_name == ForkName::Base && self.contains_deposits()
+        {
+            return Err(Error::SkippedBls);
+        }
+
         let mut bulk_state = self.pre.clone();
         let mut expected = self.post.clone();
         let spec = &testing_spec::<E>(fork_name);
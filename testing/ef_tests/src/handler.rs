@@ -5,7 +5,53 @@ use derivative::Derivative;
 use std::fs::{self, DirEntry};
 use std::marker::PhantomData;
 use std::path::PathBuf;
-use types::{BeaconState, EthSpec, ForkName};
+use std::str::FromStr;
+use std::sync::LazyLock;
+use types::{BeaconState, EthSpec, ForkName, MainnetEthSpec, MinimalEthSpec};
+
+/// Environment variable restricting the test runs to a single fork, e.g. `EF_TESTS_FORK=electra`.
+pub const FORK_FILTER_ENV_VAR: &str = "EF_TESTS_FORK";
+
+/// The fork requested via `FORK_FILTER_ENV_VAR`, if any.
+static FORK_FILTER: LazyLock<Option<ForkName>> = LazyLock::new(|| {
+    let fork_name = std::env::var(FORK_FILTER_ENV_VAR).ok()?;
+    let fork_name = ForkName::from_str(&fork_name)
+        .unwrap_or_else(|e| panic!("invalid {FORK_FILTER_ENV_VAR} value: {e}"));
+    Some(fork_name)
+});
+
+/// Returns `true` if tests for `fork_name` should run according to `FORK_FILTER_ENV_VAR`.
+pub fn is_fork_selected(fork_name: ForkName) -> bool {
+    FORK_FILTER.is_none_or(|selected| selected == fork_name)
+}
+
+/// Environment variable overriding the features for which SSZ static tests are run, as a
+/// comma-separated list, e.g. `EF_TESTS_SSZ_STATIC_FEATURES=fulu`. An empty value disables them.
+pub const SSZ_STATIC_FEATURES_ENV_VAR: &str = "EF_TESTS_SSZ_STATIC_FEATURES";
+
+/// The features for which SSZ static tests are run if `SSZ_STATIC_FEATURES_ENV_VAR` is not set.
+pub const DEFAULT_SSZ_STATIC_FEATURES: &[FeatureName] = &[FeatureName::Fulu];
+
+/// The features for which SSZ static tests are run.
+static SSZ_STATIC_FEATURES: LazyLock<Vec<FeatureName>> = LazyLock::new(|| {
+    let Ok(features) = std::env::var(SSZ_STATIC_FEATURES_ENV_VAR) else {
+        return DEFAULT_SSZ_STATIC_FEATURES.to_vec();
+    };
+    features
+        .split(',')
+        .map(str::trim)
+        .filter(|feature_name| !feature_name.is_empty())
+        .map(|feature_name| {
+            FeatureName::from_str(feature_name)
+                .unwrap_or_else(|e| panic!("invalid {SSZ_STATIC_FEATURES_ENV_VAR} value: {e}"))
+        })
+        .collect()
+});
+
+/// Returns `true` if SSZ static tests should run for `feature_name`.
+pub fn is_ssz_static_feature_enabled(feature_name: FeatureName) -> bool {
+    SSZ_STATIC_FEATURES.contains(&feature_name)
+}
 
 pub trait Handler {
     type Case: Case + LoadCase;
@@ -34,11 +80,19 @@ pub trait Handler {
 
     fn run(&self) {
         for fork_name in ForkName::list_all() {
-            if !self.disabled_forks().contains(&fork_name) && self.is_enabled_for_fork(fork_name) {
+            if !self.disabled_forks().contains(&fork_name)
+                && self.is_enabled_for_fork(fork_name)
+                && is_fork_selected(fork_name)
+            {
                 self.run_for_fork(fork_name);
             }
         }
 
+        // Feature tests don't belong to any fork, skip them if a single fork is requested.
+        if FORK_FILTER.is_some() {
+            return;
+        }
+
         // Run feature tests for future forks that are not yet added to `ForkName`.
         // This runs tests in the directory named by the feature instead of the fork name.
         // e.g. consensus-spec-tests/tests/general/[feature_name]/[runner_name]
@@ -369,7 +423,7 @@ where
         // SszStaticHandler::<AttestationBase<MainnetEthSpec>, MainnetEthSpec>::pre_electra().run();
         // SszStaticHandler::<AttestationElectra<MainnetEthSpec>, MainnetEthSpec>::electra_only().run();
         // ```
-        feature_name == FeatureName::Fulu
+        is_ssz_static_feature_enabled(feature_name)
             && self.supported_forks.contains(&feature_name.fork_name())
     }
 }
@@ -393,7 +447,7 @@ where
     }
 
     fn is_enabled_for_feature(&self, feature_name: FeatureName) -> bool {
-        feature_name == FeatureName::Fulu
+        is_ssz_static_feature_enabled(feature_name)
     }
 }
 
@@ -418,7 +472,7 @@ where
     }
 
     fn is_enabled_for_feature(&self, feature_name: FeatureName) -> bool {
-        feature_name == FeatureName::Fulu
+        is_ssz_static_feature_enabled(feature_name)
     }
 }
 
@@ -465,10 +519,10 @@ impl<E: EthSpec + TypeName> Handler for SanityBlocksHandler<E> {
         "blocks".into()
     }
 
-    fn is_enabled_for_fork(&self, _fork_name: ForkName) -> bool {
-        // NOTE: v1.1.0-beta.4 doesn't mark the historical blocks test as requiring real crypto, so
-        // only run these tests with real crypto for now.
-        cfg!(not(feature = "fake_crypto"))
+    fn is_enabled_for_fork(&self, fork_name: ForkName) -> bool {
+        // Later forks compute sync committees, which requires real crypto. Base cases that depend
+        // on signatures without being marked as such are skipped by the case itself.
+        fork_name == ForkName::Base || cfg!(not(feature = "fake_crypto"))
     }
 }
 
@@ -923,6 +977,18 @@ impl<E: EthSpec + TypeName> Handler for ComputeColumnsForCustodyGroupHandler<E>
     }
 }
 
+/// Runs the PeerDAS networking tests under every `EthSpec` preset. The custody group and column
+/// mappings depend on the preset, so running a single preset may miss preset-specific bugs.
+pub fn run_networking_tests_for_all_presets() {
+    run_networking_tests::<MainnetEthSpec>();
+    run_networking_tests::<MinimalEthSpec>();
+}
+
+fn run_networking_tests<E: EthSpec + TypeName>() {
+    GetCustodyGroupsHandler::<E>::default().run();
+    ComputeColumnsForCustodyGroupHandler::<E>::default().run();
+}
+
 #[derive(Derivative)]
 #[derivative(Default(bound = ""))]
 pub struct KZGComputeCellsAndKZGProofHandler<E>(PhantomData<E>);
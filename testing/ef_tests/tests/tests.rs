@@ -973,13 +973,6 @@ fn rewards() {
 }
 
 #[test]
-fn get_custody_groups() {
-    GetCustodyGroupsHandler::<MainnetEthSpec>::default().run();
-    GetCustodyGroupsHandler::<MinimalEthSpec>::default().run()
-}
-
-#[test]
-fn compute_columns_for_custody_group() {
-    ComputeColumnsForCustodyGroupHandler::<MainnetEthSpec>::default().run();
-    ComputeColumnsForCustodyGroupHandler::<MinimalEthSpec>::default().run();
+fn networking() {
+    run_networking_tests_for_all_presets();
 }
@@ -4,17 +4,23 @@
 
 use beacon_node::ProductionBeaconNode;
 use environment::RuntimeContext;
+use eth2::lighthouse_vc::http_client::ValidatorClientHttpClient;
 use eth2::{reqwest::ClientBuilder, BeaconNodeHttpClient, Timeouts};
+use eth2_network_config::{Eth2NetworkConfig, GenesisStateSource};
+use kzg::trusted_setup::get_trusted_setup;
 use sensitive_url::SensitiveUrl;
+use ssz::Encode;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tempfile::{Builder as TempBuilder, TempDir};
 use tokio::time::timeout;
-use types::EthSpec;
+use types::{BeaconState, Config, EthSpec};
 use validator_client::ProductionValidatorClient;
 use validator_dir::insecure_keys::build_deterministic_validator_dirs;
+use validator_http_api::ApiSecret;
 
 pub use beacon_node::{ClientConfig, ClientGenesis, ProductionClient};
 pub use beacon_node_fallback::ApiTopic;
@@ -27,8 +33,8 @@ pub use validator_client::Config as ValidatorConfig;
 
 /// The global timeout for HTTP requests to the beacon node.
 const HTTP_TIMEOUT: Duration = Duration::from_secs(8);
-/// The timeout for a beacon node to start up.
-const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+/// The default timeout for a beacon node to start up.
+const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
 
 /// Provides a beacon node that is running in the current process on a given tokio executor (it
 /// is _local_ to this process).
@@ -45,7 +51,48 @@ impl<E: EthSpec> LocalBeaconNode<E> {
     /// The node created is using the same types as the node we use in production.
     pub async fn production(
         context: RuntimeContext<E>,
+        client_config: ClientConfig,
+    ) -> Result<Self, String> {
+        Self::new(context, client_config, None, DEFAULT_STARTUP_TIMEOUT).await
+    }
+
+    /// Starts a new, production beacon node as per `Self::production`, failing if it does not
+    /// start up within `startup_timeout` rather than the default of 60 seconds.
+    pub async fn production_with_timeout(
+        context: RuntimeContext<E>,
+        client_config: ClientConfig,
+        startup_timeout: Duration,
+    ) -> Result<Self, String> {
+        Self::new(context, client_config, None, startup_timeout).await
+    }
+
+    /// Starts a new, production beacon node as per `Self::production`, starting from
+    /// `genesis_state` if it is provided.
+    ///
+    /// The genesis state is provided to the node through the network config of `context` and the
+    /// node is started from it with `ClientGenesis::GenesisState`, overriding
+    /// `client_config.genesis`. Since the genesis
+    /// state may be arbitrarily old, genesis sync is allowed outside of the blob availability
+    /// window.
+    pub async fn production_with_genesis_state(
+        context: RuntimeContext<E>,
+        client_config: ClientConfig,
+        genesis_state: Option<Arc<BeaconState<E>>>,
+    ) -> Result<Self, String> {
+        Self::new(
+            context,
+            client_config,
+            genesis_state,
+            DEFAULT_STARTUP_TIMEOUT,
+        )
+        .await
+    }
+
+    async fn new(
+        mut context: RuntimeContext<E>,
         mut client_config: ClientConfig,
+        genesis_state: Option<Arc<BeaconState<E>>>,
+        startup_timeout: Duration,
     ) -> Result<Self, String> {
         // Creates a temporary directory that will be deleted once this `TempDir` is dropped.
         let datadir = TempBuilder::new()
@@ -56,12 +103,36 @@ impl<E: EthSpec> LocalBeaconNode<E> {
         client_config.set_data_dir(datadir.path().into());
         client_config.network.network_dir = PathBuf::from(datadir.path()).join("network");
 
+        if let Some(genesis_state) = genesis_state {
+            let genesis_state_bytes = genesis_state.as_ssz_bytes();
+
+            // The genesis state is obtained from the network config, create one matching the spec
+            // of the context if it does not have one.
+            let mut eth2_network_config = match &context.eth2_network_config {
+                Some(eth2_network_config) => Eth2NetworkConfig::clone(eth2_network_config),
+                None => Eth2NetworkConfig {
+                    deposit_contract_deploy_block: 0,
+                    boot_enr: None,
+                    genesis_state_source: GenesisStateSource::IncludedBytes,
+                    genesis_state_bytes: None,
+                    config: Config::from_chain_spec::<E>(&context.eth2_config.spec),
+                    kzg_trusted_setup: get_trusted_setup(),
+                },
+            };
+            eth2_network_config.genesis_state_source = GenesisStateSource::IncludedBytes;
+            eth2_network_config.genesis_state_bytes = Some(genesis_state_bytes.into());
+            context.eth2_network_config = Some(Arc::new(eth2_network_config));
+
+            client_config.genesis = ClientGenesis::GenesisState;
+            client_config.allow_insecure_genesis_sync = true;
+        }
+
         timeout(
-            STARTUP_TIMEOUT,
+            startup_timeout,
             ProductionBeaconNode::new(context, client_config),
         )
         .await
-        .map_err(|_| format!("Beacon node startup timed out after {:?}", STARTUP_TIMEOUT))?
+        .map_err(|_| format!("Beacon node startup timed out after {:?}", startup_timeout))?
         .map(move |client| Self {
             client: client.into_inner(),
             datadir,
@@ -230,6 +301,40 @@ impl<E: EthSpec> LocalValidatorClient<E> {
     }
 }
 
+impl<E: EthSpec> LocalValidatorClient<E> {
+    /// Returns the address the HTTP API server of `self` is listening on, or `None` if the server
+    /// is disabled.
+    pub fn http_api_listen_addr(&self) -> Option<SocketAddr> {
+        self.client.http_api_listen_addr()
+    }
+
+    /// Returns a `ValidatorClientHttpClient` that can connect to the keymanager API of `self`.
+    ///
+    /// Returns `Ok(None)` if the HTTP API server is disabled.
+    pub fn remote_keymanager_client(&self) -> Result<Option<ValidatorClientHttpClient>, String> {
+        let Some(listen_addr) = self.http_api_listen_addr() else {
+            return Ok(None);
+        };
+
+        let validator_client_url: SensitiveUrl = SensitiveUrl::parse(
+            format!("http://{}:{}", listen_addr.ip(), listen_addr.port()).as_str(),
+        )
+        .map_err(|e| format!("Unable to parse validator client URL: {:?}", e))?;
+        let api_token = ApiSecret::create_or_open(self.client.http_api_token_path())?.api_token();
+        let validator_client_http_client = ClientBuilder::new()
+            .timeout(HTTP_TIMEOUT)
+            .build()
+            .map_err(|e| format!("Unable to build HTTP client: {:?}", e))?;
+        ValidatorClientHttpClient::from_components(
+            validator_client_url,
+            validator_client_http_client,
+            api_token,
+        )
+        .map(Some)
+        .map_err(|e| format!("Unable to build validator client HTTP client: {:?}", e))
+    }
+}
+
 /// Provides an execution engine api server that is running in the current process on a given tokio executor (it
 /// is _local_ to this process).
 ///
@@ -23,7 +23,7 @@ mod tests {
     use eth2_keystore::KeystoreBuilder;
     use eth2_network_config::Eth2NetworkConfig;
     use initialized_validators::{
-        load_pem_certificate, load_pkcs12_identity, InitializedValidators,
+        load_pem_certificate, load_pkcs12_identity, InitializedValidators, PreferredSigningMethod,
     };
     use logging::test_logger;
     use parking_lot::Mutex;
@@ -294,6 +294,48 @@ mod tests {
             }
         }
 
+        /// Returns a definition of the validator which signs with the keystore of `self`.
+        pub fn local_keystore_definition(&self) -> ValidatorDefinition {
+            ValidatorDefinition {
+                enabled: true,
+                voting_public_key: self.keypair.pk.clone(),
+                graffiti: None,
+                suggested_fee_recipient: None,
+                gas_limit: None,
+                builder_proposals: None,
+                builder_boost_factor: None,
+                prefer_builder_proposals: None,
+                description: String::default(),
+                signing_definition: SigningDefinition::LocalKeystore {
+                    voting_keystore_path: self.keystore_path.clone(),
+                    voting_keystore_password_path: None,
+                    voting_keystore_password: Some(KEYSTORE_PASSWORD.to_string().into()),
+                },
+            }
+        }
+
+        /// Returns a definition of the validator which signs with the Web3Signer of `self`.
+        pub fn web3signer_definition(&self) -> ValidatorDefinition {
+            ValidatorDefinition {
+                enabled: true,
+                voting_public_key: self.keypair.pk.clone(),
+                graffiti: None,
+                suggested_fee_recipient: None,
+                gas_limit: None,
+                builder_proposals: None,
+                builder_boost_factor: None,
+                prefer_builder_proposals: None,
+                description: String::default(),
+                signing_definition: SigningDefinition::Web3Signer(Web3SignerDefinition {
+                    url: self.url.to_string(),
+                    root_certificate_path: Some(root_certificate_path()),
+                    request_timeout_ms: None,
+                    client_identity_path: Some(client_identity_path()),
+                    client_identity_password: Some(client_identity_password()),
+                }),
+            }
+        }
+
         pub async fn upcheck(&self) -> Result<(), ()> {
             let url = self.url.join("upcheck").unwrap();
             self.http_client
@@ -420,24 +462,8 @@ mod tests {
             let validator_pubkey = signer_rig.keypair.pk.clone();
 
             let local_signer_validator_store = {
-                let validator_definition = ValidatorDefinition {
-                    enabled: true,
-                    voting_public_key: validator_pubkey.clone(),
-                    graffiti: None,
-                    suggested_fee_recipient: None,
-                    gas_limit: None,
-                    builder_proposals: None,
-                    builder_boost_factor: None,
-                    prefer_builder_proposals: None,
-                    description: String::default(),
-                    signing_definition: SigningDefinition::LocalKeystore {
-                        voting_keystore_path: signer_rig.keystore_path.clone(),
-                        voting_keystore_password_path: None,
-                        voting_keystore_password: Some(KEYSTORE_PASSWORD.to_string().into()),
-                    },
-                };
                 ValidatorStoreRig::new(
-                    vec![validator_definition],
+                    vec![signer_rig.local_keystore_definition()],
                     slashing_protection_config,
                     false,
                     spec.clone(),
@@ -446,26 +472,8 @@ mod tests {
             };
 
             let remote_signer_validator_store = {
-                let validator_definition = ValidatorDefinition {
-                    enabled: true,
-                    voting_public_key: validator_pubkey.clone(),
-                    graffiti: None,
-                    suggested_fee_recipient: None,
-                    gas_limit: None,
-                    builder_proposals: None,
-                    builder_boost_factor: None,
-                    prefer_builder_proposals: None,
-                    description: String::default(),
-                    signing_definition: SigningDefinition::Web3Signer(Web3SignerDefinition {
-                        url: signer_rig.url.to_string(),
-                        root_certificate_path: Some(root_certificate_path()),
-                        request_timeout_ms: None,
-                        client_identity_path: Some(client_identity_path()),
-                        client_identity_password: Some(client_identity_password()),
-                    }),
-                };
                 ValidatorStoreRig::new(
-                    vec![validator_definition],
+                    vec![signer_rig.web3signer_definition()],
                     slashing_protection_config,
                     true,
                     spec,
@@ -898,6 +906,52 @@ mod tests {
         .await;
     }
 
+    /// Test that the preferred signing method is used for a validator which is defined by both a
+    /// local keystore and a Web3Signer, regardless of the order of the definitions.
+    async fn test_preferred_signing_method(preferred: PreferredSigningMethod, listen_port: u16) {
+        let signer_rig =
+            Web3SignerRig::new("mainnet", WEB3SIGNER_LISTEN_ADDRESS, listen_port).await;
+        let validator_pubkey = PublicKeyBytes::from(&signer_rig.keypair.pk);
+
+        let definition_orders = [
+            vec![
+                signer_rig.local_keystore_definition(),
+                signer_rig.web3signer_definition(),
+            ],
+            vec![
+                signer_rig.web3signer_definition(),
+                signer_rig.local_keystore_definition(),
+            ],
+        ];
+
+        for validator_definitions in definition_orders {
+            let validator_dir = TempDir::new().unwrap();
+            let config = initialized_validators::Config {
+                preferred_signing_method: Some(preferred),
+                ..Default::default()
+            };
+            let initialized_validators = InitializedValidators::from_definitions(
+                ValidatorDefinitions::from(validator_definitions),
+                validator_dir.path().into(),
+                config,
+                test_logger(),
+            )
+            .await
+            .unwrap();
+
+            // Only validators signing with a local keystore hold a lockfile.
+            let uses_local_keystore = initialized_validators
+                .validator(&validator_pubkey)
+                .unwrap()
+                .keystore_lockfile()
+                .is_some();
+            assert_eq!(
+                uses_local_keystore,
+                preferred == PreferredSigningMethod::LocalKeystore
+            );
+        }
+    }
+
     #[tokio::test]
     async fn mainnet_base_types() {
         test_base_types("mainnet", 4242).await
@@ -943,4 +997,14 @@ mod tests {
     async fn slashing_protection_enabled_locally() {
         test_lighthouse_slashing_protection(SlashingProtectionConfig { local: true }, 4254).await
     }
+
+    #[tokio::test]
+    async fn preferred_signing_method_local_keystore() {
+        test_preferred_signing_method(PreferredSigningMethod::LocalKeystore, 4255).await
+    }
+
+    #[tokio::test]
+    async fn preferred_signing_method_web3signer() {
+        test_preferred_signing_method(PreferredSigningMethod::Web3Signer, 4256).await
+    }
 }
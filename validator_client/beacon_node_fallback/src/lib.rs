@@ -25,7 +25,9 @@ use std::vec::Vec;
 use strum::EnumVariantNames;
 use tokio::{sync::RwLock, time::sleep};
 use types::{ChainSpec, Config as ConfigSpec, EthSpec, Slot};
-use validator_metrics::{inc_counter_vec, ENDPOINT_ERRORS, ENDPOINT_REQUESTS};
+use validator_metrics::{
+    inc_counter_vec, set_gauge_vec, ENDPOINT_ERRORS, ENDPOINT_REQUESTS, VC_BEACON_NODE_SYNCED,
+};
 
 /// Message emitted when the VC detects the BN is using a different spec.
 const UPDATE_REQUIRED_LOG_HINT: &str = "this VC or the remote BN may need updating";
@@ -510,6 +512,19 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
             }
         }
 
+        for candidate in candidates.iter() {
+            let is_synced = candidate.health().await.is_ok_and(|health| {
+                self.distance_tiers
+                    .compute_distance_tier(health.health_tier.sync_distance)
+                    == SyncDistanceTier::Synced
+            });
+            set_gauge_vec(
+                &VC_BEACON_NODE_SYNCED,
+                &[&candidate.beacon_node.to_string()],
+                is_synced as i64,
+            );
+        }
+
         drop(candidates);
 
         let mut candidates = self.candidates.write().await;
@@ -969,6 +984,41 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn update_all_candidates_should_set_synced_metric() {
+        let spec = Arc::new(MainnetEthSpec::default_spec());
+        let (mut mock_beacon_node_1, beacon_node_1) = new_mock_beacon_node(0, &spec).await;
+        // The second node does not serve `node/syncing` and is therefore unavailable.
+        let (_mock_beacon_node_2, beacon_node_2) = new_mock_beacon_node(1, &spec).await;
+        let synced_gauge = |candidate: &CandidateBeaconNode<E>| {
+            validator_metrics::get_int_gauge(
+                &VC_BEACON_NODE_SYNCED,
+                &[&candidate.beacon_node.to_string()],
+            )
+            .map(|gauge| gauge.get())
+        };
+
+        let beacon_node_fallback = create_beacon_node_fallback(
+            vec![beacon_node_1.clone(), beacon_node_2.clone()],
+            vec![],
+            spec.clone(),
+            test_logger(),
+        );
+
+        mock_beacon_node_1.mock_get_node_syncing(eth2::types::SyncingData {
+            is_syncing: false,
+            is_optimistic: false,
+            el_offline: false,
+            head_slot: Slot::new(1),
+            sync_distance: Slot::new(0),
+        });
+
+        beacon_node_fallback.update_all_candidates().await;
+
+        assert_eq!(synced_gauge(&beacon_node_1), Some(1));
+        assert_eq!(synced_gauge(&beacon_node_2), Some(0));
+    }
+
     #[tokio::test]
     async fn broadcast_should_send_to_all_bns() {
         let spec = Arc::new(MainnetEthSpec::default_spec());
@@ -49,10 +49,14 @@ use sysinfo::{System, SystemExt};
 use system_health::observe_system_health_vc;
 use task_executor::TaskExecutor;
 use tokio_stream::{wrappers::BroadcastStream, StreamExt};
-use types::{ChainSpec, ConfigAndPreset, EthSpec};
+use types::{ChainSpec, ConfigAndPreset, Epoch, EthSpec};
 use validator_dir::Builder as ValidatorDirBuilder;
 use validator_services::block_service::BlockService;
+use validator_services::duties_service::{
+    refresh_validator_duties, DutiesService, Error as DutiesServiceError,
+};
 use warp::{sse::Event, Filter};
+use warp_utils::reject::convert_rejection;
 use warp_utils::task::blocking_json_task;
 
 #[derive(Debug)]
@@ -80,6 +84,7 @@ pub struct Context<T: SlotClock, E: EthSpec> {
     pub task_executor: TaskExecutor,
     pub api_secret: ApiSecret,
     pub block_service: Option<BlockService<T, E>>,
+    pub duties_service: Option<Arc<DutiesService<T, E>>>,
     pub validator_store: Option<Arc<ValidatorStore<T, E>>>,
     pub validator_dir: Option<PathBuf>,
     pub secrets_dir: Option<PathBuf>,
@@ -197,6 +202,17 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    let inner_duties_service = ctx.duties_service.clone();
+    let duties_service_filter = warp::any()
+        .map(move || inner_duties_service.clone())
+        .and_then(|duties_service: Option<_>| async move {
+            duties_service.ok_or_else(|| {
+                warp_utils::reject::custom_not_found(
+                    "duties service is not initialized.".to_string(),
+                )
+            })
+        });
+
     let inner_validator_store = ctx.validator_store.clone();
     let validator_store_filter = warp::any()
         .map(move || inner_validator_store.clone())
@@ -375,6 +391,49 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             },
         );
 
+    // GET lighthouse/signing_ledger/{epoch}
+    let get_lighthouse_signing_ledger = warp::path("lighthouse")
+        .and(warp::path("signing_ledger"))
+        .and(warp::path::param::<Epoch>())
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .then(|epoch: Epoch, validator_store: Arc<ValidatorStore<T, E>>| {
+            blocking_json_task(move || {
+                let mut signings = validator_store.signings_at_epoch(epoch).ok_or_else(|| {
+                    warp_utils::reject::custom_not_found(
+                        "signing ledger is disabled, enable it with --enable-signing-ledger"
+                            .to_string(),
+                    )
+                })?;
+
+                // List every enabled validator, including those which signed nothing.
+                let mut validators = validator_store
+                    .initialized_validators()
+                    .read()
+                    .iter_voting_pubkeys()
+                    .map(|pubkey| (*pubkey, signings.remove(pubkey).unwrap_or_default()))
+                    .collect::<Vec<_>>();
+                // Validators which have been disabled or removed since signing.
+                validators.extend(signings);
+
+                let validators = validators
+                    .into_iter()
+                    .map(|(voting_pubkey, signings)| api_types::ValidatorSignings {
+                        voting_pubkey,
+                        signings: signings
+                            .into_iter()
+                            .map(|signing| api_types::SignedMessage {
+                                message_type: signing.message_type.as_str().to_string(),
+                                slot: signing.slot,
+                            })
+                            .collect(),
+                    })
+                    .collect::<Vec<_>>();
+
+                Ok(api_types::GenericResponse::from(validators))
+            })
+        });
+
     // GET lighthouse/ui/health
     let get_lighthouse_ui_health = warp::path("lighthouse")
         .and(warp::path("ui"))
@@ -694,6 +753,35 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             },
         );
 
+    // POST lighthouse/validators/{validator_pubkey}/refresh_duties
+    let post_validators_refresh_duties = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("refresh_duties"))
+        .and(warp::path::end())
+        .and(duties_service_filter)
+        .then(
+            |validator_pubkey: PublicKey, duties_service: Arc<DutiesService<T, E>>| async move {
+                let pubkey = PublicKeyBytes::from(&validator_pubkey);
+                let result = refresh_validator_duties(&duties_service, pubkey)
+                    .await
+                    .map(|()| warp::reply::json(&()))
+                    .map_err(|e| match e {
+                        DutiesServiceError::UnknownPubkey(_) => {
+                            warp_utils::reject::custom_not_found(format!(
+                                "no validator for {:?}",
+                                validator_pubkey
+                            ))
+                        }
+                        e => warp_utils::reject::custom_server_error(format!(
+                            "unable to refresh duties: {:?}",
+                            e
+                        )),
+                    });
+                convert_rejection(result).await
+            },
+        );
+
     // PATCH lighthouse/validators/{validator_pubkey}
     let patch_validators = warp::path("lighthouse")
         .and(warp::path("validators"))
@@ -1313,6 +1401,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(get_lighthouse_spec)
                         .or(get_lighthouse_validators)
                         .or(get_lighthouse_validators_pubkey)
+                        .or(get_lighthouse_signing_ledger)
                         .or(get_lighthouse_ui_health)
                         .or(get_lighthouse_ui_graffiti)
                         .or(get_lighthouse_ui_fallback_health)
@@ -1328,6 +1417,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(post_validators_keystore)
                         .or(post_validators_mnemonic)
                         .or(post_validators_web3signer)
+                        .or(post_validators_refresh_duties)
                         .or(post_validators_voluntary_exits)
                         .or(post_fee_recipient)
                         .or(post_gas_limit)
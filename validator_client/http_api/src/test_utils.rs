@@ -127,6 +127,7 @@ impl ApiTester {
             task_executor: test_runtime.task_executor.clone(),
             api_secret,
             block_service: None,
+            duties_service: None,
             validator_dir: Some(validator_dir.path().into()),
             secrets_dir: Some(secrets_dir.path().into()),
             validator_store: Some(validator_store.clone()),
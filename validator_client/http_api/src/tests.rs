@@ -117,6 +117,7 @@ impl ApiTester {
             task_executor: test_runtime.task_executor.clone(),
             api_secret,
             block_service: None,
+            duties_service: None,
             validator_dir: Some(validator_dir.path().into()),
             secrets_dir: Some(secrets_dir.path().into()),
             validator_store: Some(validator_store.clone()),
@@ -523,6 +524,54 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_signing_ledger(self) -> Self {
+        let ledger = self
+            .client
+            .get_lighthouse_signing_ledger(Epoch::new(0))
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(ledger.len(), self.vals_enabled());
+        assert!(ledger.iter().all(|validator| validator.signings.is_empty()));
+
+        let voting_pubkey = ledger[0].voting_pubkey;
+        self.validator_store
+            .produce_sync_committee_signature(Slot::new(1), Hash256::ZERO, 0, &voting_pubkey)
+            .await
+            .unwrap();
+
+        let ledger = self
+            .client
+            .get_lighthouse_signing_ledger(Epoch::new(0))
+            .await
+            .unwrap()
+            .data;
+        let validator = ledger
+            .iter()
+            .find(|validator| validator.voting_pubkey == voting_pubkey)
+            .unwrap();
+        assert_eq!(
+            validator.signings,
+            vec![SignedMessage {
+                message_type: "sync_committee_message".to_string(),
+                slot: Slot::new(1),
+            }]
+        );
+
+        self
+    }
+
+    pub async fn test_signing_ledger_disabled(self) -> Self {
+        let err = self
+            .client
+            .get_lighthouse_signing_ledger(Epoch::new(0))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 404);
+
+        self
+    }
+
     fn get_current_epoch(&self) -> Epoch {
         self.slot_clock
             .now()
@@ -861,6 +910,10 @@ async fn routes_with_invalid_auth() {
                 .await
         })
         .await
+        .test_with_invalid_auth(|client| async move {
+            client.get_lighthouse_signing_ledger(Epoch::new(0)).await
+        })
+        .await
         .test_with_invalid_auth(|client| async move {
             client
                 .post_lighthouse_validators(vec![ValidatorRequest {
@@ -1033,6 +1086,30 @@ async fn validator_exit() {
         .await;
 }
 
+#[tokio::test]
+async fn signing_ledger() {
+    let config = ValidatorStoreConfig {
+        enable_signing_ledger: true,
+        ..ValidatorStoreConfig::default()
+    };
+    ApiTester::new_with_config(config)
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .test_signing_ledger()
+        .await;
+}
+
+#[tokio::test]
+async fn signing_ledger_disabled() {
+    ApiTester::new().await.test_signing_ledger_disabled().await;
+}
+
 #[tokio::test]
 async fn validator_enabling() {
     ApiTester::new()
@@ -1,17 +1,25 @@
-//! This crate provides a HTTP server that is solely dedicated to serving the `/metrics` endpoint.
+//! This crate provides a HTTP server that is solely dedicated to serving the `/metrics` endpoint,
+//! along with a lightweight `/health` endpoint for liveness probes.
 //!
 //! For other endpoints, see the `http_api` crate.
 
+use ethereum_hashing::hash_fixed;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use health_metrics::gather::catch_scrape_panic;
+pub use health_metrics::gather::MetricsGatherError;
 use lighthouse_version::version_with_platform;
 use malloc_utils::scrape_allocator_metrics;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
-use slog::{crit, info, Logger};
+use slog::{info, Logger};
 use slot_clock::{SlotClock, SystemTimeSlotClock};
 use std::future::Future;
+use std::io::Write;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use types::EthSpec;
 use validator_services::duties_service::DutiesService;
 use validator_store::ValidatorStore;
@@ -19,6 +27,8 @@ use warp::{http::Response, Filter};
 
 #[derive(Debug)]
 pub enum Error {
+    /// The server is disabled by `Config::enabled`, so it was not started.
+    Disabled,
     Warp(#[allow(dead_code)] warp::Error),
     Other(#[allow(dead_code)] String),
 }
@@ -59,6 +69,15 @@ pub struct Config {
     pub listen_port: u16,
     pub allow_origin: Option<String>,
     pub allocator_metrics_enabled: bool,
+    /// How long the server keeps serving after the shutdown signal, so that a final scrape can
+    /// capture the terminal state of the services shutting down alongside it.
+    pub shutdown_grace_period: Duration,
+    /// Compress the response if the client supports it, as advertised by its `Accept-Encoding`
+    /// header.
+    pub compression_enabled: bool,
+    /// If set, requests must provide the token stored in this file in an
+    /// `Authorization: Bearer <token>` header.
+    pub auth_token_path: Option<PathBuf>,
 }
 
 impl Default for Config {
@@ -69,13 +88,17 @@ impl Default for Config {
             listen_port: 5064,
             allow_origin: None,
             allocator_metrics_enabled: true,
+            shutdown_grace_period: Duration::ZERO,
+            compression_enabled: true,
+            auth_token_path: None,
         }
     }
 }
 
 /// Creates a server that will serve requests using information from `ctx`.
 ///
-/// The server will shut down gracefully when the `shutdown` future resolves.
+/// The server will shut down gracefully once `config.shutdown_grace_period` has elapsed after the
+/// `shutdown` future resolves.
 ///
 /// ## Returns
 ///
@@ -87,7 +110,9 @@ impl Default for Config {
 /// ## Errors
 ///
 /// Returns an error if the server is unable to bind or there is another error during
-/// configuration.
+/// configuration. Returns `Error::Disabled` without starting anything if the server is disabled
+/// in the config, so that it can be called regardless of `Config::enabled`. Returns
+/// `Error::Other` if `config.auth_token_path` is set but the token can't be read or is empty.
 pub fn serve<E: EthSpec>(
     ctx: Arc<Context<E>>,
     shutdown: impl Future<Output = ()> + Send + Sync + 'static,
@@ -95,11 +120,21 @@ pub fn serve<E: EthSpec>(
     let config = &ctx.config;
     let log = ctx.log.clone();
 
+    if !config.enabled {
+        return Err(Error::Disabled);
+    }
+
+    let auth_token = config
+        .auth_token_path
+        .as_deref()
+        .map(read_auth_token)
+        .transpose()?;
+
     // Configure CORS.
     let cors_builder = {
         let builder = warp::cors()
             .allow_method("GET")
-            .allow_headers(vec!["Content-Type"]);
+            .allow_headers(vec!["Content-Type", "Authorization"]);
 
         warp_utils::cors::set_builder_origins(
             builder,
@@ -108,45 +143,26 @@ pub fn serve<E: EthSpec>(
         )?
     };
 
-    // Sanity check.
-    if !config.enabled {
-        crit!(log, "Cannot start disabled metrics HTTP server");
-        return Err(Error::Other(
-            "A disabled metrics server should not be started".to_string(),
-        ));
-    }
-
-    let inner_ctx = ctx.clone();
-    let routes = warp::get()
-        .and(warp::path("metrics"))
-        .map(move || inner_ctx.clone())
-        .and_then(|ctx: Arc<Context<E>>| async move {
-            Ok::<_, warp::Rejection>(
-                gather_prometheus_metrics(&ctx)
-                    .map(|body| {
-                        Response::builder()
-                            .status(200)
-                            .header("Content-Type", "text/plain")
-                            .body(body)
-                            .unwrap()
-                    })
-                    .unwrap_or_else(|e| {
-                        Response::builder()
-                            .status(500)
-                            .header("Content-Type", "text/plain")
-                            .body(format!("Unable to gather metrics: {:?}", e))
-                            .unwrap()
-                    }),
-            )
-        })
+    let routes = metrics_filter(ctx.clone(), auth_token)
+        .or(health_filter(ctx.clone()))
         // Add a `Server` header.
         .map(|reply| warp::reply::with_header(reply, "Server", &version_with_platform()))
         .with(cors_builder.build());
 
+    let shutdown_grace_period = config.shutdown_grace_period;
+    let shutdown_log = log.clone();
     let (listening_socket, server) = warp::serve(routes).try_bind_with_graceful_shutdown(
         SocketAddr::new(config.listen_addr, config.listen_port),
-        async {
+        async move {
             shutdown.await;
+            if !shutdown_grace_period.is_zero() {
+                info!(
+                    shutdown_log,
+                    "Metrics HTTP server shutting down after grace period";
+                    "grace_period" => ?shutdown_grace_period,
+                );
+                tokio::time::sleep(shutdown_grace_period).await;
+            }
         },
     )?;
 
@@ -159,9 +175,167 @@ pub fn serve<E: EthSpec>(
     Ok((listening_socket, server))
 }
 
+/// Reads the token required to access the server from `path`.
+fn read_auth_token(path: &Path) -> Result<Arc<String>, Error> {
+    let token = std::fs::read_to_string(path)
+        .map_err(|e| format!("Unable to read metrics auth token from {path:?}: {e:?}"))?;
+    let token = token.trim();
+    if token.is_empty() {
+        return Err(Error::Other(format!(
+            "Metrics auth token in {path:?} is empty"
+        )));
+    }
+    Ok(Arc::new(token.to_string()))
+}
+
+/// Returns `true` if `authorization` is a bearer `Authorization` header value holding `token`.
+///
+/// The SHA-256 digests of the tokens are compared in constant time, so that neither the token nor
+/// its length leak through timing.
+fn is_authorized(authorization: Option<&str>, token: &str) -> bool {
+    let Some(provided) = authorization.and_then(|value| value.strip_prefix("Bearer ")) else {
+        return false;
+    };
+    hash_fixed(provided.as_bytes())
+        .iter()
+        .zip(hash_fixed(token.as_bytes()).iter())
+        .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+/// Returns the filter serving `GET /metrics`.
+///
+/// If `auth_token` is set, requests without a matching bearer token are rejected with a 401.
+fn metrics_filter<E: EthSpec>(
+    ctx: Arc<Context<E>>,
+    auth_token: Option<Arc<String>>,
+) -> impl Filter<Extract = (Response<Vec<u8>>,), Error = warp::Rejection> + Clone {
+    warp::get()
+        .and(warp::path("metrics"))
+        .map(move || (ctx.clone(), auth_token.clone()))
+        .and(warp::header::optional::<String>("Accept-Encoding"))
+        .and(warp::header::optional::<String>("Authorization"))
+        .and_then(
+            |(ctx, auth_token): (Arc<Context<E>>, Option<Arc<String>>),
+             accept_encoding: Option<String>,
+             authorization: Option<String>| async move {
+                if let Some(token) = auth_token {
+                    if !is_authorized(authorization.as_deref(), &token) {
+                        return Ok::<_, warp::Rejection>(
+                            Response::builder()
+                                .status(401)
+                                .header("Content-Type", "text/plain")
+                                .header("WWW-Authenticate", "Bearer")
+                                .body("Unauthorized".into())
+                                .unwrap(),
+                        );
+                    }
+                }
+
+                let use_gzip = ctx.config.compression_enabled
+                    && accept_encoding.as_deref().is_some_and(accepts_gzip);
+                Ok::<_, warp::Rejection>(
+                    gather_prometheus_metrics(&ctx)
+                        .map(|body| {
+                            if !use_gzip {
+                                return Response::builder()
+                                    .status(200)
+                                    .header("Content-Type", "text/plain")
+                                    .body(body.into())
+                                    .unwrap();
+                            }
+                            match gzip(body.as_bytes()) {
+                                Ok(compressed) => Response::builder()
+                                    .status(200)
+                                    .header("Content-Type", "text/plain")
+                                    .header("Content-Encoding", "gzip")
+                                    .header("Vary", "Accept-Encoding")
+                                    .body(compressed)
+                                    .unwrap(),
+                                Err(e) => Response::builder()
+                                    .status(500)
+                                    .header("Content-Type", "text/plain")
+                                    .body(format!("Unable to compress metrics: {:?}", e).into())
+                                    .unwrap(),
+                            }
+                        })
+                        .unwrap_or_else(|e| {
+                            Response::builder()
+                                .status(500)
+                                .header("Content-Type", "text/plain")
+                                .body(format!("Unable to gather metrics: {:?}", e).into())
+                                .unwrap()
+                        }),
+                )
+            },
+        )
+}
+
+/// The response of `GET /health`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Health {
+    pub genesis_time: Option<u64>,
+    /// `true` if at least one beacon node is synced.
+    pub connected: bool,
+}
+
+/// Returns the filter serving `GET /health`.
+///
+/// Unlike `/metrics` this does not gather the Prometheus registry, so it is cheap enough for
+/// liveness probes. It does not require the auth token, since it exposes nothing sensitive.
+fn health_filter<E: EthSpec>(
+    ctx: Arc<Context<E>>,
+) -> impl Filter<Extract = (warp::reply::Json,), Error = warp::Rejection> + Clone {
+    warp::get()
+        .and(warp::path("health"))
+        .and(warp::path::end())
+        .map(move || ctx.clone())
+        .then(|ctx: Arc<Context<E>>| async move {
+            let (genesis_time, duties_service) = {
+                let shared = ctx.shared.read();
+                (shared.genesis_time, shared.duties_service.clone())
+            };
+            let connected = match duties_service {
+                Some(duties_service) => {
+                    let (_, _, num_synced) = duties_service.beacon_nodes.get_notifier_info().await;
+                    num_synced > 0
+                }
+                None => false,
+            };
+            warp::reply::json(&Health {
+                genesis_time,
+                connected,
+            })
+        })
+}
+
+/// Returns `true` if the value of an `Accept-Encoding` header accepts gzip. An encoding with a
+/// quality value of zero is not acceptable.
+fn accepts_gzip(accept_encoding: &str) -> bool {
+    accept_encoding.split(',').any(|item| {
+        let mut parts = item.split(';').map(str::trim);
+        let is_gzip = parts
+            .next()
+            .is_some_and(|coding| coding.eq_ignore_ascii_case("gzip"));
+        let rejected = parts.any(|param| {
+            param
+                .strip_prefix("q=")
+                .and_then(|q| q.parse::<f32>().ok())
+                .is_some_and(|q| q == 0.0)
+        });
+        is_gzip && !rejected
+    })
+}
+
+fn gzip(body: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(vec![], Compression::default());
+    encoder.write_all(body)?;
+    encoder.finish()
+}
+
 pub fn gather_prometheus_metrics<E: EthSpec>(
     ctx: &Context<E>,
-) -> std::result::Result<String, String> {
+) -> std::result::Result<String, MetricsGatherError> {
     use validator_metrics::*;
     let mut buffer = vec![];
     let encoder = TextEncoder::new();
@@ -169,6 +343,11 @@ pub fn gather_prometheus_metrics<E: EthSpec>(
     {
         let shared = ctx.shared.read();
 
+        set_gauge(
+            &VC_GENESIS_TIME_CONFIGURED,
+            i64::from(shared.genesis_time.is_some()),
+        );
+
         if let Some(genesis_time) = shared.genesis_time {
             if let Ok(now) = SystemTime::now().duration_since(UNIX_EPOCH) {
                 let distance = now.as_secs() as i64 - genesis_time as i64;
@@ -203,14 +382,177 @@ pub fn gather_prometheus_metrics<E: EthSpec>(
     // It's important to ensure these metrics are explicitly enabled in the case that users aren't
     // using glibc and this function causes panics.
     if ctx.config.allocator_metrics_enabled {
-        scrape_allocator_metrics();
+        catch_scrape_panic(scrape_allocator_metrics)
+            .map_err(MetricsGatherError::AllocatorScrape)?;
     }
 
-    health_metrics::metrics::scrape_health_metrics();
+    catch_scrape_panic(health_metrics::metrics::scrape_health_metrics)
+        .map_err(MetricsGatherError::HealthScrape)?;
 
     encoder
         .encode(&metrics::gather(), &mut buffer)
-        .map_err(|e| format!("{e:?}"))?;
+        .map_err(|e| MetricsGatherError::Encode(format!("{e:?}")))?;
+
+    String::from_utf8(buffer).map_err(MetricsGatherError::Utf8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+    use tempfile::NamedTempFile;
+    use tokio::sync::oneshot;
+    use types::MainnetEthSpec;
+
+    fn context(compression_enabled: bool) -> Arc<Context<MainnetEthSpec>> {
+        Arc::new(Context {
+            config: Config {
+                enabled: true,
+                allocator_metrics_enabled: false,
+                compression_enabled,
+                ..Config::default()
+            },
+            shared: RwLock::new(Shared {
+                validator_store: None,
+                duties_service: None,
+                genesis_time: None,
+            }),
+            log: Logger::root(slog::Discard, slog::o!()),
+        })
+    }
+
+    #[tokio::test]
+    async fn metrics_are_gzipped_if_accepted() {
+        let response = warp::test::request()
+            .path("/metrics")
+            .header("Accept-Encoding", "deflate, gzip;q=1.0, br")
+            .reply(&metrics_filter(context(true), None))
+            .await;
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers()["Content-Encoding"], "gzip");
+        assert_eq!(response.headers()["Vary"], "Accept-Encoding");
+        let mut body = String::new();
+        GzDecoder::new(&response.body()[..])
+            .read_to_string(&mut body)
+            .unwrap();
+        assert!(body.contains("# TYPE"));
+    }
+
+    #[tokio::test]
+    async fn metrics_are_plain_text_if_compression_is_not_accepted() {
+        for (compression_enabled, accept_encoding) in [
+            (true, None),
+            (true, Some("br, gzip;q=0")),
+            (true, Some("deflate")),
+            (false, Some("gzip")),
+        ] {
+            let mut request = warp::test::request().path("/metrics");
+            if let Some(accept_encoding) = accept_encoding {
+                request = request.header("Accept-Encoding", accept_encoding);
+            }
+            let response = request
+                .reply(&metrics_filter(context(compression_enabled), None))
+                .await;
+
+            assert_eq!(response.status(), 200);
+            assert!(response.headers().get("Content-Encoding").is_none());
+            assert!(std::str::from_utf8(response.body())
+                .unwrap()
+                .contains("# TYPE"));
+        }
+    }
+
+    #[tokio::test]
+    async fn metrics_require_auth_token_if_configured() {
+        let token = Arc::new("secret".to_string());
+        for (authorization, expected_status) in [
+            (None, 401),
+            (Some("Bearer wrong!"), 401),
+            (Some("Bearer secre"), 401),
+            (Some("secret"), 401),
+            (Some("Bearer secret"), 200),
+        ] {
+            let mut request = warp::test::request().path("/metrics");
+            if let Some(authorization) = authorization {
+                request = request.header("Authorization", authorization);
+            }
+            let response = request
+                .reply(&metrics_filter(context(true), Some(token.clone())))
+                .await;
 
-    String::from_utf8(buffer).map_err(|e| format!("Failed to encode prometheus info: {:?}", e))
+            assert_eq!(
+                response.status(),
+                expected_status,
+                "authorization: {authorization:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn health_is_served_without_services() {
+        let ctx = context(true);
+        ctx.shared.write().genesis_time = Some(1_606_824_023);
+
+        let response = warp::test::request()
+            .path("/health")
+            .reply(&health_filter(ctx))
+            .await;
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            serde_json::from_slice::<Health>(response.body()).unwrap(),
+            Health {
+                genesis_time: Some(1_606_824_023),
+                connected: false,
+            }
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn metrics_are_served_during_shutdown_grace_period() {
+        let mut ctx = Arc::into_inner(context(false)).unwrap();
+        ctx.config.listen_port = 0;
+        ctx.config.shutdown_grace_period = Duration::from_secs(2);
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let (listening_socket, server) = serve(Arc::new(ctx), async {
+            let _ = shutdown_rx.await;
+        })
+        .unwrap();
+        let server = tokio::spawn(server);
+
+        // Fire the shutdown signal, then scrape while the server is in its grace period.
+        drop(shutdown_tx);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let response = reqwest::get(format!("http://{listening_socket}/metrics"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert!(response.text().await.unwrap().contains("# TYPE"));
+        assert!(!server.is_finished());
+
+        // The server shuts down once the grace period has elapsed.
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .expect("server should shut down after the grace period")
+            .unwrap();
+    }
+
+    #[test]
+    fn serve_fails_with_empty_auth_token() {
+        let token_file = NamedTempFile::new().unwrap();
+        std::fs::write(token_file.path(), "\n").unwrap();
+
+        for auth_token_path in [
+            token_file.path().to_path_buf(),
+            token_file.path().with_extension("missing"),
+        ] {
+            let mut ctx = Arc::into_inner(context(true)).unwrap();
+            ctx.config.auth_token_path = Some(auth_token_path);
+            let result = serve(Arc::new(ctx), std::future::pending());
+            assert!(matches!(result, Err(Error::Other(_))));
+        }
+    }
 }
@@ -27,6 +27,7 @@ use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::{self, Read};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 use types::graffiti::GraffitiString;
@@ -53,6 +54,9 @@ pub const DEFAULT_WEB3SIGNER_KEEP_ALIVE: Option<Duration> = Some(Duration::from_
 pub struct Config {
     pub web3_signer_keep_alive_timeout: Option<Duration>,
     pub web3_signer_max_idle_connections: Option<usize>,
+    /// The signing method to use for a validator that is defined by both a local keystore and a
+    /// web3signer. If `None`, the first enabled definition of the validator is used.
+    pub preferred_signing_method: Option<PreferredSigningMethod>,
 }
 
 impl Default for Config {
@@ -60,6 +64,49 @@ impl Default for Config {
         Config {
             web3_signer_keep_alive_timeout: DEFAULT_WEB3SIGNER_KEEP_ALIVE,
             web3_signer_max_idle_connections: None,
+            preferred_signing_method: None,
+        }
+    }
+}
+
+/// The signing method preferred for validators with more than one signing definition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PreferredSigningMethod {
+    LocalKeystore,
+    Web3Signer,
+}
+
+impl PreferredSigningMethod {
+    /// Returns `true` if `signing_definition` describes the preferred signing method.
+    fn matches_definition(&self, signing_definition: &SigningDefinition) -> bool {
+        matches!(
+            (self, signing_definition),
+            (Self::LocalKeystore, SigningDefinition::LocalKeystore { .. })
+                | (Self::Web3Signer, SigningDefinition::Web3Signer(_))
+        )
+    }
+
+    /// Returns `true` if `signing_method` is the preferred signing method.
+    fn matches_method(&self, signing_method: &SigningMethod) -> bool {
+        matches!(
+            (self, signing_method),
+            (Self::LocalKeystore, SigningMethod::LocalKeystore { .. })
+                | (Self::Web3Signer, SigningMethod::Web3Signer { .. })
+        )
+    }
+}
+
+impl FromStr for PreferredSigningMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "local-keystore" => Ok(Self::LocalKeystore),
+            "web3signer" => Ok(Self::Web3Signer),
+            other => Err(format!(
+                "Unknown signing method: {}, expected local-keystore or web3signer",
+                other
+            )),
         }
     }
 }
@@ -1212,7 +1259,8 @@ impl InitializedValidators {
     ///
     /// A validator is considered "already known" and skipped if the public key is already known.
     /// I.e., if there are two different definitions with the same public key then the second will
-    /// be ignored.
+    /// be ignored, unless it uses the `preferred_signing_method` from the config and the first
+    /// does not.
     pub async fn update_validators(&mut self) -> Result<(), Error> {
         //use key cache if available
         let mut key_stores = HashMap::new();
@@ -1249,8 +1297,17 @@ impl InitializedValidators {
             if def.enabled {
                 let pubkey_bytes = def.voting_public_key.compress();
 
-                if self.validators.contains_key(&pubkey_bytes) {
-                    continue;
+                if let Some(existing) = self.validators.get(&pubkey_bytes) {
+                    let prefer_definition =
+                        self.config
+                            .preferred_signing_method
+                            .is_some_and(|preferred| {
+                                preferred.matches_definition(&def.signing_definition)
+                                    && !preferred.matches_method(&existing.signing_method)
+                            });
+                    if !prefer_definition {
+                        continue;
+                    }
                 }
 
                 match &def.signing_definition {
@@ -1278,8 +1335,7 @@ impl InitializedValidators {
                                     .filter(|l| l.file_existed())
                                     .map(|l| l.path().to_owned());
 
-                                self.validators
-                                    .insert(init.voting_public_key().compress(), init);
+                                Self::insert_validator(&mut self.validators, init);
                                 info!(
                                     self.log,
                                     "Enabled validator";
@@ -1323,8 +1379,7 @@ impl InitializedValidators {
                         .await
                         {
                             Ok(init) => {
-                                self.validators
-                                    .insert(init.voting_public_key().compress(), init);
+                                Self::insert_validator(&mut self.validators, init);
 
                                 info!(
                                     self.log,
@@ -1409,6 +1464,19 @@ impl InitializedValidators {
         Ok(())
     }
 
+    /// Inserts `validator` into `validators`, keeping the validator index of any validator it
+    /// replaces.
+    fn insert_validator(
+        validators: &mut HashMap<PublicKeyBytes, InitializedValidator>,
+        mut validator: InitializedValidator,
+    ) {
+        let pubkey_bytes = validator.voting_public_key().compress();
+        if let Some(replaced) = validators.get(&pubkey_bytes) {
+            validator.index = replaced.index;
+        }
+        validators.insert(pubkey_bytes, validator);
+    }
+
     pub fn get_index(&self, pubkey: &PublicKeyBytes) -> Option<u64> {
         self.validators.get(pubkey).and_then(|val| val.index)
     }
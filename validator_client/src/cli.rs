@@ -289,6 +289,39 @@ pub struct ValidatorClient {
     )]
     pub metrics_allow_origin: Option<String>,
 
+    #[clap(
+        long,
+        value_name = "PATH",
+        requires = "metrics",
+        help = "Path to a file containing a token which requests to the Prometheus metrics \
+                HTTP server must provide in an `Authorization: Bearer <token>` header. \
+                If not supplied, the server does not require authorization.",
+        display_order = 0
+    )]
+    pub metrics_auth_token_path: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "SECONDS",
+        requires = "metrics",
+        default_value_t = 0,
+        help = "Keep the Prometheus metrics HTTP server running for this many seconds after \
+                shutdown begins, so that a final scrape can capture the terminal state of \
+                the validator client.",
+        display_order = 0
+    )]
+    pub metrics_shutdown_grace_period: u64,
+
+    #[clap(
+        long,
+        requires = "metrics",
+        help = "Do not compress the responses of the Prometheus metrics HTTP server, even if \
+                the client accepts gzip encoded responses.",
+        display_order = 0,
+        help_heading = FLAG_HEADER
+    )]
+    pub metrics_disable_compression: bool,
+
     #[clap(
         long,
         help = "Enable per validator metrics for > 64 validators. \
@@ -300,6 +333,29 @@ pub struct ValidatorClient {
     )]
     pub enable_high_validator_count_metrics: bool,
 
+    #[clap(
+        long,
+        requires = "http",
+        help = "Record the blocks, attestations and sync committee messages signed by each \
+                validator over the last few epochs, and serve them on the \
+                /lighthouse/signing_ledger/{epoch} HTTP API endpoint. The ledger is kept in \
+                memory and grows with the number of validators.",
+        display_order = 0,
+        help_heading = FLAG_HEADER
+    )]
+    pub enable_signing_ledger: bool,
+
+    #[clap(
+        long,
+        value_name = "COUNT",
+        default_value_t = 10000,
+        help = "The maximum number of validators for which per validator metrics are \
+                published. Once reached, metrics are not published for further validators \
+                and a warning is logged.",
+        display_order = 0
+    )]
+    pub validator_metrics_cardinality_cap: usize,
+
     /* Explorer metrics */
     #[clap(
         long,
@@ -475,4 +531,15 @@ pub struct ValidatorClient {
         display_order = 0
     )]
     pub web3_signer_max_idle_connections: Option<usize>,
+
+    #[clap(
+        long,
+        value_name = "SIGNING_METHOD",
+        value_parser = ["local-keystore", "web3signer"],
+        help = "The signing method to use for a validator which is defined by both a local \
+                keystore and a web3signer. By default the first enabled definition of the \
+                validator is used.",
+        display_order = 0
+    )]
+    pub preferred_signing_method: Option<String>,
 }
@@ -20,6 +20,7 @@ use std::time::Duration;
 use types::GRAFFITI_BYTES_LEN;
 use validator_http_api::{self, PK_FILENAME};
 use validator_http_metrics;
+use validator_services::duties_service::DEFAULT_VALIDATOR_METRICS_CARDINALITY_CAP;
 use validator_store::Config as ValidatorStoreConfig;
 
 pub const DEFAULT_BEACON_NODE: &str = "http://localhost:5052/";
@@ -69,6 +70,8 @@ pub struct Config {
     /// Note: We publish validator specific metrics for low validator counts without this flag
     /// (<= 64 validators)
     pub enable_high_validator_count_metrics: bool,
+    /// The maximum number of distinct validators for which per-validator metrics are published.
+    pub validator_metrics_cardinality_cap: usize,
     /// Enable use of the blinded block endpoints during proposals.
     pub builder_registration_timestamp_override: Option<u64>,
     /// A list of custom certificates that the validator client will additionally use when
@@ -119,6 +122,7 @@ impl Default for Config {
             monitoring_api: None,
             enable_doppelganger_protection: false,
             enable_high_validator_count_metrics: false,
+            validator_metrics_cardinality_cap: DEFAULT_VALIDATOR_METRICS_CARDINALITY_CAP,
             beacon_nodes_tls_certs: None,
             builder_registration_timestamp_override: None,
             broadcast_topics: vec![ApiTopic::Subscriptions],
@@ -262,6 +266,10 @@ impl Config {
                 .web3_signer_max_idle_connections = Some(n);
         }
 
+        if let Some(signing_method) = &validator_client_config.preferred_signing_method {
+            config.initialized_validators.preferred_signing_method = Some(signing_method.parse()?);
+        }
+
         /*
          * Http API server
          */
@@ -310,6 +318,8 @@ impl Config {
         config.http_metrics.enabled = validator_client_config.metrics;
         config.enable_high_validator_count_metrics =
             validator_client_config.enable_high_validator_count_metrics;
+        config.validator_metrics_cardinality_cap =
+            validator_client_config.validator_metrics_cardinality_cap;
 
         if let Some(metrics_address) = &validator_client_config.metrics_address {
             config.http_metrics.listen_addr = metrics_address
@@ -328,6 +338,16 @@ impl Config {
             config.http_metrics.allow_origin = Some(allow_origin.to_string());
         }
 
+        config.http_metrics.auth_token_path =
+            validator_client_config.metrics_auth_token_path.clone();
+
+        config.http_metrics.shutdown_grace_period =
+            Duration::from_secs(validator_client_config.metrics_shutdown_grace_period);
+
+        if validator_client_config.metrics_disable_compression {
+            config.http_metrics.compression_enabled = false;
+        }
+
         if cli_args.get_flag(DISABLE_MALLOC_TUNING_FLAG) {
             config.http_metrics.allocator_metrics_enabled = false;
         }
@@ -351,6 +371,8 @@ impl Config {
         config.validator_store.prefer_builder_proposals =
             validator_client_config.prefer_builder_proposals;
         config.validator_store.gas_limit = Some(validator_client_config.gas_limit);
+        config.validator_store.enable_signing_ledger =
+            validator_client_config.enable_signing_ledger;
 
         config.builder_registration_timestamp_override =
             validator_client_config.builder_registration_timestamp_override;
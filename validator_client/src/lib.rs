@@ -21,7 +21,7 @@ use environment::RuntimeContext;
 use eth2::{reqwest::ClientBuilder, BeaconNodeHttpClient, StatusCode, Timeouts};
 use initialized_validators::Error::UnableToOpenVotingKeystore;
 use notifier::spawn_notifier;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use reqwest::Certificate;
 use slog::{debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
@@ -42,7 +42,7 @@ use validator_http_api::ApiSecret;
 use validator_services::{
     attestation_service::{AttestationService, AttestationServiceBuilder},
     block_service::{BlockService, BlockServiceBuilder},
-    duties_service::{self, DutiesService},
+    duties_service::{self, CappedValidatorLabels, DutiesService},
     preparation_service::{PreparationService, PreparationServiceBuilder},
     sync::SyncDutiesMap,
     sync_committee_service::SyncCommitteeService,
@@ -136,36 +136,32 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
         );
 
         // Optionally start the metrics server.
-        let validator_metrics_ctx = if config.http_metrics.enabled {
-            let shared = validator_http_metrics::Shared {
-                validator_store: None,
-                genesis_time: None,
-                duties_service: None,
+        let ctx: Arc<validator_http_metrics::Context<E>> =
+            Arc::new(validator_http_metrics::Context {
+                config: config.http_metrics.clone(),
+                shared: RwLock::new(validator_http_metrics::Shared {
+                    validator_store: None,
+                    genesis_time: None,
+                    duties_service: None,
+                }),
+                log: log.clone(),
+            });
+        let validator_metrics_ctx =
+            match validator_http_metrics::serve(ctx.clone(), context.executor.exit()) {
+                Ok((_listen_addr, server)) => {
+                    context
+                        .clone()
+                        .executor
+                        .spawn_without_exit(server, "metrics-api");
+                    Some(ctx)
+                }
+                Err(validator_http_metrics::Error::Disabled) => {
+                    info!(log, "HTTP metrics server is disabled");
+                    None
+                }
+                Err(e) => return Err(format!("Unable to start metrics API server: {:?}", e)),
             };
 
-            let ctx: Arc<validator_http_metrics::Context<E>> =
-                Arc::new(validator_http_metrics::Context {
-                    config: config.http_metrics.clone(),
-                    shared: RwLock::new(shared),
-                    log: log.clone(),
-                });
-
-            let exit = context.executor.exit();
-
-            let (_listen_addr, server) = validator_http_metrics::serve(ctx.clone(), exit)
-                .map_err(|e| format!("Unable to start metrics API server: {:?}", e))?;
-
-            context
-                .clone()
-                .executor
-                .spawn_without_exit(server, "metrics-api");
-
-            Some(ctx)
-        } else {
-            info!(log, "HTTP metrics server is disabled");
-            None
-        };
-
         // Start the explorer client which periodically sends validator process
         // and system metrics to the configured endpoint.
         if let Some(monitoring_config) = &config.monitoring_api {
@@ -332,7 +328,8 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
                 url.clone(),
                 beacon_node_http_client,
                 timeouts,
-            ))
+            )
+            .with_request_observer(Arc::new(validator_metrics::observe_bn_latency)))
         };
 
         let beacon_nodes: Vec<BeaconNodeHttpClient> = config
@@ -477,6 +474,9 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             spec: context.eth2_config.spec.clone(),
             context: duties_context,
             enable_high_validator_count_metrics: config.enable_high_validator_count_metrics,
+            attestation_duty_metric_labels: Mutex::new(CappedValidatorLabels::new(
+                config.validator_metrics_cardinality_cap,
+            )),
             distributed: config.distributed,
         });
 
@@ -558,6 +558,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
                 task_executor: self.context.executor.clone(),
                 api_secret,
                 block_service: Some(self.block_service.clone()),
+                duties_service: Some(self.duties_service.clone()),
                 validator_store: Some(self.validator_store.clone()),
                 validator_dir: Some(self.config.validator_dir.clone()),
                 secrets_dir: Some(self.config.secrets_dir.clone()),
@@ -638,6 +639,17 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
 
         Ok(())
     }
+
+    /// Returns the address the HTTP API server is listening on, or `None` if the server is
+    /// disabled or the service has not been started.
+    pub fn http_api_listen_addr(&self) -> Option<SocketAddr> {
+        self.http_api_listen_addr
+    }
+
+    /// Returns the path of the file holding the API token of the HTTP API server.
+    pub fn http_api_token_path(&self) -> &Path {
+        &self.config.http_api.http_token_path
+    }
 }
 
 async fn init_from_beacon_node<E: EthSpec>(
@@ -1,4 +1,5 @@
 use std::sync::LazyLock;
+use std::time::Duration;
 
 pub const SUCCESS: &str = "success";
 pub const SLASHABLE: &str = "slashable";
@@ -28,6 +29,7 @@ pub const VALIDATOR_DUTIES_SYNC_HTTP_POST: &str = "validator_duties_sync_http_po
 pub const VALIDATOR_ID_HTTP_GET: &str = "validator_id_http_get";
 pub const SUBSCRIPTIONS_HTTP_POST: &str = "subscriptions_http_post";
 pub const UPDATE_PROPOSERS: &str = "update_proposers";
+pub const REFRESH_VALIDATOR_DUTIES: &str = "refresh_validator_duties";
 pub const ATTESTATION_SELECTION_PROOFS: &str = "attestation_selection_proofs";
 pub const SUBSCRIPTIONS: &str = "subscriptions";
 pub const LOCAL_KEYSTORE: &str = "local_keystore";
@@ -41,6 +43,12 @@ pub static GENESIS_DISTANCE: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
         "Distance between now and genesis time",
     )
 });
+pub static VC_GENESIS_TIME_CONFIGURED: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "vc_genesis_time_configured",
+        "Set to 1 if the genesis time is known, otherwise vc_genesis_distance_seconds is missing",
+    )
+});
 pub static ENABLED_VALIDATORS_COUNT: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
     try_create_int_gauge(
         "vc_validators_enabled_count",
@@ -53,12 +61,18 @@ pub static TOTAL_VALIDATORS_COUNT: LazyLock<Result<IntGauge>> = LazyLock::new(||
         "Number of total validators (enabled and disabled)",
     )
 });
+pub static VC_VALIDATORS_ACTIVE_COUNT: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "vc_validators_active_count",
+        "Number of managed validators that are active on-chain according to the beacon node",
+    )
+});
 
 pub static SIGNED_BLOCKS_TOTAL: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
     try_create_int_counter_vec(
         "vc_signed_beacon_blocks_total",
         "Total count of attempted block signings",
-        &["status"],
+        &["status", "fork"],
     )
 });
 pub static SIGNED_ATTESTATIONS_TOTAL: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
@@ -121,6 +135,13 @@ pub static SIGNED_VALIDATOR_REGISTRATIONS_TOTAL: LazyLock<Result<IntCounterVec>>
             &["status"],
         )
     });
+pub static BUILDER_LAST_REGISTRATION_TIMESTAMP: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "builder_last_registration_timestamp",
+        "Unix timestamp in seconds of the last validator registration batch successfully \
+         published to the builder network",
+    )
+});
 pub static DUTIES_SERVICE_TIMES: LazyLock<Result<HistogramVec>> = LazyLock::new(|| {
     try_create_histogram_vec(
         "vc_duties_service_task_times_seconds",
@@ -221,6 +242,13 @@ pub static ETH2_FALLBACK_CONNECTED: LazyLock<Result<IntGauge>> = LazyLock::new(|
         "Set to 1 if connected to atleast one synced eth2 fallback node, otherwise set to 0",
     )
 });
+pub static VC_BEACON_NODE_SYNCED: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "vc_beacon_node_synced",
+        "Set to 1 if the beacon node is available and synced, otherwise set to 0",
+        &["beacon_node"],
+    )
+});
 /*
  * Signing Metrics
  */
@@ -231,10 +259,11 @@ pub static SIGNING_TIMES: LazyLock<Result<HistogramVec>> = LazyLock::new(|| {
         &["type"],
     )
 });
-pub static BLOCK_SIGNING_TIMES: LazyLock<Result<Histogram>> = LazyLock::new(|| {
-    try_create_histogram(
+pub static BLOCK_SIGNING_TIMES: LazyLock<Result<HistogramVec>> = LazyLock::new(|| {
+    try_create_histogram_vec(
         "vc_block_signing_times_seconds",
         "Duration to obtain a signature for a block",
+        &["fork"],
     )
 });
 
@@ -245,6 +274,22 @@ pub static ATTESTATION_DUTY: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
         &["validator"],
     )
 });
+pub static ATTESTATION_INCLUSION_DISTANCE: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "vc_attestation_inclusion_distance",
+        "Inclusion distance of the latest attestation included on chain, as reported by the \
+        beacon node, for all managed validators",
+        &["validator"],
+    )
+});
+pub static VC_METRIC_CARDINALITY_CAPPED: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "vc_metric_cardinality_capped",
+        "Set to 1 if a per-validator metric stopped adding validators upon reaching the \
+        cardinality cap",
+        &["metric"],
+    )
+});
 /*
  * BN latency
  */
@@ -262,3 +307,39 @@ pub static VC_BEACON_NODE_LATENCY_PRIMARY_ENDPOINT: LazyLock<Result<Histogram>>
             "Round-trip latency for the primary BN endpoint",
         )
     });
+pub static VC_BEACON_NODE_REQUEST_LATENCY: LazyLock<Result<HistogramVec>> = LazyLock::new(|| {
+    try_create_histogram_vec(
+        "vc_beacon_node_request_latency",
+        "Round-trip latency of all requests to each BN endpoint by HTTP method",
+        &["endpoint", "method"],
+    )
+});
+
+/// Records the round-trip `duration` of a request with the HTTP `method` to the BN at `endpoint`.
+///
+/// As for the other BN metrics, `endpoint` is the (redacted) URL of the BN, not an API path.
+pub fn observe_bn_latency(endpoint: &str, method: &str, duration: Duration) {
+    observe_timer_vec(
+        &VC_BEACON_NODE_REQUEST_LATENCY,
+        &[endpoint, method],
+        duration,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn observe_bn_latency_records_into_label_pair() {
+        let endpoint = "http://observe-bn-latency.test:5052/";
+        observe_bn_latency(endpoint, "POST", Duration::from_millis(250));
+
+        let post = get_histogram(&VC_BEACON_NODE_REQUEST_LATENCY, &[endpoint, "POST"]).unwrap();
+        assert_eq!(post.get_sample_count(), 1);
+        assert_eq!(post.get_sample_sum(), 0.25);
+
+        let get = get_histogram(&VC_BEACON_NODE_REQUEST_LATENCY, &[endpoint, "GET"]).unwrap();
+        assert_eq!(get.get_sample_count(), 0);
+    }
+}
@@ -1,12 +1,17 @@
 use crate::duties_service::{DutiesService, DutyAndProof};
 use beacon_node_fallback::{ApiTopic, BeaconNodeFallback};
+use doppelganger_service::DoppelgangerStatus;
 use either::Either;
 use environment::RuntimeContext;
+use eth2::lighthouse::ValidatorMetricsResponse;
+use eth2::StatusCode;
 use futures::future::join_all;
+use parking_lot::Mutex;
 use slog::{crit, debug, error, info, trace, warn};
 use slot_clock::SlotClock;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::time::{sleep, sleep_until, Duration, Instant};
 use tree_hash::TreeHash;
@@ -77,6 +82,8 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
                 context: self
                     .context
                     .ok_or("Cannot build AttestationService without runtime_context")?,
+                inclusion_distance_metric_labels: Mutex::new(HashSet::new()),
+                inclusion_distance_unsupported: AtomicBool::new(false),
             }),
         })
     }
@@ -89,6 +96,11 @@ pub struct Inner<T, E: EthSpec> {
     slot_clock: T,
     beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     context: RuntimeContext<E>,
+    /// The validators labelled in the `ATTESTATION_INCLUSION_DISTANCE` metric.
+    inclusion_distance_metric_labels: Mutex<HashSet<u64>>,
+    /// Set once a beacon node reports that it doesn't serve the validator metrics used for the
+    /// `ATTESTATION_INCLUSION_DISTANCE` metric, e.g. because it isn't a Lighthouse beacon node.
+    inclusion_distance_unsupported: AtomicBool,
 }
 
 /// Attempts to produce attestations for all known validators 1/3rd of the way through each slot.
@@ -217,6 +229,17 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         // production.
         self.spawn_slashing_protection_pruning_task(slot, aggregate_production_instant);
 
+        // The beacon node only learns the inclusion distances of the previous epoch during epoch
+        // processing, so poll them once per epoch.
+        if slot
+            == slot
+                .epoch(E::slots_per_epoch())
+                .start_slot(E::slots_per_epoch())
+            && !self.inclusion_distance_unsupported.load(Ordering::Relaxed)
+        {
+            self.spawn_inclusion_distance_task(aggregate_production_instant);
+        }
+
         Ok(())
     }
 
@@ -726,11 +749,139 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             "slashing_protection_pre_pruning",
         );
     }
+
+    /// Spawn a task to update the `ATTESTATION_INCLUSION_DISTANCE` metric at `update_instant`.
+    fn spawn_inclusion_distance_task(&self, update_instant: Instant) {
+        let attestation_service = self.clone();
+
+        self.inner.context.executor.spawn(
+            async move {
+                sleep_until(update_instant).await;
+                attestation_service
+                    .update_inclusion_distance_metrics()
+                    .await
+            },
+            "attestation_inclusion_distance",
+        );
+    }
+
+    /// Fetch the inclusion distances of the latest attestations of all managed validators from
+    /// the validator monitor of the beacon node.
+    ///
+    /// This endpoint is specific to Lighthouse beacon nodes, which must also be monitoring the
+    /// validators (e.g. with `--validator-monitor-auto`). Validators the beacon node does not
+    /// report on are not labelled.
+    async fn update_inclusion_distance_metrics(&self) {
+        let log = self.context.log();
+
+        let local_indices: HashSet<u64> = if self.duties_service.per_validator_metrics() {
+            self.validator_store
+                .voting_pubkeys::<Vec<_>, _>(DoppelgangerStatus::ignored)
+                .iter()
+                .filter_map(|pubkey| self.validator_store.validator_index(pubkey))
+                .collect()
+        } else {
+            HashSet::new()
+        };
+
+        let response = if local_indices.is_empty() {
+            None
+        } else {
+            let indices = local_indices.iter().copied().collect::<Vec<_>>();
+            match self
+                .beacon_nodes
+                .first_success(|beacon_node| {
+                    let indices = indices.clone();
+                    async move {
+                        match beacon_node
+                            .post_lighthouse_ui_validator_metrics(indices)
+                            .await
+                        {
+                            Ok(response) => Ok(Some(response.data)),
+                            // The endpoint is not served, which is not an error of the beacon
+                            // node.
+                            Err(e) if e.status() == Some(StatusCode::NOT_FOUND) => Ok(None),
+                            Err(e) => Err(e),
+                        }
+                    }
+                })
+                .await
+            {
+                Ok(Some(response)) => Some(response),
+                Ok(None) => {
+                    info!(
+                        log,
+                        "Beacon node does not report attestation inclusion distances";
+                        "info" => "the attestation inclusion distance metric is disabled",
+                    );
+                    self.inclusion_distance_unsupported
+                        .store(true, Ordering::Relaxed);
+                    None
+                }
+                Err(e) => {
+                    debug!(
+                        log,
+                        "Unable to fetch attestation inclusion distances";
+                        "error" => %e,
+                    );
+                    None
+                }
+            }
+        };
+
+        set_inclusion_distance_metrics(
+            &mut self.inclusion_distance_metric_labels.lock(),
+            &local_indices,
+            response.as_ref(),
+        );
+    }
+}
+
+/// Sets the `ATTESTATION_INCLUSION_DISTANCE` metric of the `local_indices` for which `response`
+/// reports an included attestation, and removes the labels of validators which are no longer
+/// managed, e.g. because they have been disabled.
+///
+/// `labelled` tracks the validators currently labelled in the metric.
+fn set_inclusion_distance_metrics(
+    labelled: &mut HashSet<u64>,
+    local_indices: &HashSet<u64>,
+    response: Option<&ValidatorMetricsResponse>,
+) {
+    labelled.retain(|validator_index| {
+        if local_indices.contains(validator_index) {
+            return true;
+        }
+        if let Ok(gauge_vec) = &*validator_metrics::ATTESTATION_INCLUSION_DISTANCE {
+            let _ = gauge_vec.remove_label_values(&[&validator_index.to_string()]);
+        }
+        false
+    });
+
+    for (id, metrics) in response
+        .into_iter()
+        .flat_map(|response| &response.validators)
+    {
+        let Ok(validator_index) = id.parse::<u64>() else {
+            continue;
+        };
+        // A distance of zero means that no attestation of the validator has been included yet.
+        let distance = metrics.latest_attestation_inclusion_distance;
+        if !local_indices.contains(&validator_index) || distance == 0 {
+            continue;
+        }
+        validator_metrics::set_int_gauge(
+            &validator_metrics::ATTESTATION_INCLUSION_DISTANCE,
+            &[id],
+            distance as i64,
+        );
+        labelled.insert(validator_index);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use eth2::lighthouse::ValidatorMetrics;
     use futures::future::FutureExt;
     use parking_lot::RwLock;
 
@@ -751,4 +902,70 @@ mod tests {
             "state should have been updated"
         );
     }
+
+    fn monitor_metrics(latest_attestation_inclusion_distance: u64) -> ValidatorMetrics {
+        ValidatorMetrics {
+            attestation_hits: 0,
+            attestation_misses: 0,
+            attestation_hit_percentage: 0.0,
+            attestation_head_hits: 0,
+            attestation_head_misses: 0,
+            attestation_head_hit_percentage: 0.0,
+            attestation_target_hits: 0,
+            attestation_target_misses: 0,
+            attestation_target_hit_percentage: 0.0,
+            latest_attestation_inclusion_distance,
+        }
+    }
+
+    fn inclusion_distance(validator_index: u64) -> Option<i64> {
+        validator_metrics::get_int_gauge(
+            &validator_metrics::ATTESTATION_INCLUSION_DISTANCE,
+            &[&validator_index.to_string()],
+        )
+        .map(|gauge| gauge.get())
+    }
+
+    fn has_inclusion_distance_label(validator_index: u64) -> bool {
+        validator_metrics::gather()
+            .iter()
+            .filter(|family| family.get_name() == "vc_attestation_inclusion_distance")
+            .flat_map(|family| family.get_metric())
+            .any(|metric| {
+                metric
+                    .get_label()
+                    .iter()
+                    .any(|label| label.get_value() == validator_index.to_string())
+            })
+    }
+
+    #[test]
+    fn inclusion_distance_metric_removes_stale_validators() {
+        // Use indices which are unlikely to clash with other tests sharing the global registry.
+        let (included, unreported, disabled) = (1_000_001, 1_000_002, 1_000_003);
+        let mut labelled = HashSet::new();
+
+        let response = ValidatorMetricsResponse {
+            validators: HashMap::from([
+                (included.to_string(), monitor_metrics(2)),
+                (unreported.to_string(), monitor_metrics(0)),
+                (disabled.to_string(), monitor_metrics(1)),
+            ]),
+        };
+        let local_indices = HashSet::from([included, unreported, disabled]);
+        set_inclusion_distance_metrics(&mut labelled, &local_indices, Some(&response));
+
+        assert_eq!(inclusion_distance(included), Some(2));
+        assert_eq!(inclusion_distance(disabled), Some(1));
+        assert!(!has_inclusion_distance_label(unreported));
+        assert_eq!(labelled, HashSet::from([included, disabled]));
+
+        // The labels of disabled validators are removed even if the beacon node is unavailable.
+        let local_indices = HashSet::from([included, unreported]);
+        set_inclusion_distance_metrics(&mut labelled, &local_indices, None);
+
+        assert!(has_inclusion_distance_label(included));
+        assert!(!has_inclusion_distance_label(disabled));
+        assert_eq!(labelled, HashSet::from([included]));
+    }
 }
@@ -333,7 +333,16 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         unsigned_block: UnsignedBlock<E>,
     ) -> Result<(), BlockError> {
         let log = self.context.log();
-        let signing_timer = validator_metrics::start_timer(&validator_metrics::BLOCK_SIGNING_TIMES);
+        let fork_name = self
+            .context
+            .eth2_config
+            .spec
+            .fork_name_at_slot::<E>(slot)
+            .to_string();
+        let signing_timer = validator_metrics::start_timer_vec(
+            &validator_metrics::BLOCK_SIGNING_TIMES,
+            &[&fork_name],
+        );
 
         let res = match unsigned_block {
             UnsignedBlock::Full(block_contents) => {
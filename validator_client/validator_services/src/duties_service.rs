@@ -14,9 +14,10 @@ use doppelganger_service::DoppelgangerStatus;
 use environment::RuntimeContext;
 use eth2::types::{
     AttesterData, BeaconCommitteeSubscription, DutiesResponse, ProposerData, StateId, ValidatorId,
+    ValidatorStatus,
 };
 use futures::{stream, StreamExt};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use safe_arith::{ArithError, SafeArith};
 use slog::{debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
@@ -27,7 +28,9 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::{sync::mpsc::Sender, time::sleep};
 use types::{ChainSpec, Epoch, EthSpec, Hash256, PublicKeyBytes, SelectionProof, Slot};
-use validator_metrics::{get_int_gauge, set_int_gauge, ATTESTATION_DUTY};
+use validator_metrics::{
+    get_int_gauge, set_int_gauge, ATTESTATION_DUTY, VC_METRIC_CARDINALITY_CAPPED,
+};
 use validator_store::{Error as ValidatorStoreError, ValidatorStore};
 
 /// Only retain `HISTORICAL_DUTIES_EPOCHS` duties prior to the current epoch.
@@ -49,6 +52,9 @@ const SELECTION_PROOF_SCHEDULE_DENOM: u32 = 2;
 /// flag in the cli to enable collection of per validator metrics.
 const VALIDATOR_METRICS_MIN_COUNT: usize = 64;
 
+/// Default maximum number of distinct validators for which per-validator metrics are published.
+pub const DEFAULT_VALIDATOR_METRICS_CARDINALITY_CAP: usize = 10_000;
+
 /// The number of validators to request duty information for in the initial request.
 /// The initial request is used to determine if further requests are required, so that it
 /// reduces the amount of data that needs to be transferred.
@@ -94,6 +100,10 @@ pub enum Error {
     InvalidModulo(#[allow(dead_code)] ArithError),
     Arith(#[allow(dead_code)] ArithError),
     SyncDutiesNotFound(#[allow(dead_code)] u64),
+    FailedToDownloadValidatorStatuses(#[allow(dead_code)] String),
+    FailedToDownloadProposers(#[allow(dead_code)] String),
+    UnknownPubkey(#[allow(dead_code)] PublicKeyBytes),
+    UnknownValidatorIndex(#[allow(dead_code)] PublicKeyBytes),
 }
 
 impl From<ArithError> for Error {
@@ -199,6 +209,47 @@ impl SubscriptionSlots {
     }
 }
 
+/// Bounds the number of distinct `validator` label values of a per-validator metric.
+///
+/// Once `cap` distinct validators have been labelled, no further validators are added so that
+/// validator clients with many keys do not produce metric families too large to be scraped.
+pub struct CappedValidatorLabels {
+    cap: usize,
+    validators: HashSet<u64>,
+    capped: bool,
+}
+
+impl CappedValidatorLabels {
+    pub fn new(cap: usize) -> Self {
+        Self {
+            cap,
+            validators: HashSet::new(),
+            capped: false,
+        }
+    }
+
+    /// Returns `true` if a metric may be published for `validator_index`, i.e. if the validator is
+    /// already labelled or the cap has not been reached yet.
+    pub fn permit(&mut self, validator_index: u64) -> bool {
+        if self.validators.contains(&validator_index) {
+            return true;
+        }
+
+        if self.validators.len() >= self.cap {
+            self.capped = true;
+            return false;
+        }
+
+        self.validators.insert(validator_index);
+        true
+    }
+
+    /// Returns `true` if a validator has been refused a label because of the cap.
+    pub fn is_capped(&self) -> bool {
+        self.capped
+    }
+}
+
 /// To assist with readability, the dependent root for attester/proposer duties.
 type DependentRoot = Hash256;
 
@@ -228,6 +279,8 @@ pub struct DutiesService<T, E: EthSpec> {
     pub spec: Arc<ChainSpec>,
     //// Whether we permit large validator counts in the metrics.
     pub enable_high_validator_count_metrics: bool,
+    /// The validators labelled in the `ATTESTATION_DUTY` metric.
+    pub attestation_duty_metric_labels: Mutex<CappedValidatorLabels>,
     /// If this validator is running in distributed mode.
     pub distributed: bool,
 }
@@ -354,13 +407,32 @@ pub fn start_update_service<T: SlotClock + 'static, E: EthSpec>(
      * Spawn the task which updates the map of pubkey to validator index.
      */
     let duties_service = core_duties_service.clone();
+    let log = core_duties_service.context.log().clone();
     core_duties_service.context.executor.spawn(
         async move {
+            let mut active_count_epoch = None;
             loop {
                 // Run this poll before the wait, this should hopefully download all the indices
                 // before the block/attestation tasks need them.
                 poll_validator_indices(&duties_service).await;
 
+                // Validator statuses only change at epoch boundaries, so refresh the count of
+                // active validators once per epoch.
+                let current_epoch = duties_service
+                    .slot_clock
+                    .now()
+                    .map(|slot| slot.epoch(E::slots_per_epoch()));
+                if current_epoch.is_some() && current_epoch != active_count_epoch {
+                    match poll_active_validators_count(&duties_service).await {
+                        Ok(()) => active_count_epoch = current_epoch,
+                        Err(e) => error!(
+                            log,
+                            "Failed to update active validators count";
+                            "error" => ?e
+                        ),
+                    }
+                }
+
                 if let Some(duration) = duties_service.slot_clock.duration_to_next_slot() {
                     sleep(duration).await;
                 } else {
@@ -591,6 +663,55 @@ async fn poll_validator_indices<T: SlotClock + 'static, E: EthSpec>(
     }
 }
 
+/// Query the beacon node for the statuses of all validators with known indices and record how
+/// many of them are active on-chain.
+async fn poll_active_validators_count<T: SlotClock + 'static, E: EthSpec>(
+    duties_service: &DutiesService<T, E>,
+) -> Result<(), Error> {
+    let local_pubkeys: Vec<_> = duties_service
+        .validator_store
+        .voting_pubkeys(DoppelgangerStatus::ignored);
+
+    let local_indices: Vec<_> = {
+        let vals_ref = duties_service.validator_store.initialized_validators();
+        let vals = vals_ref.read();
+        local_pubkeys
+            .iter()
+            .filter_map(|pubkey| vals.get_index(pubkey))
+            .map(ValidatorId::Index)
+            .collect()
+    };
+
+    let active_count = if local_indices.is_empty() {
+        0
+    } else {
+        duties_service
+            .beacon_nodes
+            .first_success(|beacon_node| {
+                let local_indices = local_indices.clone();
+                async move {
+                    beacon_node
+                        .post_beacon_states_validators(
+                            StateId::Head,
+                            Some(local_indices),
+                            Some(vec![ValidatorStatus::Active]),
+                        )
+                        .await
+                }
+            })
+            .await
+            .map_err(|e| Error::FailedToDownloadValidatorStatuses(e.to_string()))?
+            .map_or(0, |response| response.data.len())
+    };
+
+    validator_metrics::set_gauge(
+        &validator_metrics::VC_VALIDATORS_ACTIVE_COUNT,
+        active_count as i64,
+    );
+
+    Ok(())
+}
+
 /// Query the beacon node for attestation duties for any known validators.
 ///
 /// This function will perform (in the following order):
@@ -987,11 +1108,16 @@ fn update_per_validator_duty_metrics<T: SlotClock + 'static, E: EthSpec>(
 ) {
     if duties_service.per_validator_metrics() {
         let attesters = duties_service.attesters.read();
+        let mut metric_labels = duties_service.attestation_duty_metric_labels.lock();
+        let was_capped = metric_labels.is_capped();
         attesters.values().for_each(|attester_duties_by_epoch| {
             if let Some((_, duty_and_proof)) = attester_duties_by_epoch.get(&epoch) {
                 let duty = &duty_and_proof.duty;
                 let validator_index = duty.validator_index;
                 let duty_slot = duty.slot;
+                if !metric_labels.permit(validator_index) {
+                    return;
+                }
                 if let Some(existing_slot_gauge) =
                     get_int_gauge(&ATTESTATION_DUTY, &[&validator_index.to_string()])
                 {
@@ -1018,6 +1144,22 @@ fn update_per_validator_duty_metrics<T: SlotClock + 'static, E: EthSpec>(
                 }
             }
         });
+
+        if metric_labels.is_capped() && !was_capped {
+            set_int_gauge(
+                &VC_METRIC_CARDINALITY_CAPPED,
+                &["vc_attestation_duty_slot"],
+                1,
+            );
+            warn!(
+                duties_service.context.log(),
+                "Per-validator metrics capped";
+                "msg" => "some validators are missing from the metric, consider raising \
+                    --validator-metrics-cardinality-cap",
+                "metric" => "vc_attestation_duty_slot",
+                "cap" => metric_labels.cap,
+            );
+        }
     }
 }
 
@@ -1260,58 +1402,14 @@ async fn poll_beacon_proposers<T: SlotClock + 'static, E: EthSpec>(
     // Only download duties and push out additional block production events if we have some
     // validators.
     if !local_pubkeys.is_empty() {
-        let download_result = duties_service
-            .beacon_nodes
-            .first_success(|beacon_node| async move {
-                let _timer = validator_metrics::start_timer_vec(
-                    &validator_metrics::DUTIES_SERVICE_TIMES,
-                    &[validator_metrics::PROPOSER_DUTIES_HTTP_GET],
-                );
-                beacon_node
-                    .get_validator_duties_proposer(current_epoch)
-                    .await
-            })
-            .await;
-
-        match download_result {
-            Ok(response) => {
-                let dependent_root = response.dependent_root;
-
-                let relevant_duties = response
-                    .data
-                    .into_iter()
-                    .filter(|proposer_duty| local_pubkeys.contains(&proposer_duty.pubkey))
-                    .collect::<Vec<_>>();
-
-                debug!(
-                    log,
-                    "Downloaded proposer duties";
-                    "dependent_root" => %dependent_root,
-                    "num_relevant_duties" => relevant_duties.len(),
-                );
-
-                if let Some((prior_dependent_root, _)) = duties_service
-                    .proposers
-                    .write()
-                    .insert(current_epoch, (dependent_root, relevant_duties))
-                {
-                    if dependent_root != prior_dependent_root {
-                        warn!(
-                            log,
-                            "Proposer duties re-org";
-                            "prior_dependent_root" => %prior_dependent_root,
-                            "dependent_root" => %dependent_root,
-                            "msg" => "this may happen from time to time"
-                        )
-                    }
-                }
-            }
-            // Don't return early here, we still want to try and produce blocks using the cached values.
-            Err(e) => error!(
+        // Don't return early here, we still want to try and produce blocks using the cached values.
+        if let Err(e) = update_proposer_duties(duties_service, current_epoch, &local_pubkeys).await
+        {
+            error!(
                 log,
                 "Failed to download proposer duties";
-                "err" => %e,
-            ),
+                "err" => ?e,
+            )
         }
 
         // Compute the block proposers for this slot again, now that we've received an update from
@@ -1356,6 +1454,115 @@ async fn poll_beacon_proposers<T: SlotClock + 'static, E: EthSpec>(
     Ok(())
 }
 
+/// Download the proposer duties for `epoch` and store those of `local_pubkeys` in
+/// `duties_service.proposers`.
+async fn update_proposer_duties<T: SlotClock + 'static, E: EthSpec>(
+    duties_service: &DutiesService<T, E>,
+    epoch: Epoch,
+    local_pubkeys: &HashSet<PublicKeyBytes>,
+) -> Result<(), Error> {
+    let log = duties_service.context.log();
+
+    let response = duties_service
+        .beacon_nodes
+        .first_success(|beacon_node| async move {
+            let _timer = validator_metrics::start_timer_vec(
+                &validator_metrics::DUTIES_SERVICE_TIMES,
+                &[validator_metrics::PROPOSER_DUTIES_HTTP_GET],
+            );
+            beacon_node.get_validator_duties_proposer(epoch).await
+        })
+        .await
+        .map_err(|e| Error::FailedToDownloadProposers(e.to_string()))?;
+
+    let dependent_root = response.dependent_root;
+
+    let relevant_duties = response
+        .data
+        .into_iter()
+        .filter(|proposer_duty| local_pubkeys.contains(&proposer_duty.pubkey))
+        .collect::<Vec<_>>();
+
+    debug!(
+        log,
+        "Downloaded proposer duties";
+        "dependent_root" => %dependent_root,
+        "num_relevant_duties" => relevant_duties.len(),
+    );
+
+    if let Some((prior_dependent_root, _)) = duties_service
+        .proposers
+        .write()
+        .insert(epoch, (dependent_root, relevant_duties))
+    {
+        if dependent_root != prior_dependent_root {
+            warn!(
+                log,
+                "Proposer duties re-org";
+                "prior_dependent_root" => %prior_dependent_root,
+                "dependent_root" => %dependent_root,
+                "msg" => "this may happen from time to time"
+            )
+        }
+    }
+
+    Ok(())
+}
+
+/// Immediately download the attester and proposer duties of the validator with `pubkey` rather
+/// than waiting for the next poll, e.g. after the validator has been added.
+///
+/// Attester duties are refreshed for the current and next epoch, proposer duties for the current
+/// epoch.
+pub async fn refresh_validator_duties<T: SlotClock + 'static, E: EthSpec>(
+    duties_service: &Arc<DutiesService<T, E>>,
+    pubkey: PublicKeyBytes,
+) -> Result<(), Error> {
+    let _timer = validator_metrics::start_timer_vec(
+        &validator_metrics::DUTIES_SERVICE_TIMES,
+        &[validator_metrics::REFRESH_VALIDATOR_DUTIES],
+    );
+
+    if !duties_service.validator_store.has_validator(&pubkey) {
+        return Err(Error::UnknownPubkey(pubkey));
+    }
+
+    // Resolve the index of the validator without waiting for its next scheduled poll.
+    if duties_service
+        .validator_store
+        .validator_index(&pubkey)
+        .is_none()
+    {
+        duties_service
+            .unknown_validator_next_poll_slots
+            .write()
+            .remove(&pubkey);
+        poll_validator_indices(duties_service).await;
+    }
+    let validator_index = duties_service
+        .validator_store
+        .validator_index(&pubkey)
+        .ok_or(Error::UnknownValidatorIndex(pubkey))?;
+
+    let current_epoch = duties_service
+        .slot_clock
+        .now()
+        .ok_or(Error::UnableToReadSlotClock)?
+        .epoch(E::slots_per_epoch());
+
+    let local_pubkeys = HashSet::from([pubkey]);
+    for epoch in [current_epoch, current_epoch + 1] {
+        poll_beacon_attesters_for_epoch(duties_service, epoch, &[validator_index], &local_pubkeys)
+            .await?;
+    }
+
+    // Proposer duties are stored for all local validators at once.
+    let local_pubkeys: HashSet<_> = duties_service
+        .validator_store
+        .voting_pubkeys(DoppelgangerStatus::ignored);
+    update_proposer_duties(duties_service, current_epoch, &local_pubkeys).await
+}
+
 /// Notify the block service if it should produce a block.
 async fn notify_block_production_service<T: SlotClock + 'static, E: EthSpec>(
     current_slot: Slot,
@@ -1392,6 +1599,22 @@ async fn notify_block_production_service<T: SlotClock + 'static, E: EthSpec>(
 mod test {
     use super::*;
 
+    #[test]
+    fn capped_validator_labels() {
+        let mut labels = CappedValidatorLabels::new(2);
+
+        assert!(labels.permit(1));
+        assert!(labels.permit(2));
+        assert!(!labels.is_capped());
+
+        // Validators beyond the cap are refused, known validators are still permitted.
+        assert!(!labels.permit(3));
+        assert!(labels.is_capped());
+        assert!(labels.permit(1));
+        assert!(labels.permit(2));
+        assert!(!labels.permit(3));
+    }
+
     #[test]
     fn subscription_slots_exact() {
         // Set current slot in the past so no duties are considered expired.
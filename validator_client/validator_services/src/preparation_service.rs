@@ -478,11 +478,19 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
                     })
                     .await
                 {
-                    Ok(()) => info!(
-                        log,
-                        "Published validator registrations to the builder network";
-                        "count" => batch.len(),
-                    ),
+                    Ok(()) => {
+                        if let Ok(now) = SystemTime::now().duration_since(UNIX_EPOCH) {
+                            validator_metrics::set_gauge(
+                                &validator_metrics::BUILDER_LAST_REGISTRATION_TIMESTAMP,
+                                now.as_secs() as i64,
+                            );
+                        }
+                        info!(
+                            log,
+                            "Published validator registrations to the builder network";
+                            "count" => batch.len(),
+                        )
+                    }
                     Err(e) => warn!(
                         log,
                         "Unable to publish validator registrations to the builder network";
@@ -1,14 +1,20 @@
+mod signing_ledger;
+
+pub use signing_ledger::{SignedMessageType, Signing, SIGNING_LEDGER_EPOCHS};
+
 use account_utils::validator_definitions::{PasswordStorage, ValidatorDefinition};
 use doppelganger_service::{DoppelgangerService, DoppelgangerStatus, DoppelgangerValidatorStore};
 use initialized_validators::InitializedValidators;
 use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
+use signing_ledger::SigningLedger;
 use signing_method::{Error as SigningError, SignableMessage, SigningContext, SigningMethod};
 use slashing_protection::{
     interchange::Interchange, InterchangeError, NotSafe, Safe, SlashingDatabase,
 };
 use slog::{crit, error, info, warn, Logger};
 use slot_clock::SlotClock;
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::path::Path;
 use std::sync::Arc;
@@ -56,6 +62,9 @@ pub struct Config {
     pub prefer_builder_proposals: bool,
     /// Specifies the boost factor, a percentage multiplier to apply to the builder's payload value.
     pub builder_boost_factor: Option<u64>,
+    /// Record the blocks, attestations and sync committee messages signed by each validator over
+    /// the most recent epochs.
+    pub enable_signing_ledger: bool,
 }
 
 /// A helper struct, used for passing data from the validator store to services.
@@ -91,6 +100,7 @@ pub struct ValidatorStore<T, E: EthSpec> {
     enable_web3signer_slashing_protection: bool,
     prefer_builder_proposals: bool,
     builder_boost_factor: Option<u64>,
+    signing_ledger: Option<Mutex<SigningLedger>>,
     task_executor: TaskExecutor,
     _phantom: PhantomData<E>,
 }
@@ -131,6 +141,9 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             enable_web3signer_slashing_protection: config.enable_web3signer_slashing_protection,
             prefer_builder_proposals: config.prefer_builder_proposals,
             builder_boost_factor: config.builder_boost_factor,
+            signing_ledger: config
+                .enable_signing_ledger
+                .then(|| Mutex::new(SigningLedger::default())),
             task_executor,
             _phantom: PhantomData,
         }
@@ -150,6 +163,27 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         Ok(())
     }
 
+    /// Returns the messages signed by each validator in `epoch`, or `None` if the signing ledger is
+    /// disabled. Validators which signed nothing are omitted.
+    pub fn signings_at_epoch(&self, epoch: Epoch) -> Option<HashMap<PublicKeyBytes, Vec<Signing>>> {
+        self.signing_ledger
+            .as_ref()
+            .map(|signing_ledger| signing_ledger.lock().signings_at_epoch(epoch))
+    }
+
+    fn record_signing(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        message_type: SignedMessageType,
+        slot: Slot,
+    ) {
+        if let Some(signing_ledger) = &self.signing_ledger {
+            signing_ledger
+                .lock()
+                .record::<E>(validator_pubkey, message_type, slot);
+        }
+    }
+
     /// Returns `true` if doppelganger protection is enabled, or else `false`.
     pub fn doppelganger_protection_enabled(&self) -> bool {
         self.doppelganger_service.is_some()
@@ -597,6 +631,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         let domain_hash = signing_context.domain_hash(&self.spec);
 
         let signing_method = self.doppelganger_checked_signing_method(validator_pubkey)?;
+        let fork_name = self.spec.fork_name_at_slot::<E>(block.slot()).to_string();
 
         // Check for slashing conditions.
         let slashing_status = if signing_method
@@ -616,7 +651,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             Ok(Safe::Valid) => {
                 validator_metrics::inc_counter_vec(
                     &validator_metrics::SIGNED_BLOCKS_TOTAL,
-                    &[validator_metrics::SUCCESS],
+                    &[validator_metrics::SUCCESS, &fork_name],
                 );
 
                 let signature = signing_method
@@ -627,6 +662,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                         &self.task_executor,
                     )
                     .await?;
+                self.record_signing(validator_pubkey, SignedMessageType::Block, block.slot());
                 Ok(SignedBeaconBlock::from_block(block, signature))
             }
             Ok(Safe::SameData) => {
@@ -636,7 +672,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                 );
                 validator_metrics::inc_counter_vec(
                     &validator_metrics::SIGNED_BLOCKS_TOTAL,
-                    &[validator_metrics::SAME_DATA],
+                    &[validator_metrics::SAME_DATA, &fork_name],
                 );
                 Err(Error::SameData)
             }
@@ -649,7 +685,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                 );
                 validator_metrics::inc_counter_vec(
                     &validator_metrics::SIGNED_BLOCKS_TOTAL,
-                    &[validator_metrics::UNREGISTERED],
+                    &[validator_metrics::UNREGISTERED, &fork_name],
                 );
                 Err(Error::Slashable(NotSafe::UnregisteredValidator(pk)))
             }
@@ -661,7 +697,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                 );
                 validator_metrics::inc_counter_vec(
                     &validator_metrics::SIGNED_BLOCKS_TOTAL,
-                    &[validator_metrics::SLASHABLE],
+                    &[validator_metrics::SLASHABLE, &fork_name],
                 );
                 Err(Error::Slashable(e))
             }
@@ -716,6 +752,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                 attestation
                     .add_signature(&signature, validator_committee_position)
                     .map_err(Error::UnableToSignAttestation)?;
+                self.record_signing(
+                    validator_pubkey,
+                    SignedMessageType::Attestation,
+                    attestation.data().slot,
+                );
 
                 validator_metrics::inc_counter_vec(
                     &validator_metrics::SIGNED_ATTESTATIONS_TOTAL,
@@ -963,6 +1004,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             &validator_metrics::SIGNED_SYNC_COMMITTEE_MESSAGES_TOTAL,
             &[validator_metrics::SUCCESS],
         );
+        self.record_signing(
+            *validator_pubkey,
+            SignedMessageType::SyncCommitteeMessage,
+            slot,
+        );
 
         Ok(SyncCommitteeMessage {
             slot,
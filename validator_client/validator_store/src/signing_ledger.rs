@@ -0,0 +1,141 @@
+//! An opt-in record of the messages signed by each validator over the most recent epochs.
+//!
+//! The `SIGNED_*_TOTAL` metrics count the signings of all validators together. The ledger records
+//! which validator signed which message for which slot, so operators can confirm that each of
+//! their validators performed its duties in an epoch.
+
+use std::collections::{BTreeMap, HashMap};
+use types::{Epoch, EthSpec, PublicKeyBytes, Slot};
+
+/// The number of most recent epochs kept in the ledger.
+pub const SIGNING_LEDGER_EPOCHS: u64 = 4;
+
+/// The type of a message recorded in the ledger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignedMessageType {
+    Block,
+    Attestation,
+    SyncCommitteeMessage,
+}
+
+impl SignedMessageType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SignedMessageType::Block => "block",
+            SignedMessageType::Attestation => "attestation",
+            SignedMessageType::SyncCommitteeMessage => "sync_committee_message",
+        }
+    }
+}
+
+/// A message signed by a validator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signing {
+    pub message_type: SignedMessageType,
+    pub slot: Slot,
+}
+
+/// The messages signed by each validator, per epoch.
+#[derive(Default)]
+pub struct SigningLedger {
+    epochs: BTreeMap<Epoch, HashMap<PublicKeyBytes, Vec<Signing>>>,
+}
+
+impl SigningLedger {
+    /// Records that `pubkey` signed a message of `message_type` for `slot`.
+    ///
+    /// Signings for epochs older than the `SIGNING_LEDGER_EPOCHS` most recent ones are ignored.
+    pub fn record<E: EthSpec>(
+        &mut self,
+        pubkey: PublicKeyBytes,
+        message_type: SignedMessageType,
+        slot: Slot,
+    ) {
+        let epoch = slot.epoch(E::slots_per_epoch());
+        let latest_epoch = self
+            .epochs
+            .keys()
+            .next_back()
+            .map_or(epoch, |latest_epoch| std::cmp::max(*latest_epoch, epoch));
+        let oldest_epoch = (latest_epoch + 1).saturating_sub(SIGNING_LEDGER_EPOCHS);
+        if epoch < oldest_epoch {
+            return;
+        }
+
+        self.epochs
+            .entry(epoch)
+            .or_default()
+            .entry(pubkey)
+            .or_default()
+            .push(Signing { message_type, slot });
+        self.epochs = self.epochs.split_off(&oldest_epoch);
+    }
+
+    /// Returns the messages signed by each validator in `epoch`. Validators which signed nothing
+    /// are omitted.
+    pub fn signings_at_epoch(&self, epoch: Epoch) -> HashMap<PublicKeyBytes, Vec<Signing>> {
+        self.epochs.get(&epoch).cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::MainnetEthSpec;
+
+    type E = MainnetEthSpec;
+
+    fn slot_at_epoch(epoch: u64) -> Slot {
+        Epoch::new(epoch).start_slot(E::slots_per_epoch())
+    }
+
+    #[test]
+    fn records_signings_per_validator() {
+        let mut ledger = SigningLedger::default();
+        let pubkey_a = PublicKeyBytes::empty();
+        let pubkey_b = PublicKeyBytes::deserialize(&[1; 48]).unwrap();
+        let slot = slot_at_epoch(2);
+
+        ledger.record::<E>(pubkey_a, SignedMessageType::Block, slot);
+        ledger.record::<E>(pubkey_a, SignedMessageType::Attestation, slot + 1);
+        ledger.record::<E>(pubkey_b, SignedMessageType::SyncCommitteeMessage, slot);
+
+        let signings = ledger.signings_at_epoch(Epoch::new(2));
+        assert_eq!(
+            signings[&pubkey_a],
+            vec![
+                Signing {
+                    message_type: SignedMessageType::Block,
+                    slot
+                },
+                Signing {
+                    message_type: SignedMessageType::Attestation,
+                    slot: slot + 1
+                }
+            ]
+        );
+        assert_eq!(signings[&pubkey_b].len(), 1);
+        assert!(ledger.signings_at_epoch(Epoch::new(1)).is_empty());
+    }
+
+    #[test]
+    fn prunes_old_epochs() {
+        let mut ledger = SigningLedger::default();
+        let pubkey = PublicKeyBytes::empty();
+        for epoch in 0..10 {
+            ledger.record::<E>(pubkey, SignedMessageType::Attestation, slot_at_epoch(epoch));
+        }
+
+        let oldest_epoch = 10 - SIGNING_LEDGER_EPOCHS;
+        assert!(ledger
+            .signings_at_epoch(Epoch::new(oldest_epoch - 1))
+            .is_empty());
+        for epoch in oldest_epoch..10 {
+            assert_eq!(ledger.signings_at_epoch(Epoch::new(epoch)).len(), 1);
+        }
+
+        // Signings older than the kept epochs are ignored.
+        ledger.record::<E>(pubkey, SignedMessageType::Block, slot_at_epoch(0));
+        assert!(ledger.signings_at_epoch(Epoch::new(0)).is_empty());
+    }
+}